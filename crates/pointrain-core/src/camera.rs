@@ -0,0 +1,25 @@
+//! Projection between depth (and color) images and point clouds with a pinhole camera.
+
+mod image;
+mod intrinsics;
+mod projection;
+
+pub use image::{Depth, Image};
+pub use intrinsics::{Distortion, PinholeIntrinsics};
+pub use projection::{depth_to_pc, pc_to_depth, pc_to_rgbd, rgbd_to_pc};
+
+/// A point cloud organized as a `width` x `height` image.
+#[derive(Debug, Clone)]
+pub struct OrganizedPointCloud<PC> {
+    /// Points in row-major order. Pixels without a valid depth have a NaN position.
+    pub pc: PC,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<PC> OrganizedPointCloud<PC> {
+    /// Index into `pc` of the point at pixel (`u`, `v`).
+    pub fn index(&self, u: usize, v: usize) -> usize {
+        v * self.width + u
+    }
+}
@@ -0,0 +1,88 @@
+use crate::types::Float;
+
+/// A row-major image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image<T> {
+    width: usize,
+    height: usize,
+    data: Vec<T>,
+}
+
+impl<T: Clone> Image<T> {
+    /// An image filled with `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Image<T> {
+    /// Wraps row-major pixels, `None` unless there are exactly `width * height` of them.
+    pub fn from_vec(width: usize, height: usize, data: Vec<T>) -> Option<Self> {
+        (data.len() == width * height).then_some(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn data_mut(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Pixel at column `u` and row `v`.
+    pub fn get(&self, u: usize, v: usize) -> Option<&T> {
+        (u < self.width && v < self.height).then(|| &self.data[v * self.width + u])
+    }
+
+    pub fn get_mut(&mut self, u: usize, v: usize) -> Option<&mut T> {
+        (u < self.width && v < self.height).then(|| &mut self.data[v * self.width + u])
+    }
+}
+
+/// A depth pixel, `u16` in millimeters or `f32` in meters. Zero means no measurement.
+pub trait Depth: Copy {
+    /// Depth in meters, `None` for a missing measurement.
+    fn to_meters(self) -> Option<Float>;
+    /// Nearest representable depth, saturating for `u16`.
+    fn from_meters(depth: Float) -> Self;
+}
+
+impl Depth for u16 {
+    fn to_meters(self) -> Option<Float> {
+        (self != 0).then(|| Float::from(self) / 1000.)
+    }
+
+    fn from_meters(depth: Float) -> Self {
+        (depth * 1000.).round() as u16
+    }
+}
+
+impl Depth for f32 {
+    fn to_meters(self) -> Option<Float> {
+        (self.is_finite() && self > 0.).then_some(self)
+    }
+
+    fn from_meters(depth: Float) -> Self {
+        depth
+    }
+}
@@ -0,0 +1,87 @@
+use nalgebra::Point2;
+
+use crate::types::{Float, Position};
+
+/// Brown-Conrady lens distortion, with the coefficients ordered as in OpenCV.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Distortion {
+    pub k1: Float,
+    pub k2: Float,
+    pub p1: Float,
+    pub p2: Float,
+    pub k3: Float,
+}
+
+impl Distortion {
+    /// Distorts normalized image coordinates.
+    pub fn distort(&self, p: Point2<Float>) -> Point2<Float> {
+        let (x, y) = (p.x, p.y);
+        let r2 = x * x + y * y;
+        let radial = 1. + r2 * (self.k1 + r2 * (self.k2 + r2 * self.k3));
+        Point2::new(
+            x * radial + 2. * self.p1 * x * y + self.p2 * (r2 + 2. * x * x),
+            y * radial + self.p1 * (r2 + 2. * y * y) + 2. * self.p2 * x * y,
+        )
+    }
+
+    /// Inverse of [`Self::distort`] by fixed-point iteration.
+    pub fn undistort(&self, p: Point2<Float>) -> Point2<Float> {
+        let mut u = p;
+        for _ in 0..20 {
+            let d = self.distort(u);
+            u += p - d;
+        }
+        u
+    }
+}
+
+/// Pinhole camera intrinsics in pixels. The camera looks along +z with x right and y down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PinholeIntrinsics {
+    pub fx: Float,
+    pub fy: Float,
+    pub cx: Float,
+    pub cy: Float,
+    pub distortion: Option<Distortion>,
+}
+
+impl PinholeIntrinsics {
+    pub fn new(fx: Float, fy: Float, cx: Float, cy: Float) -> Self {
+        Self {
+            fx,
+            fy,
+            cx,
+            cy,
+            distortion: None,
+        }
+    }
+
+    pub fn with_distortion(mut self, distortion: Distortion) -> Self {
+        self.distortion = Some(distortion);
+        self
+    }
+
+    /// Pixel coordinates of a point in the camera frame, `None` behind the camera.
+    pub fn project(&self, p: &Position) -> Option<Point2<Float>> {
+        if p.z.is_nan() || p.z <= 0. {
+            return None;
+        }
+        let mut n = Point2::new(p.x / p.z, p.y / p.z);
+        if let Some(d) = &self.distortion {
+            n = d.distort(n);
+        }
+        Some(Point2::new(
+            self.fx * n.x + self.cx,
+            self.fy * n.y + self.cy,
+        ))
+    }
+
+    /// Point in the camera frame seen at pixel coordinates `uv` with depth (z) `depth`.
+    pub fn unproject(&self, uv: Point2<Float>, depth: Float) -> Position {
+        let mut n = Point2::new((uv.x - self.cx) / self.fx, (uv.y - self.cy) / self.fy);
+        if let Some(d) = &self.distortion {
+            n = d.undistort(n);
+        }
+        Position::new(n.x * depth, n.y * depth, depth)
+    }
+}
@@ -0,0 +1,141 @@
+use nalgebra::Point2;
+
+use super::{Depth, Image, OrganizedPointCloud, PinholeIntrinsics};
+use crate::{
+    pc::{PointCloud, PointCloudBase, PointCloudRgb, PointCloudWithColor},
+    point::{Point, PointRgb},
+    types::{Float, Position, Rgb},
+};
+
+/// Back-projects every pixel of a depth image.
+pub fn depth_to_pc<D: Depth>(
+    depth: &Image<D>,
+    intrinsics: &PinholeIntrinsics,
+) -> OrganizedPointCloud<PointCloud> {
+    OrganizedPointCloud {
+        pc: unproject(depth, intrinsics)
+            .map(|position| Point { position })
+            .collect(),
+        width: depth.width(),
+        height: depth.height(),
+    }
+}
+
+/// Back-projects every pixel of a depth image and colors it from a registered color image.
+///
+/// # Panics
+///
+/// Panics if the images are of different sizes.
+pub fn rgbd_to_pc<D: Depth>(
+    depth: &Image<D>,
+    color: &Image<Rgb>,
+    intrinsics: &PinholeIntrinsics,
+) -> OrganizedPointCloud<PointCloudRgb> {
+    assert_eq!(
+        (depth.width(), depth.height()),
+        (color.width(), color.height()),
+        "depth and color images must be of the same size"
+    );
+
+    OrganizedPointCloud {
+        pc: unproject(depth, intrinsics)
+            .zip(color.data())
+            .map(|(position, &color)| PointRgb { position, color })
+            .collect(),
+        width: depth.width(),
+        height: depth.height(),
+    }
+}
+
+/// Renders the depth image of a point cloud given in the camera frame, keeping the nearest
+/// point of each pixel. Pixels without points are zero.
+pub fn pc_to_depth<PC, D>(
+    pc: &PC,
+    intrinsics: &PinholeIntrinsics,
+    width: usize,
+    height: usize,
+) -> Image<D>
+where
+    PC: PointCloudBase,
+    D: Depth,
+{
+    let zbuffer = zbuffer(pc.positions(), intrinsics, width, height);
+    depth_image(&zbuffer, width, height)
+}
+
+/// Renders the depth and color images of a point cloud given in the camera frame, see
+/// [`pc_to_depth`]. Pixels without points are black.
+pub fn pc_to_rgbd<PC, D>(
+    pc: &PC,
+    intrinsics: &PinholeIntrinsics,
+    width: usize,
+    height: usize,
+) -> (Image<D>, Image<Rgb>)
+where
+    PC: PointCloudWithColor,
+    D: Depth,
+{
+    let zbuffer = zbuffer(pc.positions(), intrinsics, width, height);
+    let colors = zbuffer
+        .iter()
+        .map(|p| p.map_or(Rgb::zeros(), |(i, _)| pc.colors()[i]))
+        .collect();
+
+    (
+        depth_image(&zbuffer, width, height),
+        Image::from_vec(width, height, colors).unwrap(),
+    )
+}
+
+fn unproject<'a, D: Depth>(
+    depth: &'a Image<D>,
+    intrinsics: &'a PinholeIntrinsics,
+) -> impl Iterator<Item = Position> + 'a {
+    depth.data().iter().enumerate().map(|(i, d)| {
+        let (u, v) = (i % depth.width(), i / depth.width());
+        match d.to_meters() {
+            Some(z) => intrinsics.unproject(Point2::new(u as Float, v as Float), z),
+            None => Position::new(Float::NAN, Float::NAN, Float::NAN),
+        }
+    })
+}
+
+/// Index and depth of the nearest point of each pixel.
+fn zbuffer(
+    positions: &[Position],
+    intrinsics: &PinholeIntrinsics,
+    width: usize,
+    height: usize,
+) -> Vec<Option<(usize, Float)>> {
+    let mut zbuffer = vec![None; width * height];
+
+    for (i, p) in positions.iter().enumerate() {
+        let Some(uv) = intrinsics.project(p) else {
+            continue;
+        };
+        let (u, v) = (uv.x.round(), uv.y.round());
+        // Also rejects NaN.
+        if !(u >= 0. && v >= 0. && u < width as Float && v < height as Float) {
+            continue;
+        }
+
+        let pixel = &mut zbuffer[v as usize * width + u as usize];
+        if pixel.map_or(true, |(_, z)| p.z < z) {
+            *pixel = Some((i, p.z));
+        }
+    }
+
+    zbuffer
+}
+
+fn depth_image<D: Depth>(
+    zbuffer: &[Option<(usize, Float)>],
+    width: usize,
+    height: usize,
+) -> Image<D> {
+    let depths = zbuffer
+        .iter()
+        .map(|p| D::from_meters(p.map_or(0., |(_, z)| z)))
+        .collect();
+    Image::from_vec(width, height, depths).unwrap()
+}
@@ -1,5 +1,10 @@
+pub mod camera;
 pub mod pc;
 pub mod point;
+pub mod range_image;
 pub mod types;
 
+#[cfg(feature = "serde")]
+mod serde_support;
+
 pub use nalgebra;
@@ -1,17 +1,27 @@
 mod base;
+mod convert;
+pub mod descriptor;
 pub mod intensity;
 pub mod intensity_normal;
 pub mod normal;
 pub mod rgb;
 pub mod rgb_normal;
+pub mod rgba;
 pub mod xyz;
 
 pub use base::{
-    PointCloudBase, PointCloudWithColor, PointCloudWithIntensity, PointCloudWithNormal,
+    PointCloudBase, PointCloudWithAlpha, PointCloudWithColor, PointCloudWithDescriptor,
+    PointCloudWithIntensity, PointCloudWithNormal,
 };
+pub use convert::ColumnLengthError;
+pub use descriptor::PointCloud as PointCloudDescriptor;
 pub use intensity::PointCloud as PointCloudIntensity;
 pub use intensity_normal::PointCloud as PointCloudIntensityNormal;
 pub use normal::PointCloud as PointCloudNormal;
 pub use rgb::PointCloud as PointCloudRgb;
 pub use rgb_normal::PointCloud as PointCloudRgbNormal;
+pub use rgba::PointCloud as PointCloudRgba;
 pub use xyz::PointCloud;
+
+pub type PointCloudFpfh = PointCloudDescriptor<33>;
+pub type PointCloudShot = PointCloudDescriptor<352>;
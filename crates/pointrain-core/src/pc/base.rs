@@ -7,7 +7,7 @@ use re_types::{
 
 use crate::{
     point::PointBase,
-    types::{Float, Normal, Position, Rgb},
+    types::{Descriptor, Float, Normal, Position, Rgb},
 };
 
 pub trait PointCloudBase: Default {
@@ -34,6 +34,102 @@ pub trait PointCloudBase: Default {
     fn iter(&self) -> Self::Iter<'_>;
     fn iter_mut(&mut self) -> Self::IterMut<'_>;
 
+    /// Copies the points at `indices`, in that order. Indices may repeat.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds.
+    fn select(&self, indices: &[usize]) -> Self {
+        // Refs are not `Clone`, so a repeated index walks the cloud again.
+        let mut points: Vec<_> = self.iter().map(Some).collect();
+        let mut pc = Self::with_capacity(indices.len());
+        for &i in indices {
+            let p = match points[i].take() {
+                Some(p) => p,
+                None => self.iter().nth(i).unwrap(),
+            };
+            pc.push_ref(p);
+        }
+        pc
+    }
+    /// Copies the points whose `mask` entry is `true`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mask` is not as long as the cloud.
+    fn filter_mask(&self, mask: &[bool]) -> Self {
+        assert_eq!(self.len(), mask.len(), "mask length mismatch");
+        let mut pc = Self::with_capacity(mask.iter().filter(|&&m| m).count());
+        for (p, _) in self.iter().zip(mask).filter(|(_, &m)| m) {
+            pc.push_ref(p);
+        }
+        pc
+    }
+    /// Keeps only the points for which `f` returns `true`, preserving their order.
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(<Self::Point as PointBase>::Ref<'_>) -> bool,
+    {
+        let mask: Vec<_> = self.iter().map(f).collect();
+        *self = self.filter_mask(&mask);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            let mut pc = Self::with_capacity(len);
+            for p in self.iter().take(len) {
+                pc.push_ref(p);
+            }
+            *self = pc;
+        }
+    }
+    /// Removes a point and returns it, replacing it by the last point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    fn swap_remove(&mut self, index: usize) -> Self::Point
+    where
+        Self::Point: for<'a> From<<Self::Point as PointBase>::Ref<'a>>,
+    {
+        let len = self.len();
+        assert!(index < len, "swap_remove index out of bounds");
+        let removed = self.iter().nth(index).unwrap().into();
+        let indices: Vec<_> = (0..len - 1)
+            .map(|i| if i == index { len - 1 } else { i })
+            .collect();
+        *self = self.select(&indices);
+        removed
+    }
+    /// Splits the cloud in two at `at`, returning the points from `at` on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is greater than the length of the cloud.
+    fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(at <= len, "split_off index out of bounds");
+        let mut tail = Self::with_capacity(len - at);
+        for p in self.iter().skip(at) {
+            tail.push_ref(p);
+        }
+        self.truncate(at);
+        tail
+    }
+    /// Moves all the points of `other` to the end of the cloud.
+    fn append(&mut self, other: &mut Self) {
+        for p in other.iter() {
+            self.push_ref(p);
+        }
+        *other = Self::new();
+    }
+
+    fn extend<I: IntoIterator<Item = Self::Point>>(&mut self, iter: I) {
+        for p in iter {
+            self.push(p);
+        }
+    }
+
     fn len(&self) -> usize {
         self.positions().len()
     }
@@ -97,6 +193,24 @@ pub trait PointCloudBase: Default {
     }
 }
 
+pub(super) fn select_column<T: Copy>(column: &[T], indices: &[usize]) -> Vec<T> {
+    indices.iter().map(|&i| column[i]).collect()
+}
+
+pub(super) fn filter_column<T: Copy>(column: &[T], mask: &[bool]) -> Vec<T> {
+    assert_eq!(column.len(), mask.len(), "mask length mismatch");
+    column
+        .iter()
+        .zip(mask)
+        .filter_map(|(v, &m)| m.then_some(*v))
+        .collect()
+}
+
+pub(super) fn retain_column<T>(column: &mut Vec<T>, mask: &[bool]) {
+    let mut mask = mask.iter();
+    column.retain(|_| *mask.next().unwrap());
+}
+
 pub trait PointCloudWithIntensity: PointCloudBase {
     fn intensities(&self) -> &[Float];
     fn intensities_mut(&mut self) -> &mut [Float];
@@ -152,3 +266,22 @@ pub trait PointCloudWithColor: PointCloudBase {
             .collect()
     }
 }
+
+pub trait PointCloudWithAlpha: PointCloudWithColor {
+    fn alphas(&self) -> &[u8];
+    fn alphas_mut(&mut self) -> &mut [u8];
+
+    #[cfg(feature = "rerun")]
+    fn rerun_colors_with_alpha(&self) -> Vec<Color> {
+        self.colors()
+            .iter()
+            .zip(self.alphas())
+            .map(|(c, a)| Color::from_unmultiplied_rgba(c.x, c.y, c.z, *a))
+            .collect()
+    }
+}
+
+pub trait PointCloudWithDescriptor<const N: usize>: PointCloudBase {
+    fn descriptors(&self) -> &[Descriptor<N>];
+    fn descriptors_mut(&mut self) -> &mut [Descriptor<N>];
+}
@@ -0,0 +1,458 @@
+//! Conversions between point cloud types, moving the columns they share.
+//!
+//! Narrowing drops columns. Widening adds default columns (zero, opaque for alpha) through
+//! `From`, or given ones through the `with_*` methods and the matching `TryFrom<(pc, column)>`.
+
+use std::fmt;
+
+use super::{
+    PointCloud, PointCloudBase, PointCloudDescriptor, PointCloudIntensity,
+    PointCloudIntensityNormal, PointCloudNormal, PointCloudRgb, PointCloudRgbNormal,
+    PointCloudRgba,
+};
+use crate::types::{Descriptor, Float, Normal, Rgb};
+
+/// A column given for a point cloud has a different number of elements than it has points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnLengthError {
+    pub column: &'static str,
+    pub len: usize,
+    pub expected: usize,
+}
+
+impl fmt::Display for ColumnLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} has {} elements, expected {}",
+            self.column, self.len, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ColumnLengthError {}
+
+/// Fails unless every column has `len` elements.
+pub(crate) fn check_columns(
+    len: usize,
+    columns: &[(&'static str, usize)],
+) -> Result<(), ColumnLengthError> {
+    match columns.iter().find(|(_, l)| *l != len) {
+        Some(&(column, l)) => Err(ColumnLengthError {
+            column,
+            len: l,
+            expected: len,
+        }),
+        None => Ok(()),
+    }
+}
+
+impl From<PointCloudIntensity> for PointCloud {
+    fn from(pc: PointCloudIntensity) -> Self {
+        Self {
+            positions: pc.positions,
+        }
+    }
+}
+
+impl From<PointCloudNormal> for PointCloud {
+    fn from(pc: PointCloudNormal) -> Self {
+        Self {
+            positions: pc.positions,
+        }
+    }
+}
+
+impl From<PointCloudRgb> for PointCloud {
+    fn from(pc: PointCloudRgb) -> Self {
+        Self {
+            positions: pc.positions,
+        }
+    }
+}
+
+impl<const N: usize> From<PointCloudDescriptor<N>> for PointCloud {
+    fn from(pc: PointCloudDescriptor<N>) -> Self {
+        Self {
+            positions: pc.positions,
+        }
+    }
+}
+
+impl From<PointCloudIntensityNormal> for PointCloudIntensity {
+    fn from(pc: PointCloudIntensityNormal) -> Self {
+        Self {
+            positions: pc.positions,
+            intensities: pc.intensities,
+        }
+    }
+}
+
+impl From<PointCloudIntensityNormal> for PointCloudNormal {
+    fn from(pc: PointCloudIntensityNormal) -> Self {
+        Self {
+            positions: pc.positions,
+            normals: pc.normals,
+            curvatures: pc.curvatures,
+        }
+    }
+}
+
+impl From<PointCloudRgbNormal> for PointCloudNormal {
+    fn from(pc: PointCloudRgbNormal) -> Self {
+        Self {
+            positions: pc.positions,
+            normals: pc.normals,
+            curvatures: pc.curvatures,
+        }
+    }
+}
+
+impl From<PointCloudRgbNormal> for PointCloudRgb {
+    fn from(pc: PointCloudRgbNormal) -> Self {
+        Self {
+            positions: pc.positions,
+            colors: pc.colors,
+        }
+    }
+}
+
+impl From<PointCloudRgba> for PointCloudRgb {
+    fn from(pc: PointCloudRgba) -> Self {
+        Self {
+            positions: pc.positions,
+            colors: pc.colors,
+        }
+    }
+}
+
+impl From<PointCloudIntensityNormal> for PointCloud {
+    fn from(pc: PointCloudIntensityNormal) -> Self {
+        Self {
+            positions: pc.positions,
+        }
+    }
+}
+
+impl From<PointCloudRgbNormal> for PointCloud {
+    fn from(pc: PointCloudRgbNormal) -> Self {
+        Self {
+            positions: pc.positions,
+        }
+    }
+}
+
+impl From<PointCloudRgba> for PointCloud {
+    fn from(pc: PointCloudRgba) -> Self {
+        Self {
+            positions: pc.positions,
+        }
+    }
+}
+
+impl From<PointCloud> for PointCloudIntensity {
+    fn from(pc: PointCloud) -> Self {
+        let len = pc.len();
+        Self {
+            positions: pc.positions,
+            intensities: vec![0.; len],
+        }
+    }
+}
+
+impl From<PointCloud> for PointCloudNormal {
+    fn from(pc: PointCloud) -> Self {
+        let len = pc.len();
+        Self {
+            positions: pc.positions,
+            normals: vec![Normal::zeros(); len],
+            curvatures: vec![0.; len],
+        }
+    }
+}
+
+impl From<PointCloud> for PointCloudRgb {
+    fn from(pc: PointCloud) -> Self {
+        let len = pc.len();
+        Self {
+            positions: pc.positions,
+            colors: vec![Rgb::zeros(); len],
+        }
+    }
+}
+
+impl<const N: usize> From<PointCloud> for PointCloudDescriptor<N> {
+    fn from(pc: PointCloud) -> Self {
+        let len = pc.len();
+        Self {
+            positions: pc.positions,
+            descriptors: vec![[0.; N]; len],
+        }
+    }
+}
+
+impl From<PointCloud> for PointCloudIntensityNormal {
+    fn from(pc: PointCloud) -> Self {
+        PointCloudIntensity::from(pc).into()
+    }
+}
+
+impl From<PointCloud> for PointCloudRgbNormal {
+    fn from(pc: PointCloud) -> Self {
+        PointCloudRgb::from(pc).into()
+    }
+}
+
+impl From<PointCloud> for PointCloudRgba {
+    fn from(pc: PointCloud) -> Self {
+        PointCloudRgb::from(pc).into()
+    }
+}
+
+impl From<PointCloudIntensity> for PointCloudIntensityNormal {
+    fn from(pc: PointCloudIntensity) -> Self {
+        let len = pc.len();
+        Self {
+            positions: pc.positions,
+            intensities: pc.intensities,
+            normals: vec![Normal::zeros(); len],
+            curvatures: vec![0.; len],
+        }
+    }
+}
+
+impl From<PointCloudNormal> for PointCloudIntensityNormal {
+    fn from(pc: PointCloudNormal) -> Self {
+        let len = pc.len();
+        Self {
+            positions: pc.positions,
+            intensities: vec![0.; len],
+            normals: pc.normals,
+            curvatures: pc.curvatures,
+        }
+    }
+}
+
+impl From<PointCloudNormal> for PointCloudRgbNormal {
+    fn from(pc: PointCloudNormal) -> Self {
+        let len = pc.len();
+        Self {
+            positions: pc.positions,
+            colors: vec![Rgb::zeros(); len],
+            normals: pc.normals,
+            curvatures: pc.curvatures,
+        }
+    }
+}
+
+impl From<PointCloudRgb> for PointCloudRgbNormal {
+    fn from(pc: PointCloudRgb) -> Self {
+        let len = pc.len();
+        Self {
+            positions: pc.positions,
+            colors: pc.colors,
+            normals: vec![Normal::zeros(); len],
+            curvatures: vec![0.; len],
+        }
+    }
+}
+
+impl From<PointCloudRgb> for PointCloudRgba {
+    fn from(pc: PointCloudRgb) -> Self {
+        let len = pc.len();
+        Self {
+            positions: pc.positions,
+            colors: pc.colors,
+            alphas: vec![u8::MAX; len],
+        }
+    }
+}
+
+impl PointCloud {
+    /// Adds a column of intensities.
+    pub fn with_intensities(
+        self,
+        intensities: Vec<Float>,
+    ) -> Result<PointCloudIntensity, ColumnLengthError> {
+        check_columns(self.len(), &[("intensities", intensities.len())])?;
+        Ok(PointCloudIntensity {
+            positions: self.positions,
+            intensities,
+        })
+    }
+
+    /// Adds a column of normals, with zero curvatures.
+    pub fn with_normals(self, normals: Vec<Normal>) -> Result<PointCloudNormal, ColumnLengthError> {
+        let len = self.len();
+        check_columns(len, &[("normals", normals.len())])?;
+        Ok(PointCloudNormal {
+            positions: self.positions,
+            normals,
+            curvatures: vec![0.; len],
+        })
+    }
+
+    /// Adds a column of colors.
+    pub fn with_colors(self, colors: Vec<Rgb>) -> Result<PointCloudRgb, ColumnLengthError> {
+        check_columns(self.len(), &[("colors", colors.len())])?;
+        Ok(PointCloudRgb {
+            positions: self.positions,
+            colors,
+        })
+    }
+
+    /// Adds a column of descriptors.
+    pub fn with_descriptors<const N: usize>(
+        self,
+        descriptors: Vec<Descriptor<N>>,
+    ) -> Result<PointCloudDescriptor<N>, ColumnLengthError> {
+        check_columns(self.len(), &[("descriptors", descriptors.len())])?;
+        Ok(PointCloudDescriptor {
+            positions: self.positions,
+            descriptors,
+        })
+    }
+}
+
+impl PointCloudIntensity {
+    /// Adds a column of normals, with zero curvatures.
+    pub fn with_normals(
+        self,
+        normals: Vec<Normal>,
+    ) -> Result<PointCloudIntensityNormal, ColumnLengthError> {
+        let len = self.len();
+        check_columns(len, &[("normals", normals.len())])?;
+        Ok(PointCloudIntensityNormal {
+            positions: self.positions,
+            intensities: self.intensities,
+            normals,
+            curvatures: vec![0.; len],
+        })
+    }
+}
+
+impl PointCloudNormal {
+    /// Adds a column of intensities.
+    pub fn with_intensities(
+        self,
+        intensities: Vec<Float>,
+    ) -> Result<PointCloudIntensityNormal, ColumnLengthError> {
+        check_columns(self.len(), &[("intensities", intensities.len())])?;
+        Ok(PointCloudIntensityNormal {
+            positions: self.positions,
+            intensities,
+            normals: self.normals,
+            curvatures: self.curvatures,
+        })
+    }
+
+    /// Adds a column of colors.
+    pub fn with_colors(self, colors: Vec<Rgb>) -> Result<PointCloudRgbNormal, ColumnLengthError> {
+        check_columns(self.len(), &[("colors", colors.len())])?;
+        Ok(PointCloudRgbNormal {
+            positions: self.positions,
+            colors,
+            normals: self.normals,
+            curvatures: self.curvatures,
+        })
+    }
+}
+
+impl PointCloudRgb {
+    /// Adds a column of normals, with zero curvatures.
+    pub fn with_normals(
+        self,
+        normals: Vec<Normal>,
+    ) -> Result<PointCloudRgbNormal, ColumnLengthError> {
+        let len = self.len();
+        check_columns(len, &[("normals", normals.len())])?;
+        Ok(PointCloudRgbNormal {
+            positions: self.positions,
+            colors: self.colors,
+            normals,
+            curvatures: vec![0.; len],
+        })
+    }
+
+    /// Adds a column of alphas.
+    pub fn with_alphas(self, alphas: Vec<u8>) -> Result<PointCloudRgba, ColumnLengthError> {
+        check_columns(self.len(), &[("alphas", alphas.len())])?;
+        Ok(PointCloudRgba {
+            positions: self.positions,
+            colors: self.colors,
+            alphas,
+        })
+    }
+}
+
+impl TryFrom<(PointCloud, Vec<Float>)> for PointCloudIntensity {
+    type Error = ColumnLengthError;
+
+    fn try_from((pc, column): (PointCloud, Vec<Float>)) -> Result<Self, Self::Error> {
+        pc.with_intensities(column)
+    }
+}
+
+impl TryFrom<(PointCloud, Vec<Normal>)> for PointCloudNormal {
+    type Error = ColumnLengthError;
+
+    fn try_from((pc, column): (PointCloud, Vec<Normal>)) -> Result<Self, Self::Error> {
+        pc.with_normals(column)
+    }
+}
+
+impl TryFrom<(PointCloud, Vec<Rgb>)> for PointCloudRgb {
+    type Error = ColumnLengthError;
+
+    fn try_from((pc, column): (PointCloud, Vec<Rgb>)) -> Result<Self, Self::Error> {
+        pc.with_colors(column)
+    }
+}
+
+impl<const N: usize> TryFrom<(PointCloud, Vec<Descriptor<N>>)> for PointCloudDescriptor<N> {
+    type Error = ColumnLengthError;
+
+    fn try_from((pc, column): (PointCloud, Vec<Descriptor<N>>)) -> Result<Self, Self::Error> {
+        pc.with_descriptors(column)
+    }
+}
+
+impl TryFrom<(PointCloudIntensity, Vec<Normal>)> for PointCloudIntensityNormal {
+    type Error = ColumnLengthError;
+
+    fn try_from((pc, column): (PointCloudIntensity, Vec<Normal>)) -> Result<Self, Self::Error> {
+        pc.with_normals(column)
+    }
+}
+
+impl TryFrom<(PointCloudNormal, Vec<Float>)> for PointCloudIntensityNormal {
+    type Error = ColumnLengthError;
+
+    fn try_from((pc, column): (PointCloudNormal, Vec<Float>)) -> Result<Self, Self::Error> {
+        pc.with_intensities(column)
+    }
+}
+
+impl TryFrom<(PointCloudNormal, Vec<Rgb>)> for PointCloudRgbNormal {
+    type Error = ColumnLengthError;
+
+    fn try_from((pc, column): (PointCloudNormal, Vec<Rgb>)) -> Result<Self, Self::Error> {
+        pc.with_colors(column)
+    }
+}
+
+impl TryFrom<(PointCloudRgb, Vec<Normal>)> for PointCloudRgbNormal {
+    type Error = ColumnLengthError;
+
+    fn try_from((pc, column): (PointCloudRgb, Vec<Normal>)) -> Result<Self, Self::Error> {
+        pc.with_normals(column)
+    }
+}
+
+impl TryFrom<(PointCloudRgb, Vec<u8>)> for PointCloudRgba {
+    type Error = ColumnLengthError;
+
+    fn try_from((pc, column): (PointCloudRgb, Vec<u8>)) -> Result<Self, Self::Error> {
+        pc.with_alphas(column)
+    }
+}
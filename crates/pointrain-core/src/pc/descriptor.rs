@@ -0,0 +1,213 @@
+use super::{
+    base::{filter_column, retain_column, select_column},
+    PointCloudBase, PointCloudWithDescriptor,
+};
+use crate::{
+    point::{
+        descriptor::{Point, PointRef, PointRefMut},
+        PointBase,
+    },
+    types::{Descriptor, Position},
+};
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Columns<N>"))]
+pub struct PointCloud<const N: usize> {
+    pub(super) positions: Vec<Position>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::array_vec"))]
+    pub(super) descriptors: Vec<Descriptor<N>>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Columns<const N: usize> {
+    positions: Vec<Position>,
+    #[serde(with = "crate::serde_support::array_vec")]
+    descriptors: Vec<Descriptor<N>>,
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> TryFrom<Columns<N>> for PointCloud<N> {
+    type Error = super::ColumnLengthError;
+
+    fn try_from(c: Columns<N>) -> Result<Self, Self::Error> {
+        super::convert::check_columns(c.positions.len(), &[("descriptors", c.descriptors.len())])?;
+        Ok(Self {
+            positions: c.positions,
+            descriptors: c.descriptors,
+        })
+    }
+}
+
+impl<const N: usize> PointCloud<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[cfg(feature = "rerun")]
+    pub fn rerun_points(&self) -> re_types::archetypes::Points3D {
+        self.pos_component_base()
+    }
+}
+
+impl<const N: usize> FromIterator<Point<N>> for PointCloud<N> {
+    fn from_iter<T: IntoIterator<Item = Point<N>>>(iter: T) -> Self {
+        let mut pc = Self::new();
+        for p in iter {
+            pc.push(p);
+        }
+        pc
+    }
+}
+
+impl<'a, const N: usize> FromIterator<PointRef<'a, N>> for PointCloud<N> {
+    fn from_iter<T: IntoIterator<Item = PointRef<'a, N>>>(iter: T) -> Self {
+        let mut pc = Self::new();
+        for p in iter {
+            pc.push_ref(p);
+        }
+        pc
+    }
+}
+
+impl<const N: usize> PointCloudBase for PointCloud<N> {
+    type Point = Point<N>;
+    type Iter<'a> = Iter<'a, N>;
+    type IterMut<'a> = IterMut<'a, N>;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            positions: Vec::with_capacity(capacity),
+            descriptors: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn resize(&mut self, new_len: usize, value: Self::Point) {
+        self.positions.resize(new_len, value.position);
+        self.descriptors.resize(new_len, value.descriptor);
+    }
+
+    fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    fn positions_mut(&mut self) -> &mut [Position] {
+        &mut self.positions
+    }
+
+    fn push(&mut self, p: Self::Point) -> &mut Self {
+        self.positions.push(p.position);
+        self.descriptors.push(p.descriptor);
+        self
+    }
+
+    fn push_ref(&mut self, p: <Self::Point as PointBase>::Ref<'_>) -> &mut Self {
+        self.positions.push(*p.position);
+        self.descriptors.push(*p.descriptor);
+        self
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Self::Iter {
+            positions: self.positions.iter(),
+            descriptors: self.descriptors.iter(),
+        }
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        Self::IterMut {
+            positions: self.positions.iter_mut(),
+            descriptors: self.descriptors.iter_mut(),
+        }
+    }
+
+    fn select(&self, indices: &[usize]) -> Self {
+        Self {
+            positions: select_column(&self.positions, indices),
+            descriptors: select_column(&self.descriptors, indices),
+        }
+    }
+
+    fn filter_mask(&self, mask: &[bool]) -> Self {
+        Self {
+            positions: filter_column(&self.positions, mask),
+            descriptors: filter_column(&self.descriptors, mask),
+        }
+    }
+
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(<Self::Point as PointBase>::Ref<'_>) -> bool,
+    {
+        let mask: Vec<_> = self.iter().map(f).collect();
+        retain_column(&mut self.positions, &mask);
+        retain_column(&mut self.descriptors, &mask);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.positions.truncate(len);
+        self.descriptors.truncate(len);
+    }
+
+    fn swap_remove(&mut self, index: usize) -> Self::Point {
+        Self::Point {
+            position: self.positions.swap_remove(index),
+            descriptor: self.descriptors.swap_remove(index),
+        }
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        Self {
+            positions: self.positions.split_off(at),
+            descriptors: self.descriptors.split_off(at),
+        }
+    }
+
+    fn append(&mut self, other: &mut Self) {
+        self.positions.append(&mut other.positions);
+        self.descriptors.append(&mut other.descriptors);
+    }
+}
+
+impl<const N: usize> PointCloudWithDescriptor<N> for PointCloud<N> {
+    fn descriptors(&self) -> &[Descriptor<N>] {
+        &self.descriptors
+    }
+
+    fn descriptors_mut(&mut self) -> &mut [Descriptor<N>] {
+        &mut self.descriptors
+    }
+}
+
+pub struct Iter<'a, const N: usize> {
+    positions: std::slice::Iter<'a, Position>,
+    descriptors: std::slice::Iter<'a, Descriptor<N>>,
+}
+
+impl<'a, const N: usize> Iterator for Iter<'a, N> {
+    type Item = PointRef<'a, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(Self::Item {
+            position: self.positions.next()?,
+            descriptor: self.descriptors.next()?,
+        })
+    }
+}
+
+pub struct IterMut<'a, const N: usize> {
+    positions: std::slice::IterMut<'a, Position>,
+    descriptors: std::slice::IterMut<'a, Descriptor<N>>,
+}
+
+impl<'a, const N: usize> Iterator for IterMut<'a, N> {
+    type Item = PointRefMut<'a, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(Self::Item {
+            position: self.positions.next()?,
+            descriptor: self.descriptors.next()?,
+        })
+    }
+}
@@ -1,4 +1,7 @@
-use super::{PointCloudBase, PointCloudWithIntensity};
+use super::{
+    base::{filter_column, retain_column, select_column},
+    PointCloudBase, PointCloudWithIntensity,
+};
 use crate::{
     point::{
         intensity::{Point, PointRef, PointRefMut},
@@ -8,11 +11,33 @@ use crate::{
 };
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Columns"))]
 pub struct PointCloud {
+    pub(super) positions: Vec<Position>,
+    pub(super) intensities: Vec<Float>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Columns {
     positions: Vec<Position>,
     intensities: Vec<Float>,
 }
 
+#[cfg(feature = "serde")]
+impl TryFrom<Columns> for PointCloud {
+    type Error = super::ColumnLengthError;
+
+    fn try_from(c: Columns) -> Result<Self, Self::Error> {
+        super::convert::check_columns(c.positions.len(), &[("intensities", c.intensities.len())])?;
+        Ok(Self {
+            positions: c.positions,
+            intensities: c.intensities,
+        })
+    }
+}
+
 impl PointCloud {
     pub fn new() -> Self {
         Self::default()
@@ -95,6 +120,53 @@ impl PointCloudBase for PointCloud {
             intensities: self.intensities.iter_mut(),
         }
     }
+
+    fn select(&self, indices: &[usize]) -> Self {
+        Self {
+            positions: select_column(&self.positions, indices),
+            intensities: select_column(&self.intensities, indices),
+        }
+    }
+
+    fn filter_mask(&self, mask: &[bool]) -> Self {
+        Self {
+            positions: filter_column(&self.positions, mask),
+            intensities: filter_column(&self.intensities, mask),
+        }
+    }
+
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(<Self::Point as PointBase>::Ref<'_>) -> bool,
+    {
+        let mask: Vec<_> = self.iter().map(f).collect();
+        retain_column(&mut self.positions, &mask);
+        retain_column(&mut self.intensities, &mask);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.positions.truncate(len);
+        self.intensities.truncate(len);
+    }
+
+    fn swap_remove(&mut self, index: usize) -> Self::Point {
+        Self::Point {
+            position: self.positions.swap_remove(index),
+            intensity: self.intensities.swap_remove(index),
+        }
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        Self {
+            positions: self.positions.split_off(at),
+            intensities: self.intensities.split_off(at),
+        }
+    }
+
+    fn append(&mut self, other: &mut Self) {
+        self.positions.append(&mut other.positions);
+        self.intensities.append(&mut other.intensities);
+    }
 }
 
 impl PointCloudWithIntensity for PointCloud {
@@ -1,4 +1,7 @@
-use super::{PointCloudBase, PointCloudWithColor};
+use super::{
+    base::{filter_column, retain_column, select_column},
+    PointCloudBase, PointCloudWithColor,
+};
 use crate::{
     point::{
         rgb::{Point, PointRef, PointRefMut},
@@ -8,11 +11,33 @@ use crate::{
 };
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Columns"))]
 pub struct PointCloud {
+    pub(super) positions: Vec<Position>,
+    pub(super) colors: Vec<Rgb>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Columns {
     positions: Vec<Position>,
     colors: Vec<Rgb>,
 }
 
+#[cfg(feature = "serde")]
+impl TryFrom<Columns> for PointCloud {
+    type Error = super::ColumnLengthError;
+
+    fn try_from(c: Columns) -> Result<Self, Self::Error> {
+        super::convert::check_columns(c.positions.len(), &[("colors", c.colors.len())])?;
+        Ok(Self {
+            positions: c.positions,
+            colors: c.colors,
+        })
+    }
+}
+
 impl PointCloud {
     pub fn new() -> Self {
         Self::default()
@@ -94,6 +119,53 @@ impl PointCloudBase for PointCloud {
             colors: self.colors.iter_mut(),
         }
     }
+
+    fn select(&self, indices: &[usize]) -> Self {
+        Self {
+            positions: select_column(&self.positions, indices),
+            colors: select_column(&self.colors, indices),
+        }
+    }
+
+    fn filter_mask(&self, mask: &[bool]) -> Self {
+        Self {
+            positions: filter_column(&self.positions, mask),
+            colors: filter_column(&self.colors, mask),
+        }
+    }
+
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(<Self::Point as PointBase>::Ref<'_>) -> bool,
+    {
+        let mask: Vec<_> = self.iter().map(f).collect();
+        retain_column(&mut self.positions, &mask);
+        retain_column(&mut self.colors, &mask);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.positions.truncate(len);
+        self.colors.truncate(len);
+    }
+
+    fn swap_remove(&mut self, index: usize) -> Self::Point {
+        Self::Point {
+            position: self.positions.swap_remove(index),
+            color: self.colors.swap_remove(index),
+        }
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        Self {
+            positions: self.positions.split_off(at),
+            colors: self.colors.split_off(at),
+        }
+    }
+
+    fn append(&mut self, other: &mut Self) {
+        self.positions.append(&mut other.positions);
+        self.colors.append(&mut other.colors);
+    }
 }
 
 impl PointCloudWithColor for PointCloud {
@@ -1,4 +1,7 @@
-use super::{PointCloudBase, PointCloudWithColor, PointCloudWithNormal};
+use super::{
+    base::{filter_column, retain_column, select_column},
+    PointCloudBase, PointCloudWithColor, PointCloudWithNormal,
+};
 use crate::{
     point::{
         rgb_normal::{Point, PointRef, PointRefMut},
@@ -8,13 +11,46 @@ use crate::{
 };
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Columns"))]
 pub struct PointCloud {
+    pub(super) positions: Vec<Position>,
+    pub(super) colors: Vec<Rgb>,
+    pub(super) normals: Vec<Normal>,
+    pub(super) curvatures: Vec<Float>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Columns {
     positions: Vec<Position>,
     colors: Vec<Rgb>,
     normals: Vec<Normal>,
     curvatures: Vec<Float>,
 }
 
+#[cfg(feature = "serde")]
+impl TryFrom<Columns> for PointCloud {
+    type Error = super::ColumnLengthError;
+
+    fn try_from(c: Columns) -> Result<Self, Self::Error> {
+        super::convert::check_columns(
+            c.positions.len(),
+            &[
+                ("colors", c.colors.len()),
+                ("normals", c.normals.len()),
+                ("curvatures", c.curvatures.len()),
+            ],
+        )?;
+        Ok(Self {
+            positions: c.positions,
+            colors: c.colors,
+            normals: c.normals,
+            curvatures: c.curvatures,
+        })
+    }
+}
+
 impl PointCloud {
     pub fn new() -> Self {
         Self::default()
@@ -114,6 +150,67 @@ impl PointCloudBase for PointCloud {
             curvatures: self.curvatures.iter_mut(),
         }
     }
+
+    fn select(&self, indices: &[usize]) -> Self {
+        Self {
+            positions: select_column(&self.positions, indices),
+            colors: select_column(&self.colors, indices),
+            normals: select_column(&self.normals, indices),
+            curvatures: select_column(&self.curvatures, indices),
+        }
+    }
+
+    fn filter_mask(&self, mask: &[bool]) -> Self {
+        Self {
+            positions: filter_column(&self.positions, mask),
+            colors: filter_column(&self.colors, mask),
+            normals: filter_column(&self.normals, mask),
+            curvatures: filter_column(&self.curvatures, mask),
+        }
+    }
+
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(<Self::Point as PointBase>::Ref<'_>) -> bool,
+    {
+        let mask: Vec<_> = self.iter().map(f).collect();
+        retain_column(&mut self.positions, &mask);
+        retain_column(&mut self.colors, &mask);
+        retain_column(&mut self.normals, &mask);
+        retain_column(&mut self.curvatures, &mask);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.positions.truncate(len);
+        self.colors.truncate(len);
+        self.normals.truncate(len);
+        self.curvatures.truncate(len);
+    }
+
+    fn swap_remove(&mut self, index: usize) -> Self::Point {
+        Self::Point {
+            position: self.positions.swap_remove(index),
+            color: self.colors.swap_remove(index),
+            normal: self.normals.swap_remove(index),
+            curvature: self.curvatures.swap_remove(index),
+        }
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        Self {
+            positions: self.positions.split_off(at),
+            colors: self.colors.split_off(at),
+            normals: self.normals.split_off(at),
+            curvatures: self.curvatures.split_off(at),
+        }
+    }
+
+    fn append(&mut self, other: &mut Self) {
+        self.positions.append(&mut other.positions);
+        self.colors.append(&mut other.colors);
+        self.normals.append(&mut other.normals);
+        self.curvatures.append(&mut other.curvatures);
+    }
 }
 
 impl PointCloudWithColor for PointCloud {
@@ -0,0 +1,245 @@
+use super::{
+    base::{filter_column, retain_column, select_column},
+    PointCloudBase, PointCloudWithAlpha, PointCloudWithColor,
+};
+use crate::{
+    point::{
+        rgba::{Point, PointRef, PointRefMut},
+        PointBase,
+    },
+    types::{Position, Rgb},
+};
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Columns"))]
+pub struct PointCloud {
+    pub(super) positions: Vec<Position>,
+    pub(super) colors: Vec<Rgb>,
+    pub(super) alphas: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct Columns {
+    positions: Vec<Position>,
+    colors: Vec<Rgb>,
+    alphas: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Columns> for PointCloud {
+    type Error = super::ColumnLengthError;
+
+    fn try_from(c: Columns) -> Result<Self, Self::Error> {
+        super::convert::check_columns(
+            c.positions.len(),
+            &[("colors", c.colors.len()), ("alphas", c.alphas.len())],
+        )?;
+        Ok(Self {
+            positions: c.positions,
+            colors: c.colors,
+            alphas: c.alphas,
+        })
+    }
+}
+
+impl PointCloud {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[cfg(feature = "rerun")]
+    pub fn rerun_points(&self) -> re_types::archetypes::Points3D {
+        self.pos_component_base()
+            .with_colors(self.rerun_colors_with_alpha())
+    }
+}
+
+impl FromIterator<Point> for PointCloud {
+    fn from_iter<T: IntoIterator<Item = Point>>(iter: T) -> Self {
+        let mut pc = Self::new();
+        for p in iter {
+            pc.push(p);
+        }
+        pc
+    }
+}
+
+impl<'a> FromIterator<PointRef<'a>> for PointCloud {
+    fn from_iter<T: IntoIterator<Item = PointRef<'a>>>(iter: T) -> Self {
+        let mut pc = Self::new();
+        for p in iter {
+            pc.push_ref(p);
+        }
+        pc
+    }
+}
+
+impl PointCloudBase for PointCloud {
+    type Point = Point;
+    type Iter<'a> = Iter<'a>;
+    type IterMut<'a> = IterMut<'a>;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            positions: Vec::with_capacity(capacity),
+            colors: Vec::with_capacity(capacity),
+            alphas: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn resize(&mut self, new_len: usize, value: Self::Point) {
+        self.positions.resize(new_len, value.position);
+        self.colors.resize(new_len, value.color);
+        self.alphas.resize(new_len, value.alpha);
+    }
+
+    fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    fn positions_mut(&mut self) -> &mut [Position] {
+        &mut self.positions
+    }
+
+    fn push(&mut self, p: Self::Point) -> &mut Self {
+        self.positions.push(p.position);
+        self.colors.push(p.color);
+        self.alphas.push(p.alpha);
+        self
+    }
+
+    fn push_ref(&mut self, p: <Self::Point as PointBase>::Ref<'_>) -> &mut Self {
+        self.positions.push(*p.position);
+        self.colors.push(*p.color);
+        self.alphas.push(*p.alpha);
+        self
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Self::Iter {
+            positions: self.positions.iter(),
+            colors: self.colors.iter(),
+            alphas: self.alphas.iter(),
+        }
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        Self::IterMut {
+            positions: self.positions.iter_mut(),
+            colors: self.colors.iter_mut(),
+            alphas: self.alphas.iter_mut(),
+        }
+    }
+
+    fn select(&self, indices: &[usize]) -> Self {
+        Self {
+            positions: select_column(&self.positions, indices),
+            colors: select_column(&self.colors, indices),
+            alphas: select_column(&self.alphas, indices),
+        }
+    }
+
+    fn filter_mask(&self, mask: &[bool]) -> Self {
+        Self {
+            positions: filter_column(&self.positions, mask),
+            colors: filter_column(&self.colors, mask),
+            alphas: filter_column(&self.alphas, mask),
+        }
+    }
+
+    fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(<Self::Point as PointBase>::Ref<'_>) -> bool,
+    {
+        let mask: Vec<_> = self.iter().map(f).collect();
+        retain_column(&mut self.positions, &mask);
+        retain_column(&mut self.colors, &mask);
+        retain_column(&mut self.alphas, &mask);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.positions.truncate(len);
+        self.colors.truncate(len);
+        self.alphas.truncate(len);
+    }
+
+    fn swap_remove(&mut self, index: usize) -> Self::Point {
+        Self::Point {
+            position: self.positions.swap_remove(index),
+            color: self.colors.swap_remove(index),
+            alpha: self.alphas.swap_remove(index),
+        }
+    }
+
+    fn split_off(&mut self, at: usize) -> Self {
+        Self {
+            positions: self.positions.split_off(at),
+            colors: self.colors.split_off(at),
+            alphas: self.alphas.split_off(at),
+        }
+    }
+
+    fn append(&mut self, other: &mut Self) {
+        self.positions.append(&mut other.positions);
+        self.colors.append(&mut other.colors);
+        self.alphas.append(&mut other.alphas);
+    }
+}
+
+impl PointCloudWithColor for PointCloud {
+    fn colors(&self) -> &[Rgb] {
+        &self.colors
+    }
+
+    fn colors_mut(&mut self) -> &mut [Rgb] {
+        &mut self.colors
+    }
+}
+
+impl PointCloudWithAlpha for PointCloud {
+    fn alphas(&self) -> &[u8] {
+        &self.alphas
+    }
+
+    fn alphas_mut(&mut self) -> &mut [u8] {
+        &mut self.alphas
+    }
+}
+
+pub struct Iter<'a> {
+    positions: std::slice::Iter<'a, Position>,
+    colors: std::slice::Iter<'a, Rgb>,
+    alphas: std::slice::Iter<'a, u8>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = PointRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(Self::Item {
+            position: self.positions.next()?,
+            color: self.colors.next()?,
+            alpha: self.alphas.next()?,
+        })
+    }
+}
+
+pub struct IterMut<'a> {
+    positions: std::slice::IterMut<'a, Position>,
+    colors: std::slice::IterMut<'a, Rgb>,
+    alphas: std::slice::IterMut<'a, u8>,
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = PointRefMut<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(Self::Item {
+            position: self.positions.next()?,
+            color: self.colors.next()?,
+            alpha: self.alphas.next()?,
+        })
+    }
+}
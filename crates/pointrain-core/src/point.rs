@@ -1,12 +1,17 @@
 use crate::types::Position;
 
+pub mod descriptor;
 pub mod intensity;
 pub mod intensity_normal;
 pub mod normal;
 pub mod rgb;
 pub mod rgb_normal;
+pub mod rgba;
 pub mod xyz;
 
+pub use descriptor::{
+    Point as PointDescriptor, PointRef as PointDescriptorRef, PointRefMut as PointDescriptorRefMut,
+};
 pub use intensity::{
     Point as PointIntensity, PointRef as PointIntensityRef, PointRefMut as PointIntensityRefMut,
 };
@@ -21,8 +26,14 @@ pub use rgb::{Point as PointRgb, PointRef as PointRgbRef, PointRefMut as PointRg
 pub use rgb_normal::{
     Point as PointRgbNormal, PointRef as PointRgbNormalRef, PointRefMut as PointRgbNormalRefMut,
 };
+pub use rgba::{Point as PointRgba, PointRef as PointRgbaRef, PointRefMut as PointRgbaRefMut};
 pub use xyz::{Point, PointRef, PointRefMut};
 
+/// [FPFH](https://pointclouds.org/documentation/classpcl_1_1_f_p_f_h_estimation.html) feature.
+pub type PointFpfh = PointDescriptor<33>;
+/// [SHOT](https://pointclouds.org/documentation/classpcl_1_1_s_h_o_t_estimation.html) feature.
+pub type PointShot = PointDescriptor<352>;
+
 pub trait PointBase: Default {
     type Ref<'a>
     where
@@ -0,0 +1,68 @@
+use super::PointBase;
+use crate::types::{Descriptor, Position};
+
+/// Point with a fixed-length feature vector such as FPFH (33) or SHOT (352).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point<const N: usize> {
+    pub position: Position,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_support::array"))]
+    pub descriptor: Descriptor<N>,
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Self {
+            position: Default::default(),
+            descriptor: [0.; N],
+        }
+    }
+}
+
+impl<const N: usize> PointBase for Point<N> {
+    type Ref<'a> = PointRef<'a, N>;
+    type RefMut<'a> = PointRefMut<'a, N>;
+
+    fn as_ref(&self) -> Self::Ref<'_> {
+        Self::Ref {
+            position: &self.position,
+            descriptor: &self.descriptor,
+        }
+    }
+
+    fn as_ref_mut(&mut self) -> Self::RefMut<'_> {
+        Self::RefMut {
+            position: &mut self.position,
+            descriptor: &mut self.descriptor,
+        }
+    }
+
+    fn position(&self) -> &Position {
+        &self.position
+    }
+
+    fn position_mut(&mut self) -> &mut Position {
+        &mut self.position
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointRef<'a, const N: usize> {
+    pub position: &'a Position,
+    pub descriptor: &'a Descriptor<N>,
+}
+
+impl<const N: usize> From<PointRef<'_, N>> for Point<N> {
+    fn from(p: PointRef<'_, N>) -> Self {
+        Self {
+            position: *p.position,
+            descriptor: *p.descriptor,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PointRefMut<'a, const N: usize> {
+    pub position: &'a mut Position,
+    pub descriptor: &'a mut Descriptor<N>,
+}
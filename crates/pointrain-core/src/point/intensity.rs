@@ -2,6 +2,7 @@ use super::PointBase;
 use crate::types::{Float, Position};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub position: Position,
     pub intensity: Float,
@@ -40,6 +41,15 @@ pub struct PointRef<'a> {
     pub intensity: &'a Float,
 }
 
+impl From<PointRef<'_>> for Point {
+    fn from(p: PointRef<'_>) -> Self {
+        Self {
+            position: *p.position,
+            intensity: *p.intensity,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct PointRefMut<'a> {
     pub position: &'a mut Position,
@@ -2,6 +2,7 @@ use super::PointBase;
 use crate::types::{Float, Normal, Position};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub position: Position,
     pub intensity: Float,
@@ -48,6 +49,17 @@ pub struct PointRef<'a> {
     pub curvature: &'a Float,
 }
 
+impl From<PointRef<'_>> for Point {
+    fn from(p: PointRef<'_>) -> Self {
+        Self {
+            position: *p.position,
+            intensity: *p.intensity,
+            normal: *p.normal,
+            curvature: *p.curvature,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct PointRefMut<'a> {
     pub position: &'a mut Position,
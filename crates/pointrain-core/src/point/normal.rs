@@ -2,6 +2,7 @@ pub use super::PointBase;
 pub use crate::types::{Float, Normal, Position};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub position: Position,
     pub normal: Normal,
@@ -44,6 +45,16 @@ pub struct PointRef<'a> {
     pub curvature: &'a Float,
 }
 
+impl From<PointRef<'_>> for Point {
+    fn from(p: PointRef<'_>) -> Self {
+        Self {
+            position: *p.position,
+            normal: *p.normal,
+            curvature: *p.curvature,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct PointRefMut<'a> {
     pub position: &'a mut Position,
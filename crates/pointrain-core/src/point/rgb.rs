@@ -2,6 +2,7 @@ use super::PointBase;
 use crate::types::{Position, Rgb};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub position: Position,
     pub color: Rgb,
@@ -40,6 +41,15 @@ pub struct PointRef<'a> {
     pub color: &'a Rgb,
 }
 
+impl From<PointRef<'_>> for Point {
+    fn from(p: PointRef<'_>) -> Self {
+        Self {
+            position: *p.position,
+            color: *p.color,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct PointRefMut<'a> {
     pub position: &'a mut Position,
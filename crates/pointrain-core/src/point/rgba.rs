@@ -0,0 +1,73 @@
+use super::PointBase;
+use crate::types::{Position, Rgb};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub position: Position,
+    pub color: Rgb,
+    pub alpha: u8,
+}
+
+impl Default for Point {
+    fn default() -> Self {
+        Self {
+            position: Default::default(),
+            color: Default::default(),
+            alpha: u8::MAX,
+        }
+    }
+}
+
+impl PointBase for Point {
+    type Ref<'a> = PointRef<'a>;
+    type RefMut<'a> = PointRefMut<'a>;
+
+    fn as_ref(&self) -> Self::Ref<'_> {
+        Self::Ref {
+            position: &self.position,
+            color: &self.color,
+            alpha: &self.alpha,
+        }
+    }
+
+    fn as_ref_mut(&mut self) -> Self::RefMut<'_> {
+        Self::RefMut {
+            position: &mut self.position,
+            color: &mut self.color,
+            alpha: &mut self.alpha,
+        }
+    }
+
+    fn position(&self) -> &Position {
+        &self.position
+    }
+
+    fn position_mut(&mut self) -> &mut Position {
+        &mut self.position
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointRef<'a> {
+    pub position: &'a Position,
+    pub color: &'a Rgb,
+    pub alpha: &'a u8,
+}
+
+impl From<PointRef<'_>> for Point {
+    fn from(p: PointRef<'_>) -> Self {
+        Self {
+            position: *p.position,
+            color: *p.color,
+            alpha: *p.alpha,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PointRefMut<'a> {
+    pub position: &'a mut Position,
+    pub color: &'a mut Rgb,
+    pub alpha: &'a mut u8,
+}
@@ -0,0 +1,259 @@
+//! Spherical range images of rotating lidar scans.
+//!
+//! Columns span the azimuth from +π (behind the sensor) clockwise seen from above, so that
+//! +x is in the middle of the image. Rows go from the top beam down.
+
+use std::f32::consts::PI;
+
+use nalgebra::Vector3;
+
+use crate::{
+    camera::{Image, OrganizedPointCloud},
+    pc::{PointCloud, PointCloudBase, PointCloudIntensity, PointCloudWithIntensity},
+    point::{Point, PointIntensity},
+    types::{Float, Position},
+};
+
+/// Elevation of the rows of a range image, in radians.
+#[derive(Debug, Clone, PartialEq)]
+enum Rows {
+    Uniform {
+        height: usize,
+        fov_up: Float,
+        fov_down: Float,
+    },
+    /// Descending.
+    Beams(Vec<Float>),
+}
+
+/// Maps directions to pixels of a `width` x `height` spherical image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeImageProjection {
+    width: usize,
+    rows: Rows,
+}
+
+impl RangeImageProjection {
+    /// Rows evenly spaced between the elevations `fov_up` and `fov_down` (e.g. `-0.43` for a
+    /// sensor seeing 24.8° below the horizon).
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `width` and `height` are positive and `fov_up > fov_down`.
+    pub fn uniform(width: usize, height: usize, fov_up: Float, fov_down: Float) -> Self {
+        assert!(width > 0 && height > 0, "width and height must be positive");
+        assert!(fov_up > fov_down, "fov_up must be above fov_down");
+        Self {
+            width,
+            rows: Rows::Uniform {
+                height,
+                fov_up,
+                fov_down,
+            },
+        }
+    }
+
+    /// One row per beam at the given elevations, for sensors with irregular beam spacing.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `width` is positive.
+    pub fn beams(width: usize, mut elevations: Vec<Float>) -> Self {
+        assert!(width > 0, "width must be positive");
+        elevations.sort_by(|a, b| b.total_cmp(a));
+        Self {
+            width,
+            rows: Rows::Beams(elevations),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        match &self.rows {
+            Rows::Uniform { height, .. } => *height,
+            Rows::Beams(elevations) => elevations.len(),
+        }
+    }
+
+    /// Pixel (`u`, `v`) of a point, `None` outside the vertical field of view.
+    pub fn pixel(&self, p: &Position) -> Option<(usize, usize)> {
+        let azimuth = p.y.atan2(p.x);
+        let elevation = p.z.atan2(p.x.hypot(p.y));
+        if !azimuth.is_finite() || !elevation.is_finite() {
+            return None;
+        }
+
+        let u = ((PI - azimuth) / (2. * PI) * self.width as Float) as usize;
+        Some((u.min(self.width - 1), self.row(elevation)?))
+    }
+
+    fn row(&self, elevation: Float) -> Option<usize> {
+        match &self.rows {
+            Rows::Uniform {
+                height,
+                fov_up,
+                fov_down,
+            } => {
+                let v = (fov_up - elevation) / (fov_up - fov_down) * *height as Float;
+                (0. ..*height as Float).contains(&v).then_some(v as usize)
+            }
+            Rows::Beams(elevations) => {
+                let half_gap = |i: usize, j: usize| (elevations[i] - elevations[j]).abs() / 2.;
+                let n = elevations.len();
+                let (top, bottom) = match n {
+                    0 => return None,
+                    1 => (Float::INFINITY, Float::INFINITY),
+                    _ => (half_gap(0, 1), half_gap(n - 2, n - 1)),
+                };
+                if elevation > elevations[0] + top || elevation < elevations[n - 1] - bottom {
+                    return None;
+                }
+                (0..n).min_by(|&i, &j| {
+                    let d = |k: usize| (elevations[k] - elevation).abs();
+                    d(i).total_cmp(&d(j))
+                })
+            }
+        }
+    }
+
+    /// Unit direction through the center of pixel (`u`, `v`).
+    pub fn direction(&self, u: usize, v: usize) -> Vector3<Float> {
+        let azimuth = PI - (u as Float + 0.5) / self.width as Float * 2. * PI;
+        let elevation = match &self.rows {
+            Rows::Uniform {
+                height,
+                fov_up,
+                fov_down,
+            } => fov_up - (v as Float + 0.5) / *height as Float * (fov_up - fov_down),
+            Rows::Beams(elevations) => elevations[v],
+        };
+        Vector3::new(
+            elevation.cos() * azimuth.cos(),
+            elevation.cos() * azimuth.sin(),
+            elevation.sin(),
+        )
+    }
+}
+
+/// A point cloud projected into a spherical image, keeping the nearest point of each pixel.
+#[derive(Debug, Clone)]
+pub struct RangeImage {
+    pub projection: RangeImageProjection,
+    /// Distance from the origin, zero for empty pixels.
+    pub range: Image<Float>,
+    /// Index of the point of each pixel into the source cloud.
+    pub index: Image<Option<usize>>,
+    pub intensity: Option<Image<Float>>,
+}
+
+impl RangeImage {
+    pub fn new<PC: PointCloudBase>(pc: &PC, projection: RangeImageProjection) -> Self {
+        let (width, height) = (projection.width(), projection.height());
+        let mut range = Image::new(width, height, 0.);
+        let mut index = Image::new(width, height, None);
+
+        for (i, p) in pc.positions().iter().enumerate() {
+            let r = p.coords.norm();
+            if r.is_nan() || r == 0. {
+                continue;
+            }
+            let Some((u, v)) = projection.pixel(p) else {
+                continue;
+            };
+
+            let pixel = range.get_mut(u, v).unwrap();
+            if *pixel == 0. || r < *pixel {
+                *pixel = r;
+                *index.get_mut(u, v).unwrap() = Some(i);
+            }
+        }
+
+        Self {
+            projection,
+            range,
+            index,
+            intensity: None,
+        }
+    }
+
+    /// Same as [`Self::new`], also storing the intensity of each pixel (zero if empty).
+    pub fn with_intensity<PC: PointCloudWithIntensity>(
+        pc: &PC,
+        projection: RangeImageProjection,
+    ) -> Self {
+        let mut image = Self::new(pc, projection);
+        let intensities = image
+            .index
+            .data()
+            .iter()
+            .map(|i| i.map_or(0., |i| pc.intensities()[i]))
+            .collect();
+        image.intensity = Image::from_vec(image.width(), image.height(), intensities);
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.projection.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.projection.height()
+    }
+
+    /// Indices of the points in the `(2 * radius + 1)` square window around pixel (`u`, `v`),
+    /// wrapping around horizontally.
+    pub fn neighbors(&self, u: usize, v: usize, radius: usize) -> impl Iterator<Item = usize> + '_ {
+        let (width, height) = (self.width(), self.height());
+        let rows = v.saturating_sub(radius)..(v + radius + 1).min(height);
+        let cols = (2 * radius + 1).min(width);
+        rows.flat_map(move |v| {
+            (0..cols).filter_map(move |du| {
+                let u = (u + width - radius % width + du) % width;
+                *self.index.get(u, v).unwrap()
+            })
+        })
+    }
+
+    /// Back-projects the pixels along their center directions.
+    pub fn to_pc(&self) -> OrganizedPointCloud<PointCloud> {
+        OrganizedPointCloud {
+            pc: self
+                .positions()
+                .map(|position| Point { position })
+                .collect(),
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+
+    /// Same as [`Self::to_pc`] with intensities, `None` if the image has none.
+    pub fn to_pc_with_intensity(&self) -> Option<OrganizedPointCloud<PointCloudIntensity>> {
+        let intensity = self.intensity.as_ref()?;
+        Some(OrganizedPointCloud {
+            pc: self
+                .positions()
+                .zip(intensity.data())
+                .map(|(position, &intensity)| PointIntensity {
+                    position,
+                    intensity,
+                })
+                .collect(),
+            width: self.width(),
+            height: self.height(),
+        })
+    }
+
+    /// Positions in row-major order, NaN for empty pixels.
+    fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.range.data().iter().enumerate().map(|(i, &r)| {
+            if r == 0. {
+                return Position::new(Float::NAN, Float::NAN, Float::NAN);
+            }
+            let (u, v) = (i % self.width(), i / self.width());
+            (self.projection.direction(u, v) * r).into()
+        })
+    }
+}
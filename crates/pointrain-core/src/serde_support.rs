@@ -0,0 +1,91 @@
+//! Helpers of the `serde` implementations of points and point clouds.
+
+use std::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Fixed-size arrays of any length as tuples, serde only supports up to 32 elements.
+pub(crate) mod array {
+    use super::*;
+
+    pub(crate) fn serialize<S, T, const N: usize>(v: &[T; N], s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        let mut tuple = s.serialize_tuple(N)?;
+        for e in v {
+            tuple.serialize_element(e)?;
+        }
+        tuple.end()
+    }
+
+    pub(crate) fn deserialize<'de, D, T, const N: usize>(d: D) -> Result<[T; N], D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + Copy + Default,
+    {
+        struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+        impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+        where
+            T: Deserialize<'de> + Copy + Default,
+        {
+            type Value = [T; N];
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an array of length {N}")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<[T; N], A::Error> {
+                let mut array = [T::default(); N];
+                for (i, e) in array.iter_mut().enumerate() {
+                    *e = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                }
+                if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                    return Err(de::Error::invalid_length(N + 1, &self));
+                }
+                Ok(array)
+            }
+        }
+
+        d.deserialize_tuple(N, ArrayVisitor(PhantomData))
+    }
+}
+
+/// A column of fixed-size arrays, see [`array`].
+pub(crate) mod array_vec {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Ser<'a, T: Serialize, const N: usize>(#[serde(with = "array")] &'a [T; N]);
+
+    #[derive(Deserialize)]
+    #[serde(bound = "T: Deserialize<'de> + Copy + Default")]
+    struct De<T, const N: usize>(#[serde(with = "array")] [T; N]);
+
+    pub(crate) fn serialize<S, T, const N: usize>(v: &[[T; N]], s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        s.collect_seq(v.iter().map(Ser))
+    }
+
+    pub(crate) fn deserialize<'de, D, T, const N: usize>(d: D) -> Result<Vec<[T; N]>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de> + Copy + Default,
+    {
+        Ok(Vec::<De<T, N>>::deserialize(d)?
+            .into_iter()
+            .map(|De(v)| v)
+            .collect())
+    }
+}
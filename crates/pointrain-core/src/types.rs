@@ -4,3 +4,4 @@ pub type Float = f32;
 pub type Position = Point3<Float>;
 pub type Normal = Vector3<Float>;
 pub type Rgb = Vector3<u8>;
+pub type Descriptor<const N: usize> = [Float; N];
@@ -0,0 +1,107 @@
+use approx::assert_abs_diff_eq;
+use pointrain_core::{
+    camera::{
+        depth_to_pc, pc_to_depth, pc_to_rgbd, rgbd_to_pc, Distortion, Image, PinholeIntrinsics,
+    },
+    nalgebra::Point2,
+    pc::{PointCloudBase, PointCloudRgb, PointCloudWithColor},
+    point::PointRgb,
+    types::{Position, Rgb},
+};
+
+fn intrinsics() -> PinholeIntrinsics {
+    PinholeIntrinsics::new(100., 100., 2., 1.5)
+}
+
+#[test]
+fn test_depth_to_pc() {
+    // 4 x 3, the pixel at (1, 2) is missing.
+    let mut depth = Image::new(4, 3, 2000u16);
+    *depth.get_mut(1, 2).unwrap() = 0;
+
+    let organized = depth_to_pc(&depth, &intrinsics());
+
+    assert_eq!((organized.width, organized.height), (4, 3));
+    assert_eq!(organized.pc.len(), 12);
+    let p = organized.pc.positions()[organized.index(3, 0)];
+    assert_abs_diff_eq!(p, Position::new(0.02, -0.03, 2.), epsilon = 1e-6);
+    assert!(organized.pc.positions()[organized.index(1, 2)].x.is_nan());
+}
+
+#[test]
+fn test_rgbd_to_pc() {
+    let depth = Image::from_vec(2, 1, vec![1.5f32, f32::NAN]).unwrap();
+    let color = Image::from_vec(2, 1, vec![Rgb::new(1, 2, 3), Rgb::new(4, 5, 6)]).unwrap();
+
+    let organized = rgbd_to_pc(&depth, &color, &intrinsics());
+
+    assert_eq!(organized.pc.colors(), color.data());
+    assert_abs_diff_eq!(
+        organized.pc.positions()[0],
+        Position::new(-0.03, -0.0225, 1.5),
+        epsilon = 1e-6
+    );
+    assert!(organized.pc.positions()[1].z.is_nan());
+}
+
+#[test]
+fn test_pc_to_rgbd_zbuffer() {
+    let pc: PointCloudRgb = [
+        (Position::new(0., 0., 2.), Rgb::new(255, 0, 0)),
+        // Hidden behind the first point.
+        (Position::new(0., 0., 3.), Rgb::new(0, 255, 0)),
+        (Position::new(0.01, 0., 1.), Rgb::new(0, 0, 255)),
+        // Behind the camera and out of the image.
+        (Position::new(0., 0., -1.), Rgb::new(1, 1, 1)),
+        (Position::new(1., 0., 1.), Rgb::new(1, 1, 1)),
+    ]
+    .into_iter()
+    .map(|(position, color)| PointRgb { position, color })
+    .collect();
+
+    let (depth, color) = pc_to_rgbd::<_, f32>(&pc, &intrinsics(), 4, 3);
+
+    assert_eq!(depth.get(2, 2), Some(&2.));
+    assert_eq!(color.get(2, 2), Some(&Rgb::new(255, 0, 0)));
+    assert_eq!(depth.get(3, 2), Some(&1.));
+    assert_eq!(color.get(3, 2), Some(&Rgb::new(0, 0, 255)));
+    assert_eq!(depth.data().iter().filter(|&&d| d != 0.).count(), 2);
+    assert_eq!(color.get(0, 0), Some(&Rgb::zeros()));
+}
+
+#[test]
+fn test_depth_roundtrip() {
+    let intrinsics = PinholeIntrinsics::new(50., 60., 15.5, 11.5);
+    let depth = Image::from_vec(32, 24, (0..32 * 24).map(|i| 500 + i as u16).collect()).unwrap();
+
+    let organized = depth_to_pc(&depth, &intrinsics);
+    let rendered: Image<u16> = pc_to_depth(&organized.pc, &intrinsics, 32, 24);
+
+    assert_eq!(rendered, depth);
+}
+
+#[test]
+fn test_distortion() {
+    let distortion = Distortion {
+        k1: -0.2,
+        k2: 0.05,
+        p1: 0.001,
+        p2: -0.002,
+        k3: 0.01,
+    };
+    let intrinsics = intrinsics().with_distortion(distortion);
+
+    let p = Position::new(0.3, -0.2, 1.5);
+    let uv = intrinsics.project(&p).unwrap();
+    let undistorted = PinholeIntrinsics::new(100., 100., 2., 1.5)
+        .project(&p)
+        .unwrap();
+    assert!((uv - undistorted).norm() > 0.1);
+    assert_abs_diff_eq!(intrinsics.unproject(uv, 1.5), p, epsilon = 1e-5);
+
+    assert_abs_diff_eq!(
+        distortion.undistort(distortion.distort(Point2::new(0.1, 0.2))),
+        Point2::new(0.1, 0.2),
+        epsilon = 1e-6
+    );
+}
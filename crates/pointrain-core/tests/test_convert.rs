@@ -0,0 +1,112 @@
+use pointrain_core::{
+    pc::{
+        ColumnLengthError, PointCloud, PointCloudBase, PointCloudFpfh, PointCloudIntensity,
+        PointCloudNormal, PointCloudRgb, PointCloudRgbNormal, PointCloudRgba, PointCloudWithAlpha,
+        PointCloudWithColor, PointCloudWithDescriptor, PointCloudWithIntensity,
+        PointCloudWithNormal,
+    },
+    point::PointRgbNormal,
+    types::{Normal, Position, Rgb},
+};
+
+fn test_pc() -> PointCloudRgbNormal {
+    (0..3)
+        .map(|i| PointRgbNormal {
+            position: Position::new(i as f32, 0., 0.),
+            color: Rgb::new(i, 0, 0),
+            normal: Normal::z(),
+            curvature: 0.5,
+        })
+        .collect()
+}
+
+#[test]
+fn test_narrowing() {
+    let pc = test_pc();
+    let moved = pc.clone();
+    let positions = moved.positions().as_ptr();
+    let colors = moved.colors().as_ptr();
+
+    let rgb = PointCloudRgb::from(moved);
+    assert_eq!(rgb.colors(), pc.colors());
+    // Columns are moved, not copied.
+    assert_eq!(rgb.positions().as_ptr(), positions);
+    assert_eq!(rgb.colors().as_ptr(), colors);
+
+    let normal = PointCloudNormal::from(pc.clone());
+    assert_eq!(normal.normals(), pc.normals());
+    assert_eq!(normal.curvatures(), pc.curvatures());
+
+    let xyz: PointCloud = pc.clone().into();
+    assert_eq!(xyz.positions(), pc.positions());
+}
+
+#[test]
+fn test_widening_default() {
+    let rgb = PointCloudRgb::from(test_pc());
+
+    let rgba = PointCloudRgba::from(rgb.clone());
+    assert_eq!(rgba.colors(), rgb.colors());
+    assert_eq!(rgba.alphas(), [u8::MAX; 3]);
+
+    let rgb_normal = PointCloudRgbNormal::from(rgb);
+    assert_eq!(rgb_normal.normals(), [Normal::zeros(); 3]);
+    assert_eq!(rgb_normal.curvatures(), [0.; 3]);
+
+    let fpfh = PointCloudFpfh::from(PointCloud::from(rgb_normal));
+    assert_eq!(fpfh.len(), 3);
+    assert_eq!(fpfh.descriptors()[0], [0.; 33]);
+
+    let rgba = PointCloudRgba::from(PointCloud::from(fpfh));
+    assert_eq!(rgba.colors(), [Rgb::zeros(); 3]);
+    assert_eq!(rgba.alphas(), [u8::MAX; 3]);
+}
+
+#[test]
+fn test_with_columns() {
+    let pc = PointCloud::from(test_pc());
+    let normals = vec![Normal::x(); 3];
+    let ptr = normals.as_ptr();
+
+    let pc = pc.with_normals(normals).unwrap();
+    assert_eq!(pc.normals().as_ptr(), ptr);
+    assert_eq!(pc.curvatures(), [0.; 3]);
+
+    let pc = pc.with_colors(vec![Rgb::new(1, 2, 3); 3]).unwrap();
+    assert_eq!(pc.colors()[2], Rgb::new(1, 2, 3));
+    assert_eq!(pc.normals()[2], Normal::x());
+
+    let rgb = PointCloudRgb::from(pc);
+    assert_eq!(
+        rgb.with_alphas(vec![1, 2]).unwrap_err(),
+        ColumnLengthError {
+            column: "alphas",
+            len: 2,
+            expected: 3,
+        }
+    );
+}
+
+#[test]
+fn test_try_from_columns() {
+    let pc = PointCloud::from(test_pc());
+
+    let intensity = PointCloudIntensity::try_from((pc.clone(), vec![0.5; 3])).unwrap();
+    assert_eq!(intensity.intensities(), [0.5; 3]);
+
+    let rgb = PointCloudRgb::try_from((pc.clone(), vec![Rgb::new(1, 2, 3); 3])).unwrap();
+    assert_eq!(
+        PointCloudRgbNormal::try_from((rgb, vec![Normal::x(); 2])).unwrap_err(),
+        ColumnLengthError {
+            column: "normals",
+            len: 2,
+            expected: 3,
+        }
+    );
+}
+
+#[test]
+fn test_column_length_error() {
+    let err = PointCloud::new().with_intensities(vec![1.]).unwrap_err();
+    assert_eq!(err.to_string(), "intensities has 1 elements, expected 0");
+}
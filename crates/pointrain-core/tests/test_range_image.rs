@@ -0,0 +1,141 @@
+use approx::assert_abs_diff_eq;
+use pointrain_core::{
+    pc::{PointCloud, PointCloudBase, PointCloudIntensity, PointCloudWithIntensity},
+    point::{Point, PointIntensity},
+    range_image::{RangeImage, RangeImageProjection},
+    types::Position,
+};
+
+fn projection() -> RangeImageProjection {
+    RangeImageProjection::uniform(8, 4, 0.2, -0.2)
+}
+
+fn test_pc() -> PointCloudIntensity {
+    [
+        (Position::new(1., 0., 0.), 0.1),
+        // Hidden behind the first point.
+        (Position::new(2., 0., 0.), 0.2),
+        (Position::new(0., 3., 0.1), 0.3),
+        (Position::new(-1., 0., 0.), 0.4),
+        // Out of the vertical field of view and at the origin.
+        (Position::new(1., 0., 1.), 0.5),
+        (Position::new(0., 0., 0.), 0.6),
+    ]
+    .into_iter()
+    .map(|(position, intensity)| PointIntensity {
+        position,
+        intensity,
+    })
+    .collect()
+}
+
+#[test]
+fn test_pixel() {
+    let projection = projection();
+
+    assert_eq!(projection.pixel(&Position::new(1., 0., 0.)), Some((4, 2)));
+    assert_eq!(projection.pixel(&Position::new(0., 1., 0.15)), Some((2, 0)));
+    assert_eq!(
+        projection.pixel(&Position::new(0., -1., -0.15)),
+        Some((6, 3))
+    );
+    assert_eq!(projection.pixel(&Position::new(-1., 0., 0.)), Some((0, 2)));
+    assert_eq!(projection.pixel(&Position::new(1., 0., 0.3)), None);
+
+    for (u, v) in [(0, 0), (3, 1), (7, 3)] {
+        let p = Position::from(projection.direction(u, v) * 5.);
+        assert_eq!(projection.pixel(&p), Some((u, v)));
+    }
+}
+
+#[test]
+fn test_beams() {
+    let projection = RangeImageProjection::beams(4, vec![-0.1, 0.1, 0.]);
+    let at = |elevation: f32| Position::new(elevation.cos(), 0., elevation.sin());
+
+    assert_eq!(projection.height(), 3);
+    assert_eq!(projection.pixel(&at(0.14)), Some((2, 0)));
+    assert_eq!(projection.pixel(&at(0.04)), Some((2, 1)));
+    assert_eq!(projection.pixel(&at(-0.12)), Some((2, 2)));
+    assert_eq!(projection.pixel(&at(0.16)), None);
+    assert_eq!(projection.pixel(&at(-0.16)), None);
+    assert_abs_diff_eq!(projection.direction(2, 0).z, 0.1f32.sin(), epsilon = 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "width must be positive")]
+fn test_beams_zero_width() {
+    RangeImageProjection::beams(0, vec![0.]);
+}
+
+#[test]
+#[should_panic(expected = "width and height must be positive")]
+fn test_uniform_zero_height() {
+    RangeImageProjection::uniform(8, 0, 0.2, -0.2);
+}
+
+#[test]
+fn test_range_image() {
+    let pc = test_pc();
+    let image = RangeImage::with_intensity(&pc, projection());
+
+    assert_eq!((image.width(), image.height()), (8, 4));
+    assert_eq!(image.range.get(4, 2), Some(&1.));
+    assert_eq!(image.index.get(4, 2), Some(&Some(0)));
+    assert_eq!(image.index.get(0, 2), Some(&Some(3)));
+    assert_eq!(image.index.get(2, 1), Some(&Some(2)));
+    assert_eq!(image.index.data().iter().flatten().count(), 3);
+    assert_eq!(image.range.data().iter().filter(|&&r| r != 0.).count(), 3);
+
+    let intensity = image.intensity.as_ref().unwrap();
+    assert_eq!(intensity.get(4, 2), Some(&0.1));
+    assert_eq!(intensity.get(1, 1), Some(&0.));
+
+    assert!(RangeImage::new(&pc, projection()).intensity.is_none());
+}
+
+#[test]
+fn test_neighbors() {
+    let image = RangeImage::new(&test_pc(), projection());
+
+    let mut neighbors: Vec<_> = image.neighbors(7, 1, 1).collect();
+    neighbors.sort();
+    assert_eq!(neighbors, [3]);
+
+    let mut neighbors: Vec<_> = image.neighbors(3, 2, 1).collect();
+    neighbors.sort();
+    assert_eq!(neighbors, [0, 2]);
+    assert_eq!(image.neighbors(0, 0, 10).count(), 3);
+}
+
+#[test]
+fn test_to_pc() {
+    let projection = projection();
+    let pc: PointCloud = [(1, 1, 2.), (6, 3, 10.)]
+        .into_iter()
+        .map(|(u, v, r)| Point {
+            position: (projection.direction(u, v) * r).into(),
+        })
+        .collect();
+
+    let organized = RangeImage::new(&pc, projection).to_pc();
+    assert_eq!(organized.pc.len(), 32);
+    assert_abs_diff_eq!(
+        organized.pc.positions()[organized.index(1, 1)],
+        pc.positions()[0],
+        epsilon = 1e-5
+    );
+    assert_abs_diff_eq!(
+        organized.pc.positions()[organized.index(6, 3)],
+        pc.positions()[1],
+        epsilon = 1e-5
+    );
+    assert!(organized.pc.positions()[0].x.is_nan());
+
+    let image = RangeImage::with_intensity(&test_pc(), self::projection());
+    let organized = image.to_pc_with_intensity().unwrap();
+    assert_eq!(organized.pc.intensities()[organized.index(4, 2)], 0.1);
+    assert!(RangeImage::new(&pc, self::projection())
+        .to_pc_with_intensity()
+        .is_none());
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "serde")]
+
+use pointrain_core::{
+    pc::{
+        PointCloud, PointCloudBase, PointCloudFpfh, PointCloudRgba, PointCloudWithAlpha,
+        PointCloudWithColor, PointCloudWithDescriptor,
+    },
+    point::{Point, PointFpfh, PointRgbNormal, PointRgba},
+    types::{Normal, Position, Rgb},
+};
+use serde_json::json;
+
+#[test]
+fn test_serde_point() {
+    let p = PointRgbNormal {
+        position: Position::new(1., 2., 3.),
+        color: Rgb::new(4, 5, 6),
+        normal: Normal::new(0., 0., 1.),
+        curvature: 0.5,
+    };
+
+    let value = serde_json::to_value(p).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "position": [1., 2., 3.],
+            "color": [4, 5, 6],
+            "normal": [0., 0., 1.],
+            "curvature": 0.5,
+        })
+    );
+    assert_eq!(serde_json::from_value::<PointRgbNormal>(value).unwrap(), p);
+}
+
+#[test]
+fn test_serde_point_cloud_columns() {
+    let pc: PointCloudRgba = [(1., 10), (2., 20)]
+        .into_iter()
+        .map(|(x, a)| PointRgba {
+            position: Position::new(x, 0., 0.),
+            color: Rgb::new(1, 2, 3),
+            alpha: a,
+        })
+        .collect();
+
+    let value = serde_json::to_value(&pc).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "positions": [[1., 0., 0.], [2., 0., 0.]],
+            "colors": [[1, 2, 3], [1, 2, 3]],
+            "alphas": [10, 20],
+        })
+    );
+
+    let decoded: PointCloudRgba = serde_json::from_value(value).unwrap();
+    assert_eq!(decoded.positions(), pc.positions());
+    assert_eq!(decoded.colors(), pc.colors());
+    assert_eq!(decoded.alphas(), pc.alphas());
+}
+
+#[test]
+fn test_serde_point_cloud_length_mismatch() {
+    let value = json!({
+        "positions": [[1., 0., 0.], [2., 0., 0.]],
+        "colors": [[1, 2, 3]],
+        "alphas": [10, 20],
+    });
+
+    let err = serde_json::from_value::<PointCloudRgba>(value).unwrap_err();
+    assert!(err.to_string().contains("colors"));
+}
+
+#[test]
+fn test_serde_descriptor() {
+    let mut descriptor = [0.; 33];
+    descriptor[32] = 1.;
+    let p = PointFpfh {
+        position: Position::new(1., 2., 3.),
+        descriptor,
+    };
+    let pc: PointCloudFpfh = [p].into_iter().collect();
+
+    let json = serde_json::to_string(&pc).unwrap();
+    let decoded: PointCloudFpfh = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.descriptors(), pc.descriptors());
+    assert_eq!(
+        serde_json::from_str::<PointFpfh>(&serde_json::to_string(&p).unwrap()).unwrap(),
+        p
+    );
+
+    // Descriptors of the wrong length are rejected.
+    let json = json!({ "positions": [[0., 0., 0.]], "descriptors": [[1., 2.]] });
+    assert!(serde_json::from_value::<PointCloudFpfh>(json).is_err());
+}
+
+#[test]
+fn test_serde_point_cloud_xyz() {
+    let pc: PointCloud = [Point {
+        position: Position::new(1., 2., 3.),
+    }]
+    .into_iter()
+    .collect();
+
+    let decoded: PointCloud = serde_json::from_str(&serde_json::to_string(&pc).unwrap()).unwrap();
+    assert_eq!(decoded.positions(), pc.positions());
+}
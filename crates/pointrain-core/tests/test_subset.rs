@@ -0,0 +1,151 @@
+use pointrain_core::{
+    pc::{PointCloudBase, PointCloudRgba, PointCloudWithAlpha, PointCloudWithColor},
+    point::{PointBase, PointRgba},
+    types::{Position, Rgb},
+};
+
+fn point(i: u8) -> PointRgba {
+    PointRgba {
+        position: Position::new(i.into(), 0., 0.),
+        color: Rgb::new(i, i, i),
+        alpha: i,
+    }
+}
+
+/// Points whose position, color and alpha are all `i` for each `i` in `0..n`.
+fn test_pc(n: u8) -> PointCloudRgba {
+    (0..n).map(point).collect()
+}
+
+fn ids(pc: &PointCloudRgba) -> Vec<u8> {
+    // Columns must stay aligned.
+    for ((p, c), &a) in pc.positions().iter().zip(pc.colors()).zip(pc.alphas()) {
+        assert_eq!((p.x as u8, c.x), (a, a));
+    }
+    pc.alphas().to_vec()
+}
+
+#[test]
+fn test_select() {
+    let pc = test_pc(5);
+
+    assert_eq!(ids(&pc.select(&[3, 1, 1, 4])), [3, 1, 1, 4]);
+    assert!(pc.select(&[]).is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_select_out_of_bounds() {
+    test_pc(5).select(&[5]);
+}
+
+#[test]
+fn test_filter_mask() {
+    let pc = test_pc(4);
+
+    assert_eq!(ids(&pc.filter_mask(&[true, false, false, true])), [0, 3]);
+}
+
+#[test]
+#[should_panic(expected = "mask length mismatch")]
+fn test_filter_mask_length() {
+    test_pc(4).filter_mask(&[true]);
+}
+
+#[test]
+fn test_retain() {
+    let mut pc = test_pc(6);
+    pc.retain(|p| p.position.x > 1. && *p.alpha != 4);
+
+    assert_eq!(ids(&pc), [2, 3, 5]);
+}
+
+#[test]
+fn test_truncate_swap_remove() {
+    let mut pc = test_pc(5);
+
+    pc.truncate(4);
+    assert_eq!(ids(&pc), [0, 1, 2, 3]);
+    assert_eq!(pc.swap_remove(1), point(1));
+    assert_eq!(ids(&pc), [0, 3, 2]);
+}
+
+#[test]
+fn test_split_off_append_extend() {
+    let mut pc = test_pc(5);
+
+    let mut tail = pc.split_off(3);
+    assert_eq!(ids(&pc), [0, 1, 2]);
+    assert_eq!(ids(&tail), [3, 4]);
+
+    tail.append(&mut pc);
+    assert!(pc.is_empty());
+    assert_eq!(ids(&tail), [3, 4, 0, 1, 2]);
+
+    tail.extend([7, 8].map(point));
+    assert_eq!(ids(&tail), [3, 4, 0, 1, 2, 7, 8]);
+}
+
+/// A cloud implementing only the required methods, to exercise the defaults.
+#[derive(Default)]
+struct Minimal(PointCloudRgba);
+
+impl PointCloudBase for Minimal {
+    type Point = PointRgba;
+    type Iter<'a> = <PointCloudRgba as PointCloudBase>::Iter<'a>;
+    type IterMut<'a> = <PointCloudRgba as PointCloudBase>::IterMut<'a>;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self(PointCloudRgba::with_capacity(capacity))
+    }
+    fn resize(&mut self, new_len: usize, value: Self::Point) {
+        self.0.resize(new_len, value)
+    }
+    fn positions(&self) -> &[Position] {
+        self.0.positions()
+    }
+    fn positions_mut(&mut self) -> &mut [Position] {
+        self.0.positions_mut()
+    }
+    fn push(&mut self, p: Self::Point) -> &mut Self {
+        self.0.push(p);
+        self
+    }
+    fn push_ref(&mut self, p: <Self::Point as PointBase>::Ref<'_>) -> &mut Self {
+        self.0.push_ref(p);
+        self
+    }
+    fn iter(&self) -> Self::Iter<'_> {
+        self.0.iter()
+    }
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        self.0.iter_mut()
+    }
+}
+
+#[test]
+fn test_default_methods() {
+    let mut pc = Minimal(test_pc(8));
+
+    assert_eq!(ids(&pc.select(&[3, 1, 1, 4]).0), [3, 1, 1, 4]);
+    assert_eq!(
+        ids(&pc
+            .filter_mask(&[true, false, false, true, false, false, false, true])
+            .0),
+        [0, 3, 7]
+    );
+
+    pc.retain(|p| *p.alpha != 2);
+    assert_eq!(ids(&pc.0), [0, 1, 3, 4, 5, 6, 7]);
+    pc.truncate(6);
+    assert_eq!(ids(&pc.0), [0, 1, 3, 4, 5, 6]);
+    assert_eq!(pc.swap_remove(1), point(1));
+    assert_eq!(ids(&pc.0), [0, 6, 3, 4, 5]);
+
+    let mut tail = pc.split_off(3);
+    assert_eq!(ids(&pc.0), [0, 6, 3]);
+    assert_eq!(ids(&tail.0), [4, 5]);
+    tail.append(&mut pc);
+    assert!(pc.is_empty());
+    assert_eq!(ids(&tail.0), [4, 5, 0, 6, 3]);
+}
@@ -0,0 +1,282 @@
+//! Region of interest filters.
+//!
+//! Each filter gives a mask with `true` for the points to keep, or the cloud of those points.
+//! `negative` keeps the points outside the region instead; points with a non-finite position
+//! are dropped either way.
+
+use pointrain_core::{
+    nalgebra::{Isometry3, Point2, Vector3},
+    pc::{PointCloudBase, PointCloudWithIntensity},
+    types::{Float, Normal, Position},
+};
+
+use crate::utility;
+
+fn mask<PC: PointCloudBase>(
+    pc: &PC,
+    negative: bool,
+    inside: impl Fn(&Position) -> bool,
+) -> Vec<bool> {
+    pc.positions()
+        .iter()
+        .map(|p| utility::is_finite(p) && inside(p) != negative)
+        .collect()
+}
+
+/// Keeps the points in an oriented box.
+#[derive(Debug, Clone)]
+pub struct CropBox {
+    /// Corners of the box in its own frame.
+    pub min: Position,
+    pub max: Position,
+    /// Pose of the box frame in the cloud frame.
+    pub pose: Isometry3<Float>,
+    pub negative: bool,
+}
+
+impl CropBox {
+    /// Axis-aligned box.
+    pub fn new(min: Position, max: Position) -> Self {
+        Self {
+            min,
+            max,
+            pose: Isometry3::identity(),
+            negative: false,
+        }
+    }
+
+    pub fn contains(&self, p: &Position) -> bool {
+        let p = self.pose.inverse_transform_point(p);
+        (0..3).all(|i| self.min[i] <= p[i] && p[i] <= self.max[i])
+    }
+
+    pub fn mask<PC: PointCloudBase>(&self, pc: &PC) -> Vec<bool> {
+        mask(pc, self.negative, |p| self.contains(p))
+    }
+
+    pub fn filter<PC: PointCloudBase>(&self, pc: &PC) -> PC {
+        pc.filter_mask(&self.mask(pc))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// Keeps the points whose coordinate along `axis` is within `min..=max`.
+#[derive(Debug, Clone)]
+pub struct PassThrough {
+    pub axis: Axis,
+    pub min: Float,
+    pub max: Float,
+    pub negative: bool,
+}
+
+impl PassThrough {
+    pub fn new(axis: Axis, min: Float, max: Float) -> Self {
+        Self {
+            axis,
+            min,
+            max,
+            negative: false,
+        }
+    }
+
+    pub fn mask<PC: PointCloudBase>(&self, pc: &PC) -> Vec<bool> {
+        let i = self.axis as usize;
+        mask(pc, self.negative, |p| (self.min..=self.max).contains(&p[i]))
+    }
+
+    pub fn filter<PC: PointCloudBase>(&self, pc: &PC) -> PC {
+        pc.filter_mask(&self.mask(pc))
+    }
+}
+
+/// Keeps the points whose intensity is within `min..=max`.
+#[derive(Debug, Clone)]
+pub struct IntensityPassThrough {
+    pub min: Float,
+    pub max: Float,
+    pub negative: bool,
+}
+
+impl IntensityPassThrough {
+    pub fn new(min: Float, max: Float) -> Self {
+        Self {
+            min,
+            max,
+            negative: false,
+        }
+    }
+
+    pub fn mask<PC: PointCloudWithIntensity>(&self, pc: &PC) -> Vec<bool> {
+        pc.positions()
+            .iter()
+            .zip(pc.intensities())
+            .map(|(p, i)| {
+                // NaN intensities are outside either way.
+                utility::is_finite(p)
+                    && !i.is_nan()
+                    && (self.min..=self.max).contains(i) != self.negative
+            })
+            .collect()
+    }
+
+    pub fn filter<PC: PointCloudWithIntensity>(&self, pc: &PC) -> PC {
+        pc.filter_mask(&self.mask(pc))
+    }
+}
+
+/// Keeps the points above a planar polygon, as PCL's `ExtractPolygonalPrismData`.
+///
+/// Heights are signed distances from the polygon plane, positive on the side of the normal
+/// of the counterclockwise vertex order.
+#[derive(Debug, Clone)]
+pub struct PolygonPrism {
+    origin: Position,
+    /// Orthonormal basis with `normal = u × v`.
+    u: Vector3<Float>,
+    v: Vector3<Float>,
+    normal: Normal,
+    /// Vertices in the `u`, `v` plane frame, empty for a degenerate polygon.
+    polygon: Vec<Point2<Float>>,
+    pub min_height: Float,
+    pub max_height: Float,
+    pub negative: bool,
+}
+
+impl PolygonPrism {
+    /// Prism over a (possibly non-convex) polygon, whose vertices are projected on their
+    /// best-fit plane.
+    pub fn new(polygon: &[Position], min_height: Float, max_height: Float) -> Self {
+        // Twice the area vector, also defined for slightly non-planar polygons.
+        let mut normal = Normal::zeros();
+        for (a, b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+            normal += a.coords.cross(&b.coords);
+        }
+        let normal = normal.try_normalize(0.).unwrap_or_else(Vector3::z);
+        let origin = polygon.first().copied().unwrap_or_else(Position::origin);
+
+        let mut prism = Self::with_plane(origin, normal, min_height, max_height);
+        prism.polygon = polygon.iter().map(|p| prism.project(p)).collect();
+        if prism.polygon.len() < 3 {
+            prism.polygon.clear();
+        }
+        prism
+    }
+
+    /// Prism over the convex hull of `points` projected on the plane through their
+    /// centroid with `normal`.
+    pub fn from_convex_hull(
+        points: &[Position],
+        normal: Normal,
+        min_height: Float,
+        max_height: Float,
+    ) -> Self {
+        let centroid = points
+            .iter()
+            .fold(Vector3::zeros(), |sum, p| sum + p.coords)
+            / points.len().max(1) as Float;
+        let normal = normal.try_normalize(0.).unwrap_or_else(Vector3::z);
+
+        let mut prism = Self::with_plane(centroid.into(), normal, min_height, max_height);
+        let projected: Vec<_> = points.iter().map(|p| prism.project(p)).collect();
+        prism.polygon = convex_hull(projected);
+        prism
+    }
+
+    fn with_plane(origin: Position, normal: Normal, min_height: Float, max_height: Float) -> Self {
+        let axis = if normal.x.abs() < 0.9 {
+            Vector3::x()
+        } else {
+            Vector3::y()
+        };
+        let v = normal.cross(&axis).normalize();
+        let u = v.cross(&normal);
+        Self {
+            origin,
+            u,
+            v,
+            normal,
+            polygon: Vec::new(),
+            min_height,
+            max_height,
+            negative: false,
+        }
+    }
+
+    fn project(&self, p: &Position) -> Point2<Float> {
+        let d = p - self.origin;
+        Point2::new(d.dot(&self.u), d.dot(&self.v))
+    }
+
+    /// Vertices of the polygon projected on its plane.
+    pub fn polygon(&self) -> Vec<Position> {
+        self.polygon
+            .iter()
+            .map(|q| self.origin + self.u * q.x + self.v * q.y)
+            .collect()
+    }
+
+    pub fn normal(&self) -> &Normal {
+        &self.normal
+    }
+
+    pub fn contains(&self, p: &Position) -> bool {
+        let height = (p - self.origin).dot(&self.normal);
+        if !(self.min_height..=self.max_height).contains(&height) {
+            return false;
+        }
+
+        // Even-odd rule.
+        let q = self.project(p);
+        let mut inside = false;
+        for (a, b) in self.polygon.iter().zip(self.polygon.iter().cycle().skip(1)) {
+            if (a.y > q.y) != (b.y > q.y) && q.x < a.x + (q.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    pub fn mask<PC: PointCloudBase>(&self, pc: &PC) -> Vec<bool> {
+        mask(pc, self.negative, |p| self.contains(p))
+    }
+
+    pub fn filter<PC: PointCloudBase>(&self, pc: &PC) -> PC {
+        pc.filter_mask(&self.mask(pc))
+    }
+}
+
+/// Counterclockwise convex hull by Andrew's monotone chain, empty if degenerate.
+fn convex_hull(mut points: Vec<Point2<Float>>) -> Vec<Point2<Float>> {
+    points.retain(|p| p.x.is_finite() && p.y.is_finite());
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+
+    let cross = |o: &Point2<Float>, a: &Point2<Float>, b: &Point2<Float>| (a - o).perp(&(b - o));
+    let mut hull: Vec<Point2<Float>> = Vec::with_capacity(2 * points.len());
+    for pass in [
+        &points[..],
+        &points.iter().rev().copied().collect::<Vec<_>>()[..],
+    ] {
+        let start = hull.len();
+        for p in pass {
+            while hull.len() >= start + 2
+                && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= 0.
+            {
+                hull.pop();
+            }
+            hull.push(*p);
+        }
+        hull.pop();
+    }
+
+    if hull.len() < 3 {
+        hull.clear();
+    }
+    hull
+}
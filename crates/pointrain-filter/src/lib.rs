@@ -1,9 +1,14 @@
+pub mod crop;
+pub mod octree;
+pub mod sampling;
+pub mod search;
 pub mod utility;
 pub mod voxel_grid;
 
 use kiddo::KdTree;
 use pointrain_core::pc::PointCloudBase;
 
+#[deprecated(note = "use `KdTreeSearch`, which does not expose kiddo")]
 pub fn kdtree<PC: PointCloudBase>(pc: &PC) -> KdTree<f32, 3> {
     let mut kdtree = KdTree::with_capacity(pc.len());
 
@@ -13,4 +18,8 @@ pub fn kdtree<PC: PointCloudBase>(pc: &PC) -> KdTree<f32, 3> {
     kdtree
 }
 
+pub use crop::{Axis, CropBox, IntensityPassThrough, PassThrough, PolygonPrism};
+pub use octree::{Octree, OctreeNode};
+pub use sampling::{FarthestPointSampling, RandomSampling, VoxelGridSampling};
+pub use search::{BruteForceSearch, KdTreeSearch, Neighbor, NeighborSearch, VoxelHashSearch};
 pub use voxel_grid::VoxelGrid;
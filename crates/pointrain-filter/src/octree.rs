@@ -0,0 +1,395 @@
+//! Octree over point positions.
+//!
+//! Leaves are cubes of `resolution` aligned on a grid through the world origin, so that
+//! octrees of the same resolution share their voxels and can be compared. The root grows as
+//! points are added outside of it.
+
+mod query;
+
+use pointrain_core::{
+    nalgebra,
+    pc::PointCloudBase,
+    types::{Float, Position},
+};
+
+use crate::utility;
+
+/// Integer coordinates of a leaf voxel.
+type Key = [i64; 3];
+
+/// Bound of the key coordinates. Keys span at most `2^61` leaves, so the root is at most
+/// `62` levels deep and its bounds fit into `i64`.
+const MAX_KEY: i64 = 1 << 60;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Branch([Option<usize>; 8]),
+    /// Indices of the points in the leaf.
+    Leaf(Vec<usize>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Octree {
+    resolution: Float,
+    positions: Vec<Position>,
+    nodes: Vec<Node>,
+    root: usize,
+    /// Key of the minimum corner of the root, which spans `1 << depth` leaves.
+    root_min: Key,
+    depth: u32,
+    len: usize,
+}
+
+impl Octree {
+    /// # Panics
+    ///
+    /// Panics unless `resolution` is positive.
+    pub fn new(resolution: Float) -> Self {
+        assert!(resolution > 0., "resolution must be positive");
+        Self {
+            resolution,
+            positions: Vec::new(),
+            nodes: Vec::new(),
+            root: 0,
+            root_min: [0; 3],
+            depth: 0,
+            len: 0,
+        }
+    }
+
+    pub fn from_pc<PC: PointCloudBase>(pc: &PC, resolution: Float) -> Self {
+        let mut octree = Self::new(resolution);
+        octree.add_pc(pc);
+        octree
+    }
+
+    /// Adds the points of a cloud. Point indices follow the order in which points are added,
+    /// so they are the indices into `pc` for an octree built from a single cloud.
+    pub fn add_pc<PC: PointCloudBase>(&mut self, pc: &PC) {
+        self.positions.reserve(pc.len());
+        for p in pc.positions() {
+            self.add_point(*p);
+        }
+    }
+
+    /// Adds a point and returns its index. Points with a non-finite position, or more than
+    /// `2^60` leaves away from the origin, get an index but are not inserted.
+    pub fn add_point(&mut self, p: Position) -> usize {
+        let index = self.positions.len();
+        self.positions.push(p);
+        let Some(key) = self.key(&p) else {
+            return index;
+        };
+
+        let leaf = self.insert(key);
+        let Node::Leaf(indices) = &mut self.nodes[leaf] else {
+            unreachable!("insert returns a leaf");
+        };
+        indices.push(index);
+        self.len += 1;
+        index
+    }
+
+    pub fn resolution(&self) -> Float {
+        self.resolution
+    }
+
+    /// Number of levels below the root, the root is a leaf at depth zero.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Number of inserted points.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Positions of the added points, by index.
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Key of the leaf containing `p`, `None` if `p` is not finite or out of range.
+    fn key(&self, p: &Position) -> Option<Key> {
+        if !utility::is_finite(p) {
+            return None;
+        }
+        let key = p.coords.map(|v| (v / self.resolution).floor());
+        if key.iter().any(|k| k.abs() > MAX_KEY as Float) {
+            return None;
+        }
+        Some(key.map(|k| k as i64).into())
+    }
+
+    fn contains_key(&self, key: &Key) -> bool {
+        let size = 1 << self.depth;
+        (0..3).all(|a| self.root_min[a] <= key[a] && key[a] < self.root_min[a] + size)
+    }
+
+    /// Leaf of `key`, created along with its parents as needed.
+    fn insert(&mut self, key: Key) -> usize {
+        if self.nodes.is_empty() {
+            self.nodes.push(Node::Leaf(Vec::new()));
+            self.root = 0;
+            self.root_min = key;
+        }
+        while !self.contains_key(&key) {
+            self.grow(&key);
+        }
+
+        let (mut node, mut min) = (self.root, self.root_min);
+        for level in (0..self.depth).rev() {
+            let half = 1 << level;
+            let octant = octant(&key, &min, half);
+            for (a, m) in min.iter_mut().enumerate() {
+                if octant & (1 << a) != 0 {
+                    *m += half;
+                }
+            }
+
+            let child = match &self.nodes[node] {
+                Node::Branch(children) => children[octant],
+                Node::Leaf(_) => unreachable!("leaves are at the bottom level"),
+            };
+            node = child.unwrap_or_else(|| {
+                let child = self.nodes.len();
+                self.nodes.push(if level == 0 {
+                    Node::Leaf(Vec::new())
+                } else {
+                    Node::Branch([None; 8])
+                });
+                if let Node::Branch(children) = &mut self.nodes[node] {
+                    children[octant] = Some(child);
+                }
+                child
+            });
+        }
+        node
+    }
+
+    /// Doubles the root towards `key`.
+    fn grow(&mut self, key: &Key) {
+        let size = 1 << self.depth;
+        let mut children = [None; 8];
+        let mut min = self.root_min;
+        let mut octant = 0;
+        for (a, m) in min.iter_mut().enumerate() {
+            if key[a] < *m {
+                *m -= size;
+                octant |= 1 << a;
+            }
+        }
+        children[octant] = Some(self.root);
+
+        self.root = self.nodes.len();
+        self.nodes.push(Node::Branch(children));
+        self.root_min = min;
+        self.depth += 1;
+    }
+
+    /// Point indices of the leaf of `key`, if occupied.
+    fn leaf(&self, key: &Key) -> Option<&[usize]> {
+        if self.nodes.is_empty() || !self.contains_key(key) {
+            return None;
+        }
+        let (mut node, mut min) = (self.root, self.root_min);
+        for level in (0..self.depth).rev() {
+            let half = 1 << level;
+            let octant = octant(key, &min, half);
+            for (a, m) in min.iter_mut().enumerate() {
+                if octant & (1 << a) != 0 {
+                    *m += half;
+                }
+            }
+            let Node::Branch(children) = &self.nodes[node] else {
+                unreachable!("leaves are at the bottom level");
+            };
+            node = children[octant]?;
+        }
+        match &self.nodes[node] {
+            Node::Leaf(indices) => Some(indices),
+            Node::Branch(_) => unreachable!("branches are above the bottom level"),
+        }
+    }
+
+    /// Whether the leaf voxel containing `p` has points.
+    pub fn is_occupied(&self, p: &Position) -> bool {
+        self.key(p).is_some_and(|key| self.leaf(&key).is_some())
+    }
+
+    /// Nodes in depth-first pre-order, children in octant order (x, then y, then z bit).
+    pub fn depth_first(&self) -> DepthFirst<'_> {
+        DepthFirst {
+            stack: if self.nodes.is_empty() {
+                Vec::new()
+            } else {
+                vec![self.node(self.root, self.root_min, 0)]
+            },
+        }
+    }
+
+    /// Leaves in depth-first order.
+    pub fn leaves(&self) -> impl Iterator<Item = OctreeNode<'_>> {
+        self.depth_first().filter(|n| n.is_leaf())
+    }
+
+    /// Centers of the occupied leaf voxels.
+    pub fn voxel_centers(&self) -> impl Iterator<Item = Position> + '_ {
+        self.leaves().map(|n| n.center())
+    }
+
+    /// Indices of the points of `self` in leaf voxels with fewer than `min_points` points in
+    /// `previous` (one to find the voxels empty there), e.g. what appeared since an earlier
+    /// scan. Swap the octrees for what disappeared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the octrees have different resolutions.
+    pub fn changes(&self, previous: &Octree, min_points: usize) -> Vec<usize> {
+        assert_eq!(
+            self.resolution, previous.resolution,
+            "octrees must have the same resolution"
+        );
+
+        let mut indices = Vec::new();
+        for leaf in self.leaves() {
+            let count = previous.leaf(&leaf.min_key).map_or(0, |l| l.len());
+            if count < min_points.max(1) {
+                indices.extend(leaf.indices());
+            }
+        }
+        indices.sort_unstable();
+        indices
+    }
+
+    fn node(&self, node: usize, min_key: Key, level: u32) -> OctreeNode<'_> {
+        OctreeNode {
+            octree: self,
+            node,
+            min_key,
+            level,
+        }
+    }
+}
+
+/// Octant of `key` in the node at `min` with children of `half` leaves.
+fn octant(key: &Key, min: &Key, half: i64) -> usize {
+    (0..3)
+        .filter(|&a| key[a] >= min[a] + half)
+        .map(|a| 1 << a)
+        .sum()
+}
+
+/// A node of an [`Octree`].
+#[derive(Debug, Clone, Copy)]
+pub struct OctreeNode<'a> {
+    octree: &'a Octree,
+    node: usize,
+    min_key: Key,
+    level: u32,
+}
+
+impl<'a> OctreeNode<'a> {
+    /// Depth from the root, leaves are at [`Octree::depth`].
+    pub fn depth(&self) -> u32 {
+        self.level
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        matches!(self.octree.nodes[self.node], Node::Leaf(_))
+    }
+
+    /// Number of leaves along an edge of the node.
+    fn edge_leaves(&self) -> i64 {
+        1 << (self.octree.depth - self.level)
+    }
+
+    /// Edge length of the node cube.
+    pub fn size(&self) -> Float {
+        self.octree.resolution * self.edge_leaves() as Float
+    }
+
+    /// Minimum corner of the node cube.
+    pub fn min(&self) -> Position {
+        self.corner(0)
+    }
+
+    pub fn max(&self) -> Position {
+        self.corner(self.edge_leaves())
+    }
+
+    pub fn center(&self) -> Position {
+        nalgebra::center(&self.min(), &self.max())
+    }
+
+    /// Corner offset by `leaves` from the minimum one, from integer coordinates so that
+    /// adjacent nodes share their bounds exactly.
+    fn corner(&self, leaves: i64) -> Position {
+        self.min_key
+            .map(|k| (k + leaves) as Float * self.octree.resolution)
+            .into()
+    }
+
+    /// Indices of the points in the node and its descendants.
+    pub fn indices(&self) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut stack = vec![self.node];
+        while let Some(node) = stack.pop() {
+            match &self.octree.nodes[node] {
+                Node::Branch(children) => stack.extend(children.iter().rev().flatten()),
+                Node::Leaf(leaf) => indices.extend(leaf),
+            }
+        }
+        indices
+    }
+
+    /// Point indices of a leaf, empty for a branch.
+    pub fn leaf_indices(&self) -> &'a [usize] {
+        match &self.octree.nodes[self.node] {
+            Node::Leaf(indices) => indices,
+            Node::Branch(_) => &[],
+        }
+    }
+
+    pub fn children(&self) -> impl Iterator<Item = OctreeNode<'a>> + 'a {
+        let (octree, min_key, level) = (self.octree, self.min_key, self.level);
+        let children = match &octree.nodes[self.node] {
+            Node::Branch(children) => *children,
+            Node::Leaf(_) => [None; 8],
+        };
+        let half = 1 << (octree.depth - level).saturating_sub(1);
+        children
+            .into_iter()
+            .enumerate()
+            .filter_map(move |(octant, child)| {
+                let mut min = min_key;
+                for (a, m) in min.iter_mut().enumerate() {
+                    if octant & (1 << a) != 0 {
+                        *m += half;
+                    }
+                }
+                Some(octree.node(child?, min, level + 1))
+            })
+    }
+}
+
+/// See [`Octree::depth_first`].
+pub struct DepthFirst<'a> {
+    stack: Vec<OctreeNode<'a>>,
+}
+
+impl<'a> Iterator for DepthFirst<'a> {
+    type Item = OctreeNode<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let first = self.stack.len();
+        self.stack.extend(node.children());
+        self.stack[first..].reverse();
+        Some(node)
+    }
+}
@@ -0,0 +1,184 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use pointrain_core::{
+    nalgebra::Vector3,
+    types::{Float, Position},
+};
+
+use super::{Octree, OctreeNode};
+use crate::search::{self, Neighbor, NeighborSearch};
+
+impl Octree {
+    /// Indices of the points in the axis-aligned box from `min` to `max`, ascending.
+    pub fn box_search(&self, min: &Position, max: &Position) -> Vec<usize> {
+        let overlaps = |n: &OctreeNode| {
+            let (n_min, n_max) = (n.min(), n.max());
+            (0..3).all(|a| n_min[a] <= max[a] && min[a] <= n_max[a])
+        };
+
+        let mut indices = Vec::new();
+        let mut stack: Vec<_> = self.depth_first().take(1).collect();
+        while let Some(node) = stack.pop() {
+            if !overlaps(&node) {
+                continue;
+            }
+            stack.extend(node.children());
+            indices.extend(node.leaf_indices().iter().filter(|&&i| {
+                let p = &self.positions[i];
+                (0..3).all(|a| min[a] <= p[a] && p[a] <= max[a])
+            }));
+        }
+        indices.sort_unstable();
+        indices
+    }
+
+    /// Leaves hit by the ray from `origin` along `direction`, in the order they are entered.
+    pub fn ray_search(&self, origin: &Position, direction: &Vector3<Float>) -> Vec<OctreeNode<'_>> {
+        let mut hits = Vec::new();
+        let mut stack: Vec<_> = self.depth_first().take(1).collect();
+        while let Some(node) = stack.pop() {
+            let Some(t) = ray_entry(origin, direction, &node.min(), &node.max()) else {
+                continue;
+            };
+            if node.is_leaf() {
+                hits.push((t, node));
+            } else {
+                stack.extend(node.children());
+            }
+        }
+        hits.sort_by(|a, b| a.0.total_cmp(&b.0));
+        hits.into_iter().map(|(_, node)| node).collect()
+    }
+}
+
+/// Ray parameter at which the ray enters the box (zero if it starts inside), by the slab
+/// method.
+fn ray_entry(
+    origin: &Position,
+    direction: &Vector3<Float>,
+    min: &Position,
+    max: &Position,
+) -> Option<Float> {
+    let (mut enter, mut exit) = (0., Float::INFINITY);
+    for a in 0..3 {
+        if direction[a] == 0. {
+            if origin[a] < min[a] || max[a] < origin[a] {
+                return None;
+            }
+            continue;
+        }
+        let t0 = (min[a] - origin[a]) / direction[a];
+        let t1 = (max[a] - origin[a]) / direction[a];
+        enter = t0.min(t1).max(enter);
+        exit = t0.max(t1).min(exit);
+    }
+    (enter <= exit).then_some(enter)
+}
+
+fn distance_squared_to_box(p: &Position, min: &Position, max: &Position) -> Float {
+    (0..3)
+        .map(|a| (min[a] - p[a]).max(p[a] - max[a]).max(0.).powi(2))
+        .sum()
+}
+
+enum Item<'a> {
+    Node(OctreeNode<'a>),
+    Point(usize),
+}
+
+/// Heap entry, the smallest distance first.
+struct Entry<'a> {
+    distance_squared: Float,
+    item: Item<'a>,
+}
+
+impl PartialEq for Entry<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry<'_> {}
+
+impl PartialOrd for Entry<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance_squared.total_cmp(&self.distance_squared)
+    }
+}
+
+impl NeighborSearch for Octree {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Best-first search over the nodes.
+    fn knn(&self, query: &Position, k: usize) -> Vec<Neighbor> {
+        let mut heap: BinaryHeap<_> = self
+            .depth_first()
+            .take(1)
+            .map(|node| Entry {
+                distance_squared: 0.,
+                item: Item::Node(node),
+            })
+            .collect();
+
+        let mut neighbors = Vec::with_capacity(k);
+        while neighbors.len() < k {
+            let Some(entry) = heap.pop() else {
+                break;
+            };
+            match entry.item {
+                Item::Point(index) => neighbors.push(Neighbor {
+                    index,
+                    distance_squared: entry.distance_squared,
+                }),
+                Item::Node(node) => {
+                    heap.extend(node.children().map(|child| Entry {
+                        distance_squared: distance_squared_to_box(
+                            query,
+                            &child.min(),
+                            &child.max(),
+                        ),
+                        item: Item::Node(child),
+                    }));
+                    heap.extend(node.leaf_indices().iter().map(|&index| Entry {
+                        distance_squared: (self.positions[index] - query).norm_squared(),
+                        item: Item::Point(index),
+                    }));
+                }
+            }
+        }
+
+        // Ties are popped in any order.
+        search::nearest_k(neighbors, k)
+    }
+
+    fn radius(&self, query: &Position, radius: Float) -> Vec<Neighbor> {
+        let radius_squared = radius * radius;
+        let mut neighbors = Vec::new();
+        let mut stack: Vec<_> = self.depth_first().take(1).collect();
+        while let Some(node) = stack.pop() {
+            if distance_squared_to_box(query, &node.min(), &node.max()) > radius_squared {
+                continue;
+            }
+            stack.extend(node.children());
+            for &index in node.leaf_indices() {
+                let distance_squared = (self.positions[index] - query).norm_squared();
+                if distance_squared <= radius_squared {
+                    neighbors.push(Neighbor {
+                        index,
+                        distance_squared,
+                    });
+                }
+            }
+        }
+        search::sort(&mut neighbors);
+        neighbors
+    }
+}
@@ -0,0 +1,228 @@
+//! Downsampling to a given number of points, keeping all attributes of the sampled points.
+
+use std::collections::HashMap;
+
+use pointrain_core::{pc::PointCloudBase, types::Position};
+use rand::{rngs::StdRng, seq::index, SeedableRng};
+
+use crate::utility;
+
+fn finite_indices<PC: PointCloudBase>(pc: &PC) -> Vec<usize> {
+    (0..pc.len())
+        .filter(|&i| utility::is_finite(&pc.positions()[i]))
+        .collect()
+}
+
+/// Iteratively picks the point farthest from those already picked.
+///
+/// Output is in picking order, so every prefix is itself a farthest point sampling.
+/// Clouds with fewer finite points than `num_samples` are returned whole.
+#[derive(Debug, Default)]
+pub struct FarthestPointSampling {
+    pub num_samples: usize,
+    /// Index of the first pick, the first finite point is used if it is not finite.
+    pub start_index: usize,
+}
+
+impl FarthestPointSampling {
+    pub fn new(num_samples: usize) -> Self {
+        Self {
+            num_samples,
+            ..Default::default()
+        }
+    }
+
+    pub fn filter<PC: PointCloudBase>(&self, pc: &PC) -> PC {
+        pc.select(&self.indices(pc))
+    }
+
+    /// Indices into `pc` of the picked points.
+    pub fn indices<PC: PointCloudBase>(&self, pc: &PC) -> Vec<usize> {
+        let candidates = finite_indices(pc);
+        let num_samples = self.num_samples.min(candidates.len());
+        if num_samples == 0 {
+            return Vec::new();
+        }
+
+        let positions = pc.positions();
+        let start = candidates
+            .iter()
+            .position(|&i| i == self.start_index)
+            .unwrap_or(0);
+
+        // Squared distance of each candidate to the nearest picked point, -inf once picked.
+        let mut distances = vec![f32::INFINITY; candidates.len()];
+        let mut picked = Vec::with_capacity(num_samples);
+        let mut next = start;
+        while picked.len() < num_samples {
+            let p = positions[candidates[next]];
+            picked.push(candidates[next]);
+            distances[next] = f32::NEG_INFINITY;
+
+            let mut farthest = (0, -1.);
+            for (j, (d, &i)) in distances.iter_mut().zip(&candidates).enumerate() {
+                *d = d.min((positions[i] - p).norm_squared());
+                if *d > farthest.1 {
+                    farthest = (j, *d);
+                }
+            }
+            next = farthest.0;
+        }
+
+        picked
+    }
+}
+
+/// Uniformly picks `num_samples` points without replacement, reproducibly for a `seed`.
+///
+/// Output keeps the input order. Clouds with fewer finite points than `num_samples` are
+/// returned whole.
+#[derive(Debug, Default)]
+pub struct RandomSampling {
+    pub num_samples: usize,
+    pub seed: u64,
+}
+
+impl RandomSampling {
+    pub fn new(num_samples: usize, seed: u64) -> Self {
+        Self { num_samples, seed }
+    }
+
+    pub fn filter<PC: PointCloudBase>(&self, pc: &PC) -> PC {
+        pc.select(&self.indices(pc))
+    }
+
+    /// Indices into `pc` of the picked points, ascending.
+    pub fn indices<PC: PointCloudBase>(&self, pc: &PC) -> Vec<usize> {
+        let candidates = finite_indices(pc);
+        let num_samples = self.num_samples.min(candidates.len());
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut picked: Vec<_> = index::sample(&mut rng, candidates.len(), num_samples)
+            .into_iter()
+            .map(|i| candidates[i])
+            .collect();
+        picked.sort_unstable();
+        picked
+    }
+}
+
+/// Voxel grid downsampling with the cubic leaf size searched so that about `num_points`
+/// voxels are occupied.
+///
+/// Unlike [`crate::VoxelGrid`], each voxel keeps its point nearest to the voxel centroid
+/// instead of the centroid itself, so that attributes are preserved.
+#[derive(Debug)]
+pub struct VoxelGridSampling {
+    pub num_points: usize,
+    /// Number of bisection steps of the leaf size.
+    pub max_iterations: usize,
+}
+
+impl Default for VoxelGridSampling {
+    fn default() -> Self {
+        Self {
+            num_points: 0,
+            max_iterations: 32,
+        }
+    }
+}
+
+impl VoxelGridSampling {
+    pub fn new(num_points: usize) -> Self {
+        Self {
+            num_points,
+            ..Default::default()
+        }
+    }
+
+    pub fn filter<PC: PointCloudBase>(&self, pc: &PC) -> PC {
+        match self.leaf_size(pc) {
+            Some(leaf_size) => pc.select(&representatives(pc, leaf_size)),
+            None => PC::new(),
+        }
+    }
+
+    /// Leaf size whose number of occupied voxels is the closest to `num_points`, `None` if
+    /// there are no finite points or `num_points` is zero.
+    pub fn leaf_size<PC: PointCloudBase>(&self, pc: &PC) -> Option<f32> {
+        let positions: Vec<_> = finite_indices(pc)
+            .into_iter()
+            .map(|i| pc.positions()[i])
+            .collect();
+        if positions.is_empty() || self.num_points == 0 {
+            return None;
+        }
+
+        let (min, max) = positions.iter().fold(
+            (positions[0].coords, positions[0].coords),
+            |(min, max), p| (min.inf(&p.coords), max.sup(&p.coords)),
+        );
+        let extent = (max - min).max();
+        if extent == 0. {
+            return Some(1.);
+        }
+
+        // Bisect in log space: a leaf of twice the extent gives at most 8 voxels, and one
+        // many times smaller than the mean spacing gives one voxel per point.
+        let count = |leaf_size: f32| voxels(&positions, leaf_size).len();
+        let (mut lo, mut hi) = ((extent * 1e-6).ln(), (extent * 2.).ln());
+        let mut best = (hi.exp(), count(hi.exp()));
+        for _ in 0..self.max_iterations {
+            let mid = (lo + hi) / 2.;
+            let n = count(mid.exp());
+            if n.abs_diff(self.num_points) < best.1.abs_diff(self.num_points) {
+                best = (mid.exp(), n);
+            }
+            match n.cmp(&self.num_points) {
+                std::cmp::Ordering::Greater => lo = mid,
+                std::cmp::Ordering::Less => hi = mid,
+                std::cmp::Ordering::Equal => break,
+            }
+        }
+
+        Some(best.0)
+    }
+}
+
+type Voxel = (i64, i64, i64);
+
+fn voxel(p: &Position, leaf_size: f32) -> Voxel {
+    let ijk = p.coords.map(|v| (v / leaf_size).floor() as i64);
+    (ijk.x, ijk.y, ijk.z)
+}
+
+/// Sum and count of the positions of each voxel.
+fn voxels(positions: &[Position], leaf_size: f32) -> HashMap<Voxel, (Position, usize)> {
+    let mut voxels = HashMap::new();
+    for p in positions {
+        let (sum, count) = voxels
+            .entry(voxel(p, leaf_size))
+            .or_insert((Position::origin(), 0));
+        sum.coords += p.coords;
+        *count += 1;
+    }
+    voxels
+}
+
+/// Index of the point nearest to the centroid of each voxel, ascending.
+fn representatives<PC: PointCloudBase>(pc: &PC, leaf_size: f32) -> Vec<usize> {
+    let candidates = finite_indices(pc);
+    let positions: Vec<_> = candidates.iter().map(|&i| pc.positions()[i]).collect();
+    let centroids = voxels(&positions, leaf_size);
+
+    let mut nearest: HashMap<Voxel, (usize, f32)> = HashMap::new();
+    for (&i, p) in candidates.iter().zip(&positions) {
+        let key = voxel(p, leaf_size);
+        let (sum, count) = centroids[&key];
+        let d = (p.coords - sum.coords / count as f32).norm_squared();
+        let entry = nearest.entry(key).or_insert((i, d));
+        if d < entry.1 {
+            *entry = (i, d);
+        }
+    }
+
+    let mut indices: Vec<_> = nearest.into_values().map(|(i, _)| i).collect();
+    indices.sort_unstable();
+    indices
+}
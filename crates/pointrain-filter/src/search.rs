@@ -0,0 +1,82 @@
+//! Nearest neighbor search over the positions of a point cloud.
+//!
+//! Points with a non-finite position are not indexed. Results are sorted by ascending
+//! distance, then index.
+
+mod brute_force;
+mod kdtree;
+mod voxel_hash;
+
+use pointrain_core::types::{Float, Position};
+
+pub use brute_force::BruteForceSearch;
+pub use kdtree::KdTreeSearch;
+pub use voxel_hash::VoxelHashSearch;
+
+/// A point found by a search.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Neighbor {
+    /// Index into the searched cloud.
+    pub index: usize,
+    pub distance_squared: Float,
+}
+
+pub trait NeighborSearch: Sync {
+    /// Number of indexed points.
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The `k` nearest points, fewer if not that many are indexed.
+    fn knn(&self, query: &Position, k: usize) -> Vec<Neighbor>;
+    /// The points at most `radius` away.
+    fn radius(&self, query: &Position, radius: Float) -> Vec<Neighbor>;
+
+    fn nearest(&self, query: &Position) -> Option<Neighbor> {
+        self.knn(query, 1).pop()
+    }
+
+    /// [`Self::knn`] for each query, in parallel with the `rayon` feature.
+    fn knn_batch(&self, queries: &[Position], k: usize) -> Vec<Vec<Neighbor>> {
+        batch(queries, |q| self.knn(q, k))
+    }
+
+    /// [`Self::radius`] for each query, in parallel with the `rayon` feature.
+    fn radius_batch(&self, queries: &[Position], radius: Float) -> Vec<Vec<Neighbor>> {
+        batch(queries, |q| self.radius(q, radius))
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn batch<F>(queries: &[Position], f: F) -> Vec<Vec<Neighbor>>
+where
+    F: Fn(&Position) -> Vec<Neighbor> + Send + Sync,
+{
+    use rayon::prelude::*;
+
+    queries.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "rayon"))]
+fn batch<F>(queries: &[Position], f: F) -> Vec<Vec<Neighbor>>
+where
+    F: Fn(&Position) -> Vec<Neighbor> + Send + Sync,
+{
+    queries.iter().map(f).collect()
+}
+
+pub(crate) fn sort(neighbors: &mut [Neighbor]) {
+    neighbors.sort_unstable_by(|a, b| {
+        a.distance_squared
+            .total_cmp(&b.distance_squared)
+            .then(a.index.cmp(&b.index))
+    });
+}
+
+/// Sorts the candidates and keeps the `k` nearest.
+pub(crate) fn nearest_k(mut candidates: Vec<Neighbor>, k: usize) -> Vec<Neighbor> {
+    sort(&mut candidates);
+    candidates.truncate(k);
+    candidates
+}
@@ -0,0 +1,53 @@
+use pointrain_core::{
+    pc::PointCloudBase,
+    types::{Float, Position},
+};
+
+use super::{Neighbor, NeighborSearch};
+use crate::utility;
+
+/// Exhaustive search, for small clouds and as a reference.
+#[derive(Debug, Clone)]
+pub struct BruteForceSearch {
+    points: Vec<(usize, Position)>,
+}
+
+impl BruteForceSearch {
+    pub fn new<PC: PointCloudBase>(pc: &PC) -> Self {
+        let points = pc
+            .positions()
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, p)| utility::is_finite(p))
+            .collect();
+        Self { points }
+    }
+
+    fn all(&self, query: &Position) -> impl Iterator<Item = Neighbor> + '_ {
+        let query = *query;
+        self.points.iter().map(move |&(index, p)| Neighbor {
+            index,
+            distance_squared: (p - query).norm_squared(),
+        })
+    }
+}
+
+impl NeighborSearch for BruteForceSearch {
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    fn knn(&self, query: &Position, k: usize) -> Vec<Neighbor> {
+        super::nearest_k(self.all(query).collect(), k)
+    }
+
+    fn radius(&self, query: &Position, radius: Float) -> Vec<Neighbor> {
+        let mut neighbors: Vec<_> = self
+            .all(query)
+            .filter(|n| n.distance_squared <= radius * radius)
+            .collect();
+        super::sort(&mut neighbors);
+        neighbors
+    }
+}
@@ -0,0 +1,65 @@
+use kiddo::{KdTree, NearestNeighbour, SquaredEuclidean};
+use pointrain_core::{
+    pc::PointCloudBase,
+    types::{Float, Position},
+};
+
+use super::{Neighbor, NeighborSearch};
+
+/// Search with a [`kiddo`] kd-tree, the best default for most clouds.
+#[derive(Debug, Clone)]
+pub struct KdTreeSearch {
+    tree: KdTree<Float, 3>,
+    len: usize,
+}
+
+impl KdTreeSearch {
+    pub fn new<PC: PointCloudBase>(pc: &PC) -> Self {
+        let mut tree = KdTree::with_capacity(pc.len());
+        let mut len = 0;
+        for (i, p) in pc.positions().iter().enumerate() {
+            if crate::utility::is_finite(p) {
+                tree.add(&[p.x, p.y, p.z], i as u64);
+                len += 1;
+            }
+        }
+        Self { tree, len }
+    }
+}
+
+fn neighbor(n: NearestNeighbour<Float, u64>) -> Neighbor {
+    Neighbor {
+        index: n.item as usize,
+        distance_squared: n.distance,
+    }
+}
+
+impl NeighborSearch for KdTreeSearch {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn knn(&self, query: &Position, k: usize) -> Vec<Neighbor> {
+        if k == 0 || self.len == 0 {
+            return Vec::new();
+        }
+        let neighbors = self
+            .tree
+            .nearest_n::<SquaredEuclidean>(&[query.x, query.y, query.z], k)
+            .into_iter()
+            .map(neighbor)
+            .collect();
+        super::nearest_k(neighbors, k)
+    }
+
+    fn radius(&self, query: &Position, radius: Float) -> Vec<Neighbor> {
+        let mut neighbors: Vec<_> = self
+            .tree
+            .within_unsorted::<SquaredEuclidean>(&[query.x, query.y, query.z], radius * radius)
+            .into_iter()
+            .map(neighbor)
+            .collect();
+        super::sort(&mut neighbors);
+        neighbors
+    }
+}
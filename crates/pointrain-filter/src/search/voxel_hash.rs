@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use pointrain_core::{
+    pc::PointCloudBase,
+    types::{Float, Position},
+};
+
+use super::{Neighbor, NeighborSearch};
+use crate::utility;
+
+type Voxel = [i64; 3];
+
+/// Search over a hash of cubic voxels, fast for queries with a radius close to the voxel
+/// size and for roughly uniform density.
+#[derive(Debug, Clone)]
+pub struct VoxelHashSearch {
+    voxel_size: Float,
+    voxels: HashMap<Voxel, Vec<(usize, Position)>>,
+    /// Bounds of the occupied voxels.
+    min: Voxel,
+    max: Voxel,
+    len: usize,
+}
+
+impl VoxelHashSearch {
+    /// # Panics
+    ///
+    /// Panics unless `voxel_size` is positive.
+    pub fn new<PC: PointCloudBase>(pc: &PC, voxel_size: Float) -> Self {
+        assert!(voxel_size > 0., "voxel_size must be positive");
+
+        let mut search = Self {
+            voxel_size,
+            voxels: HashMap::new(),
+            min: [i64::MAX; 3],
+            max: [i64::MIN; 3],
+            len: 0,
+        };
+        for (i, p) in pc.positions().iter().enumerate() {
+            if !utility::is_finite(p) {
+                continue;
+            }
+            let voxel = search.voxel(p);
+            for (a, &v) in voxel.iter().enumerate() {
+                search.min[a] = search.min[a].min(v);
+                search.max[a] = search.max[a].max(v);
+            }
+            search.voxels.entry(voxel).or_default().push((i, *p));
+            search.len += 1;
+        }
+        search
+    }
+
+    pub fn voxel_size(&self) -> Float {
+        self.voxel_size
+    }
+
+    fn voxel(&self, p: &Position) -> Voxel {
+        p.coords
+            .map(|v| (v / self.voxel_size).floor() as i64)
+            .into()
+    }
+
+    /// Points in the voxels at Chebyshev distance `ring` from `center`.
+    fn ring(&self, center: Voxel, ring: i64, mut f: impl FnMut(&[(usize, Position)])) {
+        let [x, y, z] = center;
+        for i in x - ring..=x + ring {
+            for j in y - ring..=y + ring {
+                let on_shell = (i - x).abs() == ring || (j - y).abs() == ring;
+                // Only the two caps of the column are on the shell, unless the column is.
+                let step = if on_shell || ring == 0 { 1 } else { 2 * ring };
+                for k in (z - ring..=z + ring).step_by(step as usize) {
+                    if let Some(points) = self.voxels.get(&[i, j, k]) {
+                        f(points);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Largest ring around `center` that still has occupied voxels.
+    fn max_ring(&self, center: Voxel) -> i64 {
+        (0..3)
+            .map(|a| (center[a] - self.min[a]).max(self.max[a] - center[a]))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Number of voxels within `ring` of a voxel.
+fn cube(ring: i64) -> f64 {
+    (2. * ring as f64 + 1.).powi(3)
+}
+
+impl NeighborSearch for VoxelHashSearch {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn knn(&self, query: &Position, k: usize) -> Vec<Neighbor> {
+        if k == 0 || self.len == 0 || !utility::is_finite(query) {
+            return Vec::new();
+        }
+
+        let center = self.voxel(query);
+        let mut candidates = Vec::new();
+        let visit = |points: &[(usize, Position)], candidates: &mut Vec<_>| {
+            candidates.extend(points.iter().map(|&(index, p)| Neighbor {
+                index,
+                distance_squared: (p - query).norm_squared(),
+            }));
+        };
+        for ring in 0..=self.max_ring(center) {
+            if cube(ring) > self.voxels.len() as f64 {
+                // Far from the cloud, rings are mostly empty.
+                candidates.clear();
+                for points in self.voxels.values() {
+                    visit(points, &mut candidates);
+                }
+                break;
+            }
+            self.ring(center, ring, |points| visit(points, &mut candidates));
+
+            // Every point within `ring` voxels of the query is found by now.
+            if candidates.len() >= k {
+                candidates = super::nearest_k(candidates, k);
+                let reach = ring as Float * self.voxel_size;
+                if candidates[k - 1].distance_squared <= reach * reach {
+                    break;
+                }
+            }
+        }
+
+        super::nearest_k(candidates, k)
+    }
+
+    fn radius(&self, query: &Position, radius: Float) -> Vec<Neighbor> {
+        if self.len == 0 || !utility::is_finite(query) || radius.is_nan() || radius < 0. {
+            return Vec::new();
+        }
+
+        let center = self.voxel(query);
+        let rings = ((radius / self.voxel_size).ceil() as i64).min(self.max_ring(center));
+        let mut neighbors = Vec::new();
+        let mut visit = |points: &[(usize, Position)]| {
+            for &(index, p) in points {
+                let distance_squared = (p - query).norm_squared();
+                if distance_squared <= radius * radius {
+                    neighbors.push(Neighbor {
+                        index,
+                        distance_squared,
+                    });
+                }
+            }
+        };
+
+        // Scanning every voxel is cheaper than the cube of rings around a large radius.
+        if cube(rings) > self.voxels.len() as f64 {
+            self.voxels.values().for_each(|points| visit(points));
+        } else {
+            for ring in 0..=rings {
+                self.ring(center, ring, &mut visit);
+            }
+        }
+
+        super::sort(&mut neighbors);
+        neighbors
+    }
+}
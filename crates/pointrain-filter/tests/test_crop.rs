@@ -0,0 +1,133 @@
+use std::f32::consts::FRAC_PI_4;
+
+use pointrain_core::{
+    nalgebra::{Isometry3, Vector3},
+    pc::{PointCloud, PointCloudIntensity, PointCloudWithIntensity},
+    point::{Point, PointIntensity},
+    types::{Normal, Position},
+};
+use pointrain_filter::{Axis, CropBox, IntensityPassThrough, PassThrough, PolygonPrism};
+
+fn test_pc() -> PointCloudIntensity {
+    [
+        (0., 0., 0., 0.1),
+        (1., 0., 0., 0.2),
+        (0., 2., 0., 0.3),
+        (0., 0., 3., 0.4),
+        (-1., -1., -1., 0.5),
+        (f32::NAN, 0., 0., 0.6),
+    ]
+    .into_iter()
+    .map(|(x, y, z, intensity)| PointIntensity {
+        position: Position::new(x, y, z),
+        intensity,
+    })
+    .collect()
+}
+
+#[test]
+fn test_crop_box() {
+    let pc = test_pc();
+    let mut crop = CropBox::new(
+        Position::new(-0.5, -0.5, -0.5),
+        Position::new(1.5, 2.5, 0.5),
+    );
+
+    assert_eq!(crop.mask(&pc), [true, true, true, false, false, false]);
+    let filtered = crop.filter(&pc);
+    assert_eq!(filtered.intensities(), [0.1, 0.2, 0.3]);
+
+    crop.negative = true;
+    assert_eq!(crop.mask(&pc), [false, false, false, true, true, false]);
+}
+
+#[test]
+fn test_crop_box_oriented() {
+    let pc = test_pc();
+    // A thin box along the diagonal of the xy plane, through (1, 1, 0).
+    let crop = CropBox {
+        pose: Isometry3::new(Vector3::new(1., 1., 0.), Vector3::z() * FRAC_PI_4),
+        ..CropBox::new(Position::new(-2., -0.1, -0.1), Position::new(2., 0.1, 0.1))
+    };
+
+    assert!(crop.contains(&Position::new(0., 0., 0.)));
+    assert!(crop.contains(&Position::new(2., 2., 0.)));
+    assert!(!crop.contains(&Position::new(1., 0., 0.)));
+    assert_eq!(crop.filter(&pc).intensities(), [0.1]);
+}
+
+#[test]
+fn test_pass_through() {
+    let pc = test_pc();
+
+    let mut pass = PassThrough::new(Axis::Z, -0.5, 1.);
+    assert_eq!(pass.filter(&pc).intensities(), [0.1, 0.2, 0.3]);
+    pass.negative = true;
+    assert_eq!(pass.filter(&pc).intensities(), [0.4, 0.5]);
+
+    let pass = PassThrough::new(Axis::Y, 1., 2.);
+    assert_eq!(pass.mask(&pc), [false, false, true, false, false, false]);
+}
+
+#[test]
+fn test_intensity_pass_through() {
+    let pc = test_pc();
+
+    let mut pass = IntensityPassThrough::new(0.15, 0.35);
+    assert_eq!(pass.filter(&pc).intensities(), [0.2, 0.3]);
+    pass.negative = true;
+    assert_eq!(pass.mask(&pc), [true, false, false, true, true, false]);
+}
+
+#[test]
+fn test_polygon_prism() {
+    // An L shape at z = 1, in clockwise order seen from above so that heights go down.
+    let polygon = [
+        Position::new(0., 0., 1.),
+        Position::new(0., 2., 1.),
+        Position::new(1., 2., 1.),
+        Position::new(1., 1., 1.),
+        Position::new(2., 1., 1.),
+        Position::new(2., 0., 1.),
+    ];
+    let mut prism = PolygonPrism::new(&polygon, 0., 2.);
+
+    assert_eq!(*prism.normal(), -Normal::z());
+    assert!(prism.contains(&Position::new(0.5, 1.5, 0.5)));
+    assert!(prism.contains(&Position::new(1.5, 0.5, -1.)));
+    assert!(!prism.contains(&Position::new(1.5, 1.5, 0.5)));
+    assert!(!prism.contains(&Position::new(0.5, 0.5, 1.5)));
+    assert!(!prism.contains(&Position::new(0.5, 0.5, -1.5)));
+
+    let pc: PointCloud = [(0.5, 1.5), (1.5, 1.5)]
+        .into_iter()
+        .map(|(x, y)| Point {
+            position: Position::new(x, y, 0.5),
+        })
+        .collect();
+    assert_eq!(prism.mask(&pc), [true, false]);
+    prism.negative = true;
+    assert_eq!(prism.mask(&pc), [false, true]);
+}
+
+#[test]
+fn test_convex_hull_crop() {
+    let points: Vec<_> = (0..100)
+        .map(|i| {
+            let (x, y) = ((i % 10) as f32 / 9., (i / 10) as f32 / 9.);
+            Position::new(x, y, 0.01 * (x - y))
+        })
+        .collect();
+    let prism = PolygonPrism::from_convex_hull(&points, Normal::z(), -0.5, 0.5);
+
+    let mut hull = prism.polygon();
+    hull.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+    assert_eq!(hull.len(), 4);
+    assert_eq!((hull[0].x, hull[0].y), (0., 0.));
+    assert_eq!((hull[3].x, hull[3].y), (1., 1.));
+
+    assert!(prism.contains(&Position::new(0.5, 0.5, 0.2)));
+    assert!(!prism.contains(&Position::new(1.5, 0.5, 0.)));
+    assert!(!prism.contains(&Position::new(0.5, 0.5, 1.)));
+    assert_eq!(prism.filter(&test_pc()).intensities(), [0.1]);
+}
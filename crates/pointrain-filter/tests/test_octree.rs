@@ -0,0 +1,191 @@
+use pointrain_core::{
+    nalgebra::Vector3,
+    pc::{PointCloud, PointCloudBase},
+    point::Point,
+    types::Position,
+};
+use pointrain_filter::{BruteForceSearch, NeighborSearch, Octree};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn pc(positions: &[[f32; 3]]) -> PointCloud {
+    positions
+        .iter()
+        .map(|&[x, y, z]| Point {
+            position: Position::new(x, y, z),
+        })
+        .collect()
+}
+
+fn random_pc(n: usize, seed: u64) -> PointCloud {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n)
+        .map(|_| Point {
+            position: Position::new(rng.gen(), rng.gen(), rng.gen()) * 4. - Vector3::repeat(2.),
+        })
+        .collect()
+}
+
+#[test]
+fn test_insertion() {
+    let pc = pc(&[
+        [0.1, 0.1, 0.1],
+        [0.2, 0.3, 0.4],
+        [-0.1, 0.1, 0.1],
+        [f32::NAN, 0., 0.],
+        [2.5, -3.5, 0.9],
+    ]);
+    let octree = Octree::from_pc(&pc, 0.5);
+
+    assert_eq!(octree.len(), 4);
+    assert_eq!(octree.positions().len(), 5);
+    assert!(octree.is_occupied(&Position::new(0.4, 0.4, 0.4)));
+    assert!(!octree.is_occupied(&Position::new(0.6, 0.4, 0.4)));
+
+    let leaves: Vec<_> = octree.leaves().collect();
+    assert_eq!(leaves.len(), 3);
+    for leaf in &leaves {
+        assert_eq!(leaf.depth(), octree.depth());
+        assert_eq!(leaf.size(), 0.5);
+    }
+
+    let mut centers: Vec<_> = octree.voxel_centers().collect();
+    centers.sort_by(|a, b| a.x.total_cmp(&b.x));
+    assert_eq!(
+        centers,
+        [
+            Position::new(-0.25, 0.25, 0.25),
+            Position::new(0.25, 0.25, 0.25),
+            Position::new(2.75, -3.25, 0.75),
+        ]
+    );
+}
+
+#[test]
+fn test_far_apart_points() {
+    let pc = pc(&[
+        [1e18, 0., 0.],
+        [-1e18, 0., 0.],
+        [1e16, 0., 0.],
+        [-1e16, 1e16, -1e16],
+    ]);
+    let octree = Octree::from_pc(&pc, 0.01);
+
+    // Keys beyond 2^60 leaves are skipped, the others fit into the integer bounds.
+    assert_eq!(octree.len(), 2);
+    assert_eq!(octree.positions().len(), 4);
+    assert!(!octree.is_occupied(&Position::new(1e18, 0., 0.)));
+    assert!(octree.is_occupied(&Position::new(-1e16, 1e16, -1e16)));
+    assert!(octree.depth() <= 62);
+    assert_eq!(octree.leaves().count(), 2);
+}
+
+#[test]
+fn test_depth_first() {
+    let pc = random_pc(500, 0);
+    let octree = Octree::from_pc(&pc, 0.3);
+
+    let nodes: Vec<_> = octree.depth_first().collect();
+    let root = &nodes[0];
+    assert_eq!(root.depth(), 0);
+    let mut indices = root.indices();
+    indices.sort();
+    assert_eq!(indices, (0..500).collect::<Vec<_>>());
+
+    // Every child lies in its parent, which comes right before its first child.
+    for (parent, child) in nodes.iter().zip(&nodes[1..]) {
+        if child.depth() == parent.depth() + 1 {
+            assert_eq!(parent.size(), 2. * child.size());
+            for a in 0..3 {
+                assert!(parent.min()[a] <= child.min()[a] && child.max()[a] <= parent.max()[a]);
+            }
+        }
+    }
+
+    for leaf in octree.leaves() {
+        for &i in leaf.leaf_indices() {
+            let p = pc.positions()[i];
+            for a in 0..3 {
+                // Up to rounding of the voxel bounds.
+                assert!(leaf.min()[a] - 1e-6 <= p[a] && p[a] <= leaf.max()[a] + 1e-6);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_box_search() {
+    let pc = random_pc(1000, 1);
+    let octree = Octree::from_pc(&pc, 0.25);
+    let (min, max) = (Position::new(-0.5, 0., -1.), Position::new(0.7, 1.2, 0.3));
+
+    let expected: Vec<_> = (0..pc.len())
+        .filter(|&i| {
+            let p = pc.positions()[i];
+            (0..3).all(|a| min[a] <= p[a] && p[a] <= max[a])
+        })
+        .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(octree.box_search(&min, &max), expected);
+}
+
+#[test]
+fn test_neighbor_search() {
+    let pc = random_pc(1000, 2);
+    let octree = Octree::from_pc(&pc, 0.2);
+    let reference = BruteForceSearch::new(&pc);
+
+    let indices = |v: Vec<_>| {
+        v.into_iter()
+            .map(|n: pointrain_filter::Neighbor| n.index)
+            .collect::<Vec<_>>()
+    };
+    for q in random_pc(20, 3).positions() {
+        let q = q * 1.5;
+        assert_eq!(indices(octree.knn(&q, 10)), indices(reference.knn(&q, 10)));
+        assert_eq!(
+            indices(octree.radius(&q, 0.5)),
+            indices(reference.radius(&q, 0.5))
+        );
+    }
+}
+
+#[test]
+fn test_ray_search() {
+    let pc = pc(&[
+        [0.5, 0.5, 0.5],
+        [2.5, 0.5, 0.5],
+        [1.5, 0.5, 0.5],
+        [1.5, 1.5, 0.5],
+    ]);
+    let octree = Octree::from_pc(&pc, 1.);
+
+    let hits = octree.ray_search(&Position::new(-1., 0.5, 0.5), &Vector3::x());
+    let hit_indices: Vec<_> = hits.iter().map(|n| n.leaf_indices().to_vec()).collect();
+    assert_eq!(hit_indices, [[0], [2], [1]]);
+
+    let hits = octree.ray_search(&Position::new(1.5, 3., 0.5), &-Vector3::y());
+    let hit_indices: Vec<_> = hits.iter().map(|n| n.leaf_indices().to_vec()).collect();
+    assert_eq!(hit_indices, [[3], [2]]);
+
+    assert!(octree
+        .ray_search(&Position::new(-1., 0.5, 0.5), &-Vector3::x())
+        .is_empty());
+}
+
+#[test]
+fn test_changes() {
+    let before = pc(&[[0.1, 0.1, 0.1], [1.1, 0.1, 0.1], [1.2, 0.2, 0.2]]);
+    let after = pc(&[
+        [0.2, 0.2, 0.2],
+        [1.3, 0.3, 0.3],
+        [5.1, 0.1, 0.1],
+        [5.2, 0.1, 0.1],
+    ]);
+    let before = Octree::from_pc(&before, 1.);
+    let after = Octree::from_pc(&after, 1.);
+
+    assert_eq!(after.changes(&before, 1), [2, 3]);
+    assert!(before.changes(&after, 1).is_empty());
+    // Voxels with fewer than two points before also count as new.
+    assert_eq!(after.changes(&before, 2), [0, 2, 3]);
+}
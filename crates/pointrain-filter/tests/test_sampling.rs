@@ -0,0 +1,117 @@
+use pointrain_core::{
+    pc::{PointCloudBase, PointCloudIntensity, PointCloudWithIntensity},
+    point::PointIntensity,
+    types::Position,
+};
+use pointrain_filter::{FarthestPointSampling, RandomSampling, VoxelGridSampling};
+
+/// A 10 x 10 x 10 grid with the intensity of each point being its index.
+fn grid_pc() -> PointCloudIntensity {
+    (0..1000)
+        .map(|i| PointIntensity {
+            position: Position::new((i % 10) as f32, (i / 10 % 10) as f32, (i / 100) as f32),
+            intensity: i as f32,
+        })
+        .collect()
+}
+
+fn assert_attributes_kept(pc: &PointCloudIntensity, sampled: &PointCloudIntensity) {
+    for (p, &i) in sampled.positions().iter().zip(sampled.intensities()) {
+        assert_eq!(*p, pc.positions()[i as usize]);
+    }
+}
+
+#[test]
+fn test_farthest_point_sampling() {
+    let pc = grid_pc();
+    let sampled = FarthestPointSampling::new(8).filter(&pc);
+
+    assert_eq!(sampled.len(), 8);
+    assert_attributes_kept(&pc, &sampled);
+    assert_eq!(sampled.positions()[0], Position::new(0., 0., 0.));
+    assert_eq!(sampled.positions()[1], Position::new(9., 9., 9.));
+    // Picks are spread out over the grid.
+    for (i, p) in sampled.positions().iter().enumerate() {
+        for q in &sampled.positions()[..i] {
+            assert!((p - q).norm() > 6., "{p} {q}");
+        }
+    }
+}
+
+#[test]
+fn test_farthest_point_sampling_small() {
+    let mut pc = grid_pc();
+    pc.positions_mut()[0].x = f32::NAN;
+    let pc: PointCloudIntensity = pc.iter().take(5).collect();
+
+    let sampler = FarthestPointSampling {
+        num_samples: 10,
+        start_index: 0,
+    };
+    let mut indices = sampler.indices(&pc);
+    assert_eq!(indices[0], 1);
+    indices.sort();
+    assert_eq!(indices, [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_farthest_point_sampling_duplicates() {
+    let pc: PointCloudIntensity = (0..4)
+        .map(|i| PointIntensity {
+            position: Position::new(1., 2., 3.),
+            intensity: i as f32,
+        })
+        .collect();
+
+    let mut indices = FarthestPointSampling::new(3).indices(&pc);
+    indices.sort();
+    assert_eq!(indices, [0, 1, 2]);
+}
+
+#[test]
+fn test_random_sampling() {
+    let pc = grid_pc();
+    let sampled = RandomSampling::new(100, 42).filter(&pc);
+
+    assert_eq!(sampled.len(), 100);
+    assert_attributes_kept(&pc, &sampled);
+    assert!(sampled.intensities().windows(2).all(|w| w[0] < w[1]));
+
+    assert_eq!(
+        RandomSampling::new(100, 42).indices(&pc),
+        RandomSampling::new(100, 42).indices(&pc)
+    );
+    assert_ne!(
+        RandomSampling::new(100, 42).indices(&pc),
+        RandomSampling::new(100, 43).indices(&pc)
+    );
+    assert_eq!(RandomSampling::new(2000, 0).filter(&pc).len(), 1000);
+}
+
+#[test]
+fn test_voxel_grid_sampling() {
+    let pc = grid_pc();
+
+    for target in [1, 8, 27, 125, 1000] {
+        let sampler = VoxelGridSampling::new(target);
+        let sampled = sampler.filter(&pc);
+        assert_eq!(
+            sampled.len(),
+            target,
+            "leaf size {:?}",
+            sampler.leaf_size(&pc)
+        );
+        assert_attributes_kept(&pc, &sampled);
+    }
+
+    let sampled = VoxelGridSampling::new(300).filter(&pc);
+    assert!((200..=400).contains(&sampled.len()), "{}", sampled.len());
+
+    assert_eq!(VoxelGridSampling::new(0).filter(&pc).len(), 0);
+    assert_eq!(
+        VoxelGridSampling::new(10)
+            .filter(&PointCloudIntensity::new())
+            .len(),
+        0
+    );
+}
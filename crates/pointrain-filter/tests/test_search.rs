@@ -0,0 +1,101 @@
+use pointrain_core::{
+    pc::{PointCloud, PointCloudBase},
+    point::Point,
+    types::Position,
+};
+use pointrain_filter::{BruteForceSearch, KdTreeSearch, Neighbor, NeighborSearch, VoxelHashSearch};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn random_pc(n: usize, seed: u64) -> PointCloud {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n)
+        .map(|_| Point {
+            position: Position::new(rng.gen(), rng.gen(), rng.gen()),
+        })
+        .collect()
+}
+
+fn backends(pc: &PointCloud) -> Vec<(&'static str, Box<dyn NeighborSearch>)> {
+    vec![
+        ("brute force", Box::new(BruteForceSearch::new(pc))),
+        ("kd-tree", Box::new(KdTreeSearch::new(pc))),
+        ("voxel hash", Box::new(VoxelHashSearch::new(pc, 0.1))),
+        ("coarse voxel hash", Box::new(VoxelHashSearch::new(pc, 2.))),
+    ]
+}
+
+#[test]
+fn test_small() {
+    let mut pc: PointCloud = [0., 1., 3., f32::NAN]
+        .into_iter()
+        .map(|x| Point {
+            position: Position::new(x, 0., 0.),
+        })
+        .collect();
+    pc.positions_mut()[3].y = 0.;
+
+    for (name, search) in backends(&pc) {
+        assert_eq!(search.len(), 3, "{name}");
+
+        let query = Position::new(0.9, 0., 0.);
+        assert_eq!(search.nearest(&query).map(|n| n.index), Some(1), "{name}");
+
+        let knn = search.knn(&query, 5);
+        assert_eq!(
+            knn.iter().map(|n| n.index).collect::<Vec<_>>(),
+            [1, 0, 2],
+            "{name}"
+        );
+        assert!((knn[1].distance_squared - 0.81).abs() < 1e-6, "{name}");
+
+        let radius = search.radius(&query, 1.);
+        assert_eq!(
+            radius.iter().map(|n| n.index).collect::<Vec<_>>(),
+            [1, 0],
+            "{name}"
+        );
+        assert!(search.knn(&query, 0).is_empty(), "{name}");
+    }
+}
+
+#[test]
+fn test_backends_agree() {
+    let pc = random_pc(2000, 0);
+    let queries: Vec<_> = random_pc(50, 1)
+        .positions()
+        .iter()
+        // Also queries away from the cloud.
+        .map(|p| p * 1.5 - pointrain_core::nalgebra::Vector3::repeat(0.25))
+        .collect();
+
+    let reference = BruteForceSearch::new(&pc);
+    let knn = reference.knn_batch(&queries, 8);
+    let radius = reference.radius_batch(&queries, 0.15);
+    assert!(radius.iter().any(|r| r.len() > 5));
+
+    let indices = |v: &[Neighbor]| v.iter().map(|n| n.index).collect::<Vec<_>>();
+    for (name, search) in backends(&pc) {
+        for (i, (found, expected)) in search.knn_batch(&queries, 8).iter().zip(&knn).enumerate() {
+            assert_eq!(indices(found), indices(expected), "{name} knn {i}");
+        }
+        for (i, (found, expected)) in search
+            .radius_batch(&queries, 0.15)
+            .iter()
+            .zip(&radius)
+            .enumerate()
+        {
+            assert_eq!(indices(found), indices(expected), "{name} radius {i}");
+        }
+    }
+}
+
+#[test]
+fn test_empty() {
+    let pc = PointCloud::new();
+
+    for (name, search) in backends(&pc) {
+        assert!(search.is_empty(), "{name}");
+        assert_eq!(search.nearest(&Position::origin()), None, "{name}");
+        assert!(search.radius(&Position::origin(), 1.).is_empty(), "{name}");
+    }
+}
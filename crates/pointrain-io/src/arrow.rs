@@ -0,0 +1,160 @@
+//! Conversion between point clouds and Arrow record batches, and Parquet files of them.
+//!
+//! Every canonical column (see [`crate::point`]) is one Arrow column: color channels as
+//! `UInt8`, everything else as `Float32`. When reading, column names are canonicalized and
+//! columns of non-numeric types are ignored.
+
+use std::{fs::File, path::Path, sync::Arc};
+
+use arrow_array::{
+    cast::AsArray, types::*, Array, ArrayRef, Float32Array, RecordBatch, UInt8Array,
+};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter};
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    point::{canonical_column_name, PointReadable, PointWritable},
+};
+
+fn is_color_channel(column: &str) -> bool {
+    matches!(column, "r" | "g" | "b" | "a")
+}
+
+pub fn to_record_batch<PC>(pc: &PC) -> Result<RecordBatch, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = PC::Point::columns();
+    let mut values = vec![Vec::with_capacity(pc.len()); columns.len()];
+    let mut data = Vec::new();
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        for (values, datum) in values.iter_mut().zip(&data) {
+            values.push(*datum);
+        }
+    }
+
+    let mut fields = Vec::with_capacity(columns.len());
+    let mut arrays = Vec::<ArrayRef>::with_capacity(columns.len());
+    for (name, values) in columns.iter().zip(values) {
+        if is_color_channel(name) {
+            let values = values
+                .into_iter()
+                .map(PointFieldDatum::to_color_channel)
+                .collect::<Result<Vec<_>, _>>()?;
+            fields.push(Field::new(*name, DataType::UInt8, false));
+            arrays.push(Arc::new(UInt8Array::from(values)));
+        } else {
+            let values = values.into_iter().map(PointFieldDatum::to_float);
+            fields.push(Field::new(*name, DataType::Float32, false));
+            arrays.push(Arc::new(Float32Array::from_iter_values(values)));
+        }
+    }
+
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+}
+
+pub fn from_record_batch<PC>(batch: &RecordBatch) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let mut pc = PC::with_capacity(batch.num_rows());
+    extend_from_record_batch(&mut pc, batch)?;
+    Ok(pc)
+}
+
+fn extend_from_record_batch<PC>(pc: &mut PC, batch: &RecordBatch) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let schema = batch.schema();
+    let (fields, arrays): (Vec<_>, Vec<_>) = schema
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .filter(|(_, array)| is_supported(array.data_type()))
+        .map(|(field, array)| {
+            let field = PointField {
+                name: canonical_column_name(field.name()),
+                datatype: PointFieldType::F64,
+                count: 1,
+            };
+            (field, array.as_ref())
+        })
+        .unzip();
+    let func = PC::Point::read_data_func(&fields)?;
+
+    let mut data = Vec::with_capacity(fields.len());
+    for i in 0..batch.num_rows() {
+        data.clear();
+        data.extend(arrays.iter().map(|array| datum(*array, i)));
+        pc.push(func(&data)?);
+    }
+
+    Ok(())
+}
+
+fn is_supported(datatype: &DataType) -> bool {
+    use DataType::*;
+
+    matches!(
+        datatype,
+        UInt8 | UInt16 | UInt32 | Int8 | Int16 | Int32 | Float32 | Float64
+    )
+}
+
+/// Reads a value of a column of a supported type; nulls are NaN.
+fn datum(array: &dyn Array, i: usize) -> PointFieldDatum {
+    if array.is_null(i) {
+        return PointFieldDatum::F32(f32::NAN);
+    }
+
+    match array.data_type() {
+        DataType::UInt8 => PointFieldDatum::U8(array.as_primitive::<UInt8Type>().value(i)),
+        DataType::UInt16 => PointFieldDatum::U16(array.as_primitive::<UInt16Type>().value(i)),
+        DataType::UInt32 => PointFieldDatum::U32(array.as_primitive::<UInt32Type>().value(i)),
+        DataType::Int8 => PointFieldDatum::I8(array.as_primitive::<Int8Type>().value(i)),
+        DataType::Int16 => PointFieldDatum::I16(array.as_primitive::<Int16Type>().value(i)),
+        DataType::Int32 => PointFieldDatum::I32(array.as_primitive::<Int32Type>().value(i)),
+        DataType::Float32 => PointFieldDatum::F32(array.as_primitive::<Float32Type>().value(i)),
+        DataType::Float64 => PointFieldDatum::F64(array.as_primitive::<Float64Type>().value(i)),
+        t => unreachable!("unsupported data type {t}"),
+    }
+}
+
+pub fn parquet_write<PC>(f: impl AsRef<Path>, pc: &PC) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let batch = to_record_batch(pc)?;
+
+    let mut writer = ArrowWriter::try_new(File::create(f)?, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+
+    Ok(())
+}
+
+pub fn parquet_read<PC>(f: impl AsRef<Path>) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(f)?)?;
+    let mut pc = PC::with_capacity(builder.metadata().file_metadata().num_rows() as usize);
+
+    for batch in builder.build()? {
+        extend_from_record_batch(&mut pc, &batch?)?;
+    }
+
+    Ok(pc)
+}
@@ -0,0 +1,163 @@
+//! Async readers and writers over tokio's [`AsyncRead`] and [`AsyncWrite`].
+//!
+//! Readers load the whole stream and parse it with the same code as the file readers, so
+//! they accept exactly the same input. Parsing is blocking work and runs on tokio's blocking
+//! thread pool, which needs a running tokio runtime. Writers encode into memory on the
+//! calling task, as the point cloud is borrowed, and write it at once.
+
+use pointrain_core::pc::PointCloudBase;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{
+    error::PointRainIOError,
+    pcd::{self, read::pcd_read_from, write::pcd_write_to},
+    ply::{self, read::ply_read_from, write::ply_write_to},
+    point::{PointReadable, PointWritable},
+    xyz::{read::xyz_read_from, write::xyz_write_to},
+    ReadOptions, ReadReport, XyzFormat,
+};
+
+async fn read_all(mut reader: impl AsyncRead + Unpin) -> Result<Vec<u8>, PointRainIOError> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).await?;
+    Ok(data)
+}
+
+/// Parses `data` on the blocking thread pool so that large inputs do not stall the worker
+/// threads of the runtime.
+async fn parse_blocking<T>(
+    data: Vec<u8>,
+    parse: impl FnOnce(&[u8]) -> Result<T, PointRainIOError> + Send + 'static,
+) -> Result<T, PointRainIOError>
+where
+    T: Send + 'static,
+{
+    match tokio::task::spawn_blocking(move || parse(&data)).await {
+        Ok(result) => result,
+        Err(e) => match e.try_into_panic() {
+            Ok(panic) => std::panic::resume_unwind(panic),
+            Err(e) => Err(e.to_string().into()),
+        },
+    }
+}
+
+async fn write_all(
+    mut writer: impl AsyncWrite + Unpin,
+    data: &[u8],
+) -> Result<(), PointRainIOError> {
+    writer.write_all(data).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Async version of [`crate::pcd_read`].
+pub async fn pcd_read_async<PC>(reader: impl AsyncRead + Unpin) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase + Send + 'static,
+    PC::Point: pcd::point::PointReadable,
+{
+    pcd_read_async_with_options(reader, &ReadOptions::default())
+        .await
+        .map(|(pc, _)| pc)
+}
+
+/// Async version of [`crate::pcd_read_with_options`].
+pub async fn pcd_read_async_with_options<PC>(
+    reader: impl AsyncRead + Unpin,
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
+where
+    PC: PointCloudBase + Send + 'static,
+    PC::Point: pcd::point::PointReadable,
+{
+    let options = *options;
+    let data = read_all(reader).await?;
+    parse_blocking(data, move |data| pcd_read_from(data, &options)).await
+}
+
+/// Async writer of binary PCD streams.
+///
+/// Colors are packed into a `rgb` (or `rgba`) field and normals are named as in PCL.
+pub async fn pcd_write_async<PC>(
+    writer: impl AsyncWrite + Unpin,
+    pc: &PC,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let mut data = Vec::new();
+    pcd_write_to(&mut data, pc)?;
+    write_all(writer, &data).await
+}
+
+/// Async version of [`crate::ply_read`].
+pub async fn ply_read_async<PC>(reader: impl AsyncRead + Unpin) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase + Send + 'static,
+    PC::Point: ply::point::PointReadable,
+{
+    ply_read_async_with_options(reader, &ReadOptions::default())
+        .await
+        .map(|(pc, _)| pc)
+}
+
+/// Async version of [`crate::ply_read_with_options`].
+pub async fn ply_read_async_with_options<PC>(
+    reader: impl AsyncRead + Unpin,
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
+where
+    PC: PointCloudBase + Send + 'static,
+    PC::Point: ply::point::PointReadable,
+{
+    let options = *options;
+    let data = read_all(reader).await?;
+    parse_blocking(data, move |data| ply_read_from(data, &options)).await
+}
+
+/// Async writer of binary little-endian PLY streams with a single `vertex` element.
+///
+/// Colors are stored as `uchar` properties, everything else as `float`.
+pub async fn ply_write_async<PC>(
+    writer: impl AsyncWrite + Unpin,
+    pc: &PC,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let mut data = Vec::new();
+    ply_write_to(&mut data, pc)?;
+    write_all(writer, &data).await
+}
+
+/// Async version of [`crate::xyz_read_with_format`].
+pub async fn xyz_read_async_with_format<PC>(
+    reader: impl AsyncRead + Unpin,
+    format: &XyzFormat,
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
+where
+    PC: PointCloudBase + Send + 'static,
+    PC::Point: PointReadable,
+{
+    let (format, options) = (format.clone(), *options);
+    let data = read_all(reader).await?;
+    parse_blocking(data, move |data| xyz_read_from(data, &format, &options)).await
+}
+
+/// Async version of [`crate::xyz_write_with_format`].
+pub async fn xyz_write_async_with_format<PC>(
+    writer: impl AsyncWrite + Unpin,
+    pc: &PC,
+    format: &XyzFormat,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let mut data = Vec::new();
+    xyz_write_to(&mut data, pc, format)?;
+    write_all(writer, &data).await
+}
@@ -0,0 +1,357 @@
+//! Native octree compression of point clouds.
+//!
+//! Positions are snapped to the centers of a voxel grid of the given resolution and stored
+//! as the occupancy masks of an octree over it, breadth first, followed by the number of
+//! points in each occupied voxel. Attributes are kept losslessly, delta coded from point to
+//! point in octree order. Everything after the header is range coded with adaptive models.
+
+mod range_coder;
+
+use pointrain_core::pc::PointCloudBase;
+
+use self::range_coder::{ByteModel, Decoder, Encoder, Prob};
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    mesh::WritableColumns,
+    point::{PointReadable, PointWritable},
+};
+
+const MAGIC: &[u8] = b"PROC";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 3 + 8 + 8 + 3 * 8;
+
+const INTENSITY: u8 = 1;
+const RGB: u8 = 1 << 1;
+const ALPHA: u8 = 1 << 2;
+const NORMAL: u8 = 1 << 3;
+const CURVATURE: u8 = 1 << 4;
+
+/// Voxel coordinates have at most this many bits, so that node keys fit in a `u128`.
+const MAX_DEPTH: u32 = 31;
+
+/// Attribute columns in coding order, with the flag they belong to.
+const ATTRIBUTES: &[(u8, &[&str])] = &[
+    (RGB, &["r", "g", "b"]),
+    (ALPHA, &["a"]),
+    (INTENSITY, &["intensity"]),
+    (NORMAL, &["nx", "ny", "nz"]),
+    (CURVATURE, &["curvature"]),
+];
+
+fn is_color(flag: u8) -> bool {
+    flag == RGB || flag == ALPHA
+}
+
+/// Adaptive models of the octree and of every attribute channel.
+struct Models {
+    masks: ByteModel,
+    counts: [Prob; 32],
+    colors: Vec<ByteModel>,
+    /// One model per byte of each float channel, most significant first.
+    floats: Vec<[ByteModel; 4]>,
+}
+
+impl Models {
+    fn new(flags: u8) -> Self {
+        let channels = |color| {
+            ATTRIBUTES
+                .iter()
+                .filter(|(flag, _)| flags & flag != 0 && is_color(*flag) == color)
+                .map(|(_, columns)| columns.len())
+                .sum()
+        };
+        Self {
+            masks: ByteModel::default(),
+            counts: [Prob::default(); 32],
+            colors: vec![ByteModel::default(); channels(true)],
+            floats: vec![Default::default(); channels(false)],
+        }
+    }
+}
+
+/// Compresses a point cloud with voxels of `resolution`.
+///
+/// Decoded positions are within `resolution / 2` of the original ones on each axis, and
+/// points come back ordered by voxel. Points with a non-finite position are skipped.
+pub fn encode<PC>(pc: &PC, resolution: f64) -> Result<Vec<u8>, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    if !(resolution.is_finite() && resolution > 0.) {
+        return Err(format!("Invalid resolution: {resolution}").into());
+    }
+
+    let columns = WritableColumns::new(PC::Point::columns())?;
+    let attribute_columns = [
+        (RGB, columns.rgb.map(Vec::from)),
+        (ALPHA, columns.alpha.map(|a| vec![a])),
+        (INTENSITY, columns.intensity.map(|i| vec![i])),
+        (NORMAL, columns.normal.map(Vec::from)),
+        (CURVATURE, columns.curvature.map(|c| vec![c])),
+    ];
+    let flags = attribute_columns
+        .iter()
+        .filter(|(_, c)| c.is_some())
+        .fold(0, |flags, (flag, _)| flags | flag);
+
+    let mut positions = Vec::with_capacity(pc.len());
+    let mut colors = Vec::new();
+    let mut floats = Vec::new();
+    let mut data = Vec::new();
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        let position = columns.xyz.map(|i| f64::from(data[i].to_float()));
+        if !position.iter().all(|v| v.is_finite()) {
+            continue;
+        }
+        positions.push(position);
+        for (flag, indices) in &attribute_columns {
+            for &i in indices.iter().flatten() {
+                if is_color(*flag) {
+                    colors.push(data[i].to_color_channel()?);
+                } else {
+                    floats.push(data[i].to_float().to_bits());
+                }
+            }
+        }
+    }
+
+    let origin = positions.iter().fold([f64::INFINITY; 3], |min, p| {
+        std::array::from_fn(|i| min[i].min(p[i]))
+    });
+    let origin = if positions.is_empty() {
+        [0.; 3]
+    } else {
+        origin
+    };
+
+    let max_cell = ((1u64 << MAX_DEPTH) - 1) as f64;
+    let mut keys = Vec::with_capacity(positions.len());
+    let mut depth = 0;
+    for (i, p) in positions.iter().enumerate() {
+        let cell = std::array::from_fn::<_, 3, _>(|a| ((p[a] - origin[a]) / resolution).floor());
+        if cell.iter().any(|&c| c > max_cell) {
+            return Err("Resolution is too fine for the extent of the point cloud"
+                .to_string()
+                .into());
+        }
+        let cell = cell.map(|c| c as u32);
+        depth = depth.max(cell.iter().map(|c| 32 - c.leading_zeros()).max().unwrap());
+        keys.push((cell, i));
+    }
+    let mut keys: Vec<_> = keys
+        .into_iter()
+        .map(|(cell, i)| (morton_key(cell, depth), i))
+        .collect();
+    keys.sort_unstable();
+
+    // Occupied voxels and the number of points in each.
+    let mut leaves: Vec<(u128, u32)> = Vec::new();
+    for &(key, _) in &keys {
+        match leaves.last_mut() {
+            Some((last, count)) if *last == key => *count += 1,
+            _ => leaves.push((key, 1)),
+        }
+    }
+
+    let mut out = Vec::with_capacity(HEADER_LEN + positions.len());
+    out.extend(MAGIC);
+    out.extend([VERSION, flags, depth as u8]);
+    out.extend((positions.len() as u64).to_le_bytes());
+    out.extend(resolution.to_le_bytes());
+    for v in origin {
+        out.extend(v.to_le_bytes());
+    }
+
+    let mut models = Models::new(flags);
+    let mut encoder = Encoder::new(out);
+
+    for level in 0..depth {
+        let parent_shift = 3 * (depth - level);
+        let mut i = 0;
+        while i < leaves.len() {
+            let parent = leaves[i].0 >> parent_shift;
+            let mut mask = 0u8;
+            while i < leaves.len() && leaves[i].0 >> parent_shift == parent {
+                mask |= 1 << ((leaves[i].0 >> (parent_shift - 3)) & 7);
+                i += 1;
+            }
+            encoder.byte(&mut models.masks, mask);
+        }
+    }
+    for &(_, count) in &leaves {
+        encoder.number(&mut models.counts, count - 1);
+    }
+
+    let num_colors = models.colors.len();
+    let num_floats = models.floats.len();
+    let mut previous_color = vec![0u8; num_colors];
+    let mut previous_float = vec![0u32; num_floats];
+    for &(_, i) in &keys {
+        let point_colors = &colors[num_colors * i..num_colors * (i + 1)];
+        for ((model, previous), &v) in models
+            .colors
+            .iter_mut()
+            .zip(&mut previous_color)
+            .zip(point_colors)
+        {
+            encoder.byte(model, v.wrapping_sub(*previous));
+            *previous = v;
+        }
+        let point_floats = &floats[num_floats * i..num_floats * (i + 1)];
+        for ((models, previous), &v) in models
+            .floats
+            .iter_mut()
+            .zip(&mut previous_float)
+            .zip(point_floats)
+        {
+            let delta = (v ^ *previous).to_be_bytes();
+            for (model, byte) in models.iter_mut().zip(delta) {
+                encoder.byte(model, byte);
+            }
+            *previous = v;
+        }
+    }
+
+    Ok(encoder.finish())
+}
+
+/// Decompresses a point cloud written by [`encode`].
+pub fn decode<PC>(data: &[u8]) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    if data.len() < HEADER_LEN || &data[..MAGIC.len()] != MAGIC {
+        return Err("Not an octree compressed point cloud".to_string().into());
+    }
+    let header = &data[MAGIC.len()..HEADER_LEN];
+    let [version, flags, depth] = [header[0], header[1], header[2]];
+    if version != VERSION {
+        return Err(format!("Unsupported compression version: {version}").into());
+    }
+    let depth = u32::from(depth);
+    if depth > MAX_DEPTH {
+        return Err(format!("Invalid octree depth: {depth}").into());
+    }
+    let f64_at = |at: usize| f64::from_le_bytes(header[at..at + 8].try_into().unwrap());
+    let num_points = u64::from_le_bytes(header[3..11].try_into().unwrap());
+    let resolution = f64_at(11);
+    let origin = [f64_at(19), f64_at(27), f64_at(35)];
+
+    let field = |name: &str, datatype| PointField {
+        name: name.into(),
+        datatype,
+        count: 1,
+    };
+    let mut fields: Vec<_> = ["x", "y", "z"]
+        .into_iter()
+        .map(|name| field(name, PointFieldType::F64))
+        .collect();
+    // Colors come first in the datums, then the floats, both in coding order.
+    for color in [true, false] {
+        for (flag, columns) in ATTRIBUTES {
+            if flags & flag != 0 && is_color(*flag) == color {
+                let datatype = if color {
+                    PointFieldType::U8
+                } else {
+                    PointFieldType::F32
+                };
+                fields.extend(columns.iter().map(|name| field(name, datatype)));
+            }
+        }
+    }
+    let func = PC::Point::read_data_func(&fields)?;
+
+    let mut decoder = Decoder::new(&data[HEADER_LEN..])?;
+    let mut models = Models::new(flags);
+
+    let mut nodes = if num_points > 0 { vec![0u128] } else { vec![] };
+    for _ in 0..depth {
+        let mut children = Vec::with_capacity(nodes.len() * 2);
+        for node in nodes {
+            let mask = decoder.byte(&mut models.masks)?;
+            if mask == 0 {
+                return Err("Empty octree node".to_string().into());
+            }
+            children.extend(
+                (0..8)
+                    .filter(|c| mask & (1 << c) != 0)
+                    .map(|c| (node << 3) | c),
+            );
+        }
+        if children.len() as u64 > num_points {
+            return Err("More octree nodes than points".to_string().into());
+        }
+        nodes = children;
+    }
+
+    let mut counts = Vec::with_capacity(nodes.len());
+    let mut total = 0;
+    for _ in &nodes {
+        let count = u64::from(decoder.number(&mut models.counts)?) + 1;
+        total += count;
+        if total > num_points {
+            return Err("More points than declared".to_string().into());
+        }
+        counts.push(count);
+    }
+    if total != num_points {
+        return Err("Fewer points than declared".to_string().into());
+    }
+
+    // Points in a shared voxel without attributes take almost no payload, so there is no
+    // exact bound: reserve at most one point per payload byte and grow past that.
+    let capacity = num_points.min(data.len() as u64) as usize;
+    let mut pc = PC::with_capacity(capacity);
+    let mut previous_color = vec![0u8; models.colors.len()];
+    let mut previous_float = vec![0u32; models.floats.len()];
+    let mut datums = Vec::with_capacity(fields.len());
+    for (&node, count) in nodes.iter().zip(counts) {
+        let cell = morton_cell(node, depth);
+        let position =
+            std::array::from_fn::<_, 3, _>(|a| origin[a] + (f64::from(cell[a]) + 0.5) * resolution);
+        for _ in 0..count {
+            datums.clear();
+            datums.extend(position.map(PointFieldDatum::F64));
+            for (model, previous) in models.colors.iter_mut().zip(&mut previous_color) {
+                *previous = previous.wrapping_add(decoder.byte(model)?);
+                datums.push(PointFieldDatum::U8(*previous));
+            }
+            for (models, previous) in models.floats.iter_mut().zip(&mut previous_float) {
+                let mut delta = [0; 4];
+                for (model, byte) in models.iter_mut().zip(&mut delta) {
+                    *byte = decoder.byte(model)?;
+                }
+                *previous ^= u32::from_be_bytes(delta);
+                datums.push(PointFieldDatum::F32(f32::from_bits(*previous)));
+            }
+            pc.push(func(&datums)?);
+        }
+    }
+
+    Ok(pc)
+}
+
+/// Interleaves the bits of a voxel, x first, so that each level takes 3 bits.
+fn morton_key(cell: [u32; 3], depth: u32) -> u128 {
+    (0..depth).rev().fold(0, |key, bit| {
+        cell.iter()
+            .fold(key, |key, c| (key << 1) | u128::from((c >> bit) & 1))
+    })
+}
+
+fn morton_cell(key: u128, depth: u32) -> [u32; 3] {
+    let mut cell = [0; 3];
+    for level in 0..depth {
+        let bits = key >> (3 * (depth - level - 1));
+        for (a, c) in cell.iter_mut().enumerate() {
+            *c = (*c << 1) | ((bits >> (2 - a)) & 1) as u32;
+        }
+    }
+    cell
+}
@@ -0,0 +1,224 @@
+//! Adaptive binary range coder, as in LZMA.
+
+use crate::error::PointRainIOError;
+
+const PROB_BITS: u32 = 11;
+const PROB_INIT: u16 = 1 << (PROB_BITS - 1);
+const MOVE_BITS: u32 = 5;
+const TOP: u32 = 1 << 24;
+
+/// Probability of a zero bit, adapted after every coded bit.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Prob(u16);
+
+impl Default for Prob {
+    fn default() -> Self {
+        Self(PROB_INIT)
+    }
+}
+
+impl Prob {
+    fn bound(self, range: u32) -> u32 {
+        (range >> PROB_BITS) * u32::from(self.0)
+    }
+
+    fn update(&mut self, bit: bool) {
+        if bit {
+            self.0 -= self.0 >> MOVE_BITS;
+        } else {
+            self.0 += ((1 << PROB_BITS) - self.0) >> MOVE_BITS;
+        }
+    }
+}
+
+/// Probabilities of the bits of a byte, each conditioned on the bits above it.
+#[derive(Debug, Clone)]
+pub(super) struct ByteModel([Prob; 256]);
+
+impl Default for ByteModel {
+    fn default() -> Self {
+        Self([Prob::default(); 256])
+    }
+}
+
+pub(super) struct Encoder {
+    out: Vec<u8>,
+    low: u64,
+    range: u32,
+    cache: u8,
+    cache_size: u64,
+}
+
+impl Encoder {
+    pub(super) fn new(out: Vec<u8>) -> Self {
+        Self {
+            out,
+            low: 0,
+            range: u32::MAX,
+            cache: 0,
+            cache_size: 1,
+        }
+    }
+
+    pub(super) fn bit(&mut self, prob: &mut Prob, bit: bool) {
+        let bound = prob.bound(self.range);
+        if bit {
+            self.low += u64::from(bound);
+            self.range -= bound;
+        } else {
+            self.range = bound;
+        }
+        prob.update(bit);
+        self.normalize();
+    }
+
+    /// Codes the lowest `n` bits of `v`, most significant first, without a model.
+    pub(super) fn direct_bits(&mut self, v: u32, n: u32) {
+        for i in (0..n).rev() {
+            self.range >>= 1;
+            if (v >> i) & 1 == 1 {
+                self.low += u64::from(self.range);
+            }
+            self.normalize();
+        }
+    }
+
+    pub(super) fn byte(&mut self, model: &mut ByteModel, v: u8) {
+        let mut node = 1;
+        for i in (0..8).rev() {
+            let bit = (v >> i) & 1 == 1;
+            self.bit(&mut model.0[node], bit);
+            node = (node << 1) | usize::from(bit);
+        }
+    }
+
+    /// Exp-Golomb code of `v`, with an adaptive prefix.
+    pub(super) fn number(&mut self, prefix: &mut [Prob; 32], v: u32) {
+        let v = u64::from(v) + 1;
+        let n = 63 - v.leading_zeros();
+        for p in &mut prefix[..n as usize] {
+            self.bit(p, true);
+        }
+        if n < 32 {
+            self.bit(&mut prefix[n as usize], false);
+        }
+        self.direct_bits(v as u32, n);
+    }
+
+    fn normalize(&mut self) {
+        while self.range < TOP {
+            self.range <<= 8;
+            self.shift_low();
+        }
+    }
+
+    fn shift_low(&mut self) {
+        if self.low < 0xFF00_0000 || self.low > u64::from(u32::MAX) {
+            let carry = (self.low >> 32) as u8;
+            let mut byte = self.cache;
+            loop {
+                self.out.push(byte.wrapping_add(carry));
+                byte = 0xFF;
+                self.cache_size -= 1;
+                if self.cache_size == 0 {
+                    break;
+                }
+            }
+            self.cache = (self.low >> 24) as u8;
+        }
+        self.cache_size += 1;
+        self.low = (self.low & 0x00FF_FFFF) << 8;
+    }
+
+    pub(super) fn finish(mut self) -> Vec<u8> {
+        for _ in 0..5 {
+            self.shift_low();
+        }
+        self.out
+    }
+}
+
+pub(super) struct Decoder<'a> {
+    data: &'a [u8],
+    code: u32,
+    range: u32,
+}
+
+impl<'a> Decoder<'a> {
+    pub(super) fn new(data: &'a [u8]) -> Result<Self, PointRainIOError> {
+        let mut decoder = Self {
+            data,
+            code: 0,
+            range: u32::MAX,
+        };
+        if decoder.next_byte()? != 0 {
+            return Err("Invalid range coder stream".to_string().into());
+        }
+        for _ in 0..4 {
+            decoder.code = (decoder.code << 8) | u32::from(decoder.next_byte()?);
+        }
+        Ok(decoder)
+    }
+
+    fn next_byte(&mut self) -> Result<u8, PointRainIOError> {
+        let (&byte, rest) = self
+            .data
+            .split_first()
+            .ok_or_else(|| "Unexpected end of compressed data".to_string())?;
+        self.data = rest;
+        Ok(byte)
+    }
+
+    fn normalize(&mut self) -> Result<(), PointRainIOError> {
+        while self.range < TOP {
+            self.range <<= 8;
+            self.code = (self.code << 8) | u32::from(self.next_byte()?);
+        }
+        Ok(())
+    }
+
+    pub(super) fn bit(&mut self, prob: &mut Prob) -> Result<bool, PointRainIOError> {
+        let bound = prob.bound(self.range);
+        let bit = self.code >= bound;
+        if bit {
+            self.code -= bound;
+            self.range -= bound;
+        } else {
+            self.range = bound;
+        }
+        prob.update(bit);
+        self.normalize()?;
+        Ok(bit)
+    }
+
+    pub(super) fn direct_bits(&mut self, n: u32) -> Result<u32, PointRainIOError> {
+        let mut v = 0;
+        for _ in 0..n {
+            self.range >>= 1;
+            let bit = self.code >= self.range;
+            if bit {
+                self.code -= self.range;
+            }
+            v = (v << 1) | u32::from(bit);
+            self.normalize()?;
+        }
+        Ok(v)
+    }
+
+    pub(super) fn byte(&mut self, model: &mut ByteModel) -> Result<u8, PointRainIOError> {
+        let mut node = 1;
+        while node < 256 {
+            node = (node << 1) | usize::from(self.bit(&mut model.0[node])?);
+        }
+        Ok(node as u8)
+    }
+
+    pub(super) fn number(&mut self, prefix: &mut [Prob; 32]) -> Result<u32, PointRainIOError> {
+        let mut n = 0;
+        while n < 32 && self.bit(&mut prefix[n as usize])? {
+            n += 1;
+        }
+        let v = (1u64 << n) | u64::from(self.direct_bits(n)?);
+        u32::try_from(v - 1).map_err(|_| "Invalid compressed number".to_string().into())
+    }
+}
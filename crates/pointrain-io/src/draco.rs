@@ -0,0 +1,90 @@
+//! Google Draco (`.drc`) point cloud compression, bitstream version 2.2.
+//!
+//! Point clouds are encoded sequentially with quantized positions, `uint8` colors and
+//! octahedral normals, all delta coded and rANS compressed. The decoder also reads the
+//! kd-tree encoding that `draco_encoder` uses by default; meshes are not supported.
+
+mod ans;
+mod buffer;
+mod kd_tree;
+mod octahedron;
+mod read;
+mod write;
+
+pub use read::{draco_decode, draco_read};
+pub use write::{draco_encode, draco_write};
+
+const MAGIC: &[u8] = b"DRACO";
+const VERSION: (u8, u8) = (2, 2);
+const METADATA_FLAG: u16 = 0x8000;
+
+const POINT_CLOUD: u8 = 0;
+const SEQUENTIAL_ENCODING: u8 = 0;
+const KD_TREE_ENCODING: u8 = 1;
+
+const POSITION: u8 = 0;
+const NORMAL: u8 = 1;
+const COLOR: u8 = 2;
+
+const DT_UINT8: u8 = 2;
+const DT_FLOAT32: u8 = 9;
+
+const DECODER_GENERIC: u8 = 0;
+const DECODER_INTEGER: u8 = 1;
+const DECODER_QUANTIZATION: u8 = 2;
+const DECODER_NORMALS: u8 = 3;
+
+const PREDICTION_NONE: i8 = -2;
+const PREDICTION_DIFFERENCE: i8 = 0;
+const TRANSFORM_WRAP: i8 = 1;
+const TRANSFORM_OCTAHEDRON_CANONICALIZED: i8 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DracoOptions {
+    /// Bits per quantized position component, `0` stores positions as `float32`.
+    pub position_quantization_bits: u8,
+    /// Bits per octahedral normal component.
+    pub normal_quantization_bits: u8,
+}
+
+impl Default for DracoOptions {
+    fn default() -> Self {
+        Self {
+            position_quantization_bits: 11,
+            normal_quantization_bits: 8,
+        }
+    }
+}
+
+/// Size in bytes of a Draco data type, `None` for unknown types.
+fn data_type_size(data_type: u8) -> Option<usize> {
+    match data_type {
+        1 | 2 | 11 => Some(1),
+        3 | 4 => Some(2),
+        5 | 6 | 9 => Some(4),
+        7 | 8 | 10 => Some(8),
+        _ => None,
+    }
+}
+
+/// Maps a signed value to an unsigned symbol: `0, -1, 1, -2, ...` to `0, 1, 2, 3, ...`.
+fn to_symbol(v: i32) -> u32 {
+    if v >= 0 {
+        (v as u32) << 1
+    } else {
+        ((-(i64::from(v) + 1)) as u32) << 1 | 1
+    }
+}
+
+fn from_symbol(s: u32) -> i32 {
+    let v = (s >> 1) as i32;
+    if s & 1 == 0 {
+        v
+    } else {
+        -v - 1
+    }
+}
+
+fn most_significant_bit(v: u32) -> u32 {
+    31 - v.leading_zeros()
+}
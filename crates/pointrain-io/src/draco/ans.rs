@@ -0,0 +1,478 @@
+//! Asymmetric numeral system coders: rANS over symbol alphabets and rABS over bits.
+
+use super::{
+    buffer::{put_varint, Reader},
+    most_significant_bit,
+};
+use crate::error::PointRainIOError;
+
+const IO_BASE: u32 = 256;
+
+const TAGGED_SYMBOLS: u8 = 0;
+const RAW_SYMBOLS: u8 = 1;
+const MAX_RAW_BIT_LENGTH: u32 = 18;
+const TAG_BIT_LENGTH: u32 = 5;
+
+/// Lower bound of the state of the bit coder.
+const BIT_L_BASE: u32 = 4096;
+/// Bit probabilities are given in 1/256.
+const BIT_PRECISION: u32 = 256;
+
+/// Precision bits of the rANS coder of an alphabet with `bit_length` bits.
+fn precision_bits(bit_length: u32) -> u32 {
+    (3 * bit_length / 2).clamp(12, 20)
+}
+
+/// Reads the final state the encoder stored at the end of `data`, returning the offset of
+/// the remaining bytes. The two top bits give the number of bytes used.
+fn read_state(data: &[u8], max_bytes: usize) -> Result<(usize, u32), PointRainIOError> {
+    let err = || "Invalid rANS data".to_string();
+
+    let last = *data.last().ok_or_else(err)?;
+    let len = usize::from(last >> 6) + 1;
+    if len > max_bytes || len > data.len() {
+        return Err(err().into());
+    }
+    let offset = data.len() - len;
+    let state = data[offset..]
+        .iter()
+        .rev()
+        .fold(0, |state, b| state << 8 | u32::from(*b));
+
+    Ok((offset, state & ((1 << (8 * len - 2)) - 1)))
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Symbol {
+    prob: u32,
+    cum_prob: u32,
+}
+
+struct SymbolDecoder<'a> {
+    data: &'a [u8],
+    offset: usize,
+    state: u32,
+    precision_bits: u32,
+    symbols: Vec<Symbol>,
+    /// Symbol of every value of `state % precision`.
+    lut: Vec<u32>,
+}
+
+impl<'a> SymbolDecoder<'a> {
+    fn new(reader: &mut Reader<'a>, bit_length: u32) -> Result<Self, PointRainIOError> {
+        let precision_bits = precision_bits(bit_length);
+        let precision = 1 << precision_bits;
+
+        let num_symbols = reader.varint_u32()? as usize;
+        if num_symbols > reader.remaining().len() * 64 {
+            return Err(format!("Invalid number of rANS symbols: {num_symbols}").into());
+        }
+        let mut probs = vec![0; num_symbols];
+        let mut i = 0;
+        while i < num_symbols {
+            let byte = reader.u8()?;
+            // The low two bits are the number of extra bytes, or 3 for a run of zeros.
+            let token = byte & 3;
+            if token == 3 {
+                i += usize::from(byte >> 2) + 1;
+                if i > num_symbols {
+                    return Err("Invalid rANS probability table".to_string().into());
+                }
+                continue;
+            }
+            let mut prob = u32::from(byte >> 2);
+            for b in 0..token {
+                prob |= u32::from(reader.u8()?) << (8 * (b + 1) - 2);
+            }
+            probs[i] = prob;
+            i += 1;
+        }
+
+        let mut symbols = Vec::with_capacity(num_symbols);
+        let mut lut = Vec::new();
+        if num_symbols > 0 {
+            lut.reserve(precision as usize);
+            for (i, &prob) in probs.iter().enumerate() {
+                symbols.push(Symbol {
+                    prob,
+                    cum_prob: lut.len() as u32,
+                });
+                if lut.len() + prob as usize > precision as usize {
+                    return Err("Invalid rANS probability table".to_string().into());
+                }
+                lut.resize(lut.len() + prob as usize, i as u32);
+            }
+            if lut.len() != precision as usize {
+                return Err("Invalid rANS probability table".to_string().into());
+            }
+        }
+
+        let len = reader.varint()? as usize;
+        let data = reader.bytes(len)?;
+        let (offset, state) = read_state(data, 4)?;
+        let state = state + 4 * precision;
+        if state >= 4 * precision * IO_BASE {
+            return Err("Invalid rANS data".to_string().into());
+        }
+
+        Ok(Self {
+            data,
+            offset,
+            state,
+            precision_bits,
+            symbols,
+            lut,
+        })
+    }
+
+    fn decode(&mut self) -> Result<u32, PointRainIOError> {
+        if self.symbols.is_empty() {
+            return Err("Empty rANS alphabet".to_string().into());
+        }
+
+        let l_base = 4 << self.precision_bits;
+        while self.state < l_base && self.offset > 0 {
+            self.offset -= 1;
+            self.state = self.state * IO_BASE + u32::from(self.data[self.offset]);
+        }
+        let quo = self.state >> self.precision_bits;
+        let rem = self.state & ((1 << self.precision_bits) - 1);
+        let symbol = self.lut[rem as usize];
+        let Symbol { prob, cum_prob } = self.symbols[symbol as usize];
+        self.state = quo * prob + rem - cum_prob;
+
+        Ok(symbol)
+    }
+}
+
+/// Decodes `num_values` symbols written by [`encode_symbols`] or the tagged scheme, which
+/// stores the bit length of every `num_components` values followed by the raw bits.
+pub(super) fn decode_symbols(
+    reader: &mut Reader,
+    num_values: usize,
+    num_components: usize,
+) -> Result<Vec<u32>, PointRainIOError> {
+    if num_values == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut values = Vec::with_capacity(num_values);
+    match reader.u8()? {
+        RAW_SYMBOLS => {
+            let bit_length = u32::from(reader.u8()?);
+            if !(1..=MAX_RAW_BIT_LENGTH).contains(&bit_length) {
+                return Err(format!("Invalid symbol bit length: {bit_length}").into());
+            }
+            let mut decoder = SymbolDecoder::new(reader, bit_length)?;
+            for _ in 0..num_values {
+                values.push(decoder.decode()?);
+            }
+        }
+        TAGGED_SYMBOLS => {
+            let mut tags = SymbolDecoder::new(reader, TAG_BIT_LENGTH)?;
+            let mut bits = LsbBitReader::new(reader.remaining());
+            let num_components = num_components.max(1);
+            while values.len() < num_values {
+                let bit_length = tags.decode()?;
+                if bit_length > 32 {
+                    return Err(format!("Invalid symbol bit length: {bit_length}").into());
+                }
+                for _ in 0..num_components.min(num_values - values.len()) {
+                    values.push(bits.read(bit_length));
+                }
+            }
+            reader.advance(bits.bytes_read())?;
+        }
+        scheme => return Err(format!("Unknown symbol coding: {scheme}").into()),
+    }
+
+    Ok(values)
+}
+
+/// Bits packed least significant first.
+struct LsbBitReader<'a> {
+    data: &'a [u8],
+    bit: usize,
+}
+
+impl<'a> LsbBitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, bit: 0 }
+    }
+
+    fn read(&mut self, nbits: u32) -> u32 {
+        let mut value = 0;
+        for i in 0..nbits {
+            let bit = self
+                .data
+                .get(self.bit / 8)
+                .map_or(0, |b| (b >> (self.bit % 8)) & 1);
+            value |= u32::from(bit) << i;
+            self.bit += 1;
+        }
+        value
+    }
+
+    fn bytes_read(&self) -> usize {
+        self.bit.div_ceil(8)
+    }
+}
+
+/// Writes `symbols` with the raw scheme, returning `false` without writing anything if
+/// there are too many distinct symbols for it.
+pub(super) fn encode_symbols(out: &mut Vec<u8>, symbols: &[u32]) -> bool {
+    let Some(&max) = symbols.iter().max() else {
+        return true;
+    };
+
+    let mut frequencies = vec![0u64; max as usize + 1];
+    for &s in symbols {
+        frequencies[s as usize] += 1;
+    }
+    let num_unique = frequencies.iter().filter(|f| **f > 0).count() as u32;
+    let bit_length = most_significant_bit(num_unique) + 1;
+    if bit_length > MAX_RAW_BIT_LENGTH {
+        return false;
+    }
+    let precision_bits = precision_bits(bit_length);
+    let precision = 1u32 << precision_bits;
+
+    let probs = normalize(&frequencies, precision);
+    let mut table = Vec::with_capacity(probs.len());
+    let mut cum_prob = 0;
+    for &prob in &probs {
+        table.push(Symbol { prob, cum_prob });
+        cum_prob += prob;
+    }
+
+    out.push(RAW_SYMBOLS);
+    out.push(bit_length as u8);
+    put_varint(out, probs.len() as u64);
+    let mut i = 0;
+    while i < probs.len() {
+        let prob = probs[i];
+        if prob == 0 {
+            // Runs of up to 64 zero probabilities take one byte.
+            let run = probs[i + 1..]
+                .iter()
+                .take(63)
+                .take_while(|p| **p == 0)
+                .count();
+            out.push((run as u8) << 2 | 3);
+            i += run + 1;
+            continue;
+        }
+        let extra_bytes = match prob {
+            0..=0x3F => 0,
+            0x40..=0x3FFF => 1,
+            _ => 2,
+        };
+        out.push((prob << 2) as u8 | extra_bytes);
+        for b in 0..extra_bytes {
+            out.push((prob >> (8 * (b + 1) - 2)) as u8);
+        }
+        i += 1;
+    }
+
+    // The decoder reads the symbols back to front.
+    let l_base = 4 * precision;
+    let mut state = l_base;
+    let mut data = Vec::new();
+    for &s in symbols.iter().rev() {
+        let Symbol { prob, cum_prob } = table[s as usize];
+        while state >= 4 * IO_BASE * prob {
+            data.push((state % IO_BASE) as u8);
+            state /= IO_BASE;
+        }
+        state = (state / prob) * precision + state % prob + cum_prob;
+    }
+    write_state(&mut data, state - l_base);
+
+    put_varint(out, data.len() as u64);
+    out.extend(data);
+    true
+}
+
+/// Scales `frequencies` to probabilities summing up to `precision`, keeping every used
+/// symbol at a probability of at least one.
+fn normalize(frequencies: &[u64], precision: u32) -> Vec<u32> {
+    let total = frequencies.iter().sum::<u64>() as f64;
+    let mut probs: Vec<u32> = frequencies
+        .iter()
+        .map(|&f| match f {
+            0 => 0,
+            f => ((f as f64 / total * f64::from(precision) + 0.5) as u32).max(1),
+        })
+        .collect();
+
+    let mut order: Vec<_> = (0..probs.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(probs[i]));
+
+    let mut sum: i64 = probs.iter().map(|p| i64::from(*p)).sum();
+    let precision = i64::from(precision);
+    if sum < precision {
+        probs[order[0]] += (precision - sum) as u32;
+    }
+    while sum > precision {
+        for &i in &order {
+            if sum == precision {
+                break;
+            }
+            if probs[i] > 1 {
+                probs[i] -= 1;
+                sum -= 1;
+            }
+        }
+    }
+
+    probs
+}
+
+/// Appends the state with its length in the two top bits of the last byte.
+fn write_state(data: &mut Vec<u8>, state: u32) {
+    let (len, tag) = match state {
+        0..=0x3F => (1, 0),
+        0x40..=0x3FFF => (2, 1),
+        0x4000..=0x3F_FFFF => (3, 2),
+        _ => (4, 3),
+    };
+    let value = state | tag << (8 * len - 2);
+    data.extend(&value.to_le_bytes()[..len]);
+}
+
+/// Binary rABS decoder with a fixed probability of zeros.
+pub(super) struct RabsBitDecoder<'a> {
+    data: &'a [u8],
+    offset: usize,
+    state: u32,
+    prob_zero: u32,
+}
+
+impl<'a> RabsBitDecoder<'a> {
+    pub(super) fn new(reader: &mut Reader<'a>) -> Result<Self, PointRainIOError> {
+        let prob_zero = u32::from(reader.u8()?);
+        let len = reader.size()?;
+        let data = reader.bytes(len)?;
+        let (offset, state) = read_state(data, 3)?;
+        let state = state + BIT_L_BASE;
+        if state >= BIT_L_BASE * IO_BASE {
+            return Err("Invalid rABS data".to_string().into());
+        }
+
+        Ok(Self {
+            data,
+            offset,
+            state,
+            prob_zero,
+        })
+    }
+
+    pub(super) fn next_bit(&mut self) -> bool {
+        let p = BIT_PRECISION - self.prob_zero;
+        if self.state < BIT_L_BASE && self.offset > 0 {
+            self.offset -= 1;
+            self.state = self.state * IO_BASE + u32::from(self.data[self.offset]);
+        }
+        let quo = self.state / BIT_PRECISION;
+        let rem = self.state % BIT_PRECISION;
+        let xn = quo * p;
+        if rem < p {
+            self.state = xn + rem;
+            true
+        } else {
+            self.state -= xn + p;
+            false
+        }
+    }
+}
+
+/// Bits stored uncompressed in 32-bit words, most significant first.
+pub(super) struct DirectBitDecoder {
+    words: Vec<u32>,
+    pos: usize,
+    used_bits: u32,
+}
+
+impl DirectBitDecoder {
+    pub(super) fn new(reader: &mut Reader) -> Result<Self, PointRainIOError> {
+        let len = reader.u32()? as usize;
+        if len == 0 || len % 4 != 0 {
+            return Err(format!("Invalid bit buffer size: {len}").into());
+        }
+        let words = reader
+            .bytes(len)?
+            .chunks_exact(4)
+            .map(|w| u32::from_le_bytes(w.try_into().unwrap()))
+            .collect();
+
+        Ok(Self {
+            words,
+            pos: 0,
+            used_bits: 0,
+        })
+    }
+
+    pub(super) fn next_bit(&mut self) -> Result<bool, PointRainIOError> {
+        Ok(self.bits(1)? == 1)
+    }
+
+    pub(super) fn bits(&mut self, nbits: u32) -> Result<u32, PointRainIOError> {
+        let mut value = 0;
+        for _ in 0..nbits {
+            let word = self
+                .words
+                .get(self.pos)
+                .ok_or_else(|| "Unexpected end of bit buffer".to_string())?;
+            value = value << 1 | (word >> (31 - self.used_bits)) & 1;
+            self.used_bits += 1;
+            if self.used_bits == 32 {
+                self.pos += 1;
+                self.used_bits = 0;
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// Bit decoders of the kd-tree coder.
+pub(super) enum BitDecoder<'a> {
+    Direct(DirectBitDecoder),
+    Rabs(RabsBitDecoder<'a>),
+    /// One rABS decoder per bit position of a number, and one for single bits.
+    Folded(Vec<RabsBitDecoder<'a>>, RabsBitDecoder<'a>),
+}
+
+impl<'a> BitDecoder<'a> {
+    pub(super) fn direct(reader: &mut Reader<'a>) -> Result<Self, PointRainIOError> {
+        DirectBitDecoder::new(reader).map(Self::Direct)
+    }
+
+    pub(super) fn rabs(reader: &mut Reader<'a>) -> Result<Self, PointRainIOError> {
+        RabsBitDecoder::new(reader).map(Self::Rabs)
+    }
+
+    pub(super) fn folded(reader: &mut Reader<'a>) -> Result<Self, PointRainIOError> {
+        let numbers = (0..32)
+            .map(|_| RabsBitDecoder::new(reader))
+            .collect::<Result<_, _>>()?;
+        Ok(Self::Folded(numbers, RabsBitDecoder::new(reader)?))
+    }
+
+    pub(super) fn next_bit(&mut self) -> Result<bool, PointRainIOError> {
+        match self {
+            Self::Direct(d) => d.next_bit(),
+            Self::Rabs(d) | Self::Folded(_, d) => Ok(d.next_bit()),
+        }
+    }
+
+    /// Reads an `nbits` number, most significant bit first.
+    pub(super) fn bits(&mut self, nbits: u32) -> Result<u32, PointRainIOError> {
+        match self {
+            Self::Direct(d) => d.bits(nbits),
+            Self::Rabs(d) => Ok((0..nbits).fold(0, |v, _| v << 1 | u32::from(d.next_bit()))),
+            Self::Folded(numbers, _) => Ok(numbers[..nbits as usize]
+                .iter_mut()
+                .fold(0, |v, d| v << 1 | u32::from(d.next_bit()))),
+        }
+    }
+}
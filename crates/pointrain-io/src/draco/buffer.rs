@@ -0,0 +1,100 @@
+use crate::error::PointRainIOError;
+
+/// Little-endian reader over an encoded buffer.
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Bitstream version, set once the header is read.
+    pub version: (u8, u8),
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            version: super::VERSION,
+        }
+    }
+
+    pub(super) fn bytes(&mut self, len: usize) -> Result<&'a [u8], PointRainIOError> {
+        let bytes = self
+            .data
+            .get(self.pos..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or_else(|| "Unexpected end of Draco data".to_string())?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// The unread part of the buffer.
+    pub(super) fn remaining(&self) -> &'a [u8] {
+        &self.data[self.pos..]
+    }
+
+    pub(super) fn advance(&mut self, len: usize) -> Result<(), PointRainIOError> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], PointRainIOError> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, PointRainIOError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    pub(super) fn i8(&mut self) -> Result<i8, PointRainIOError> {
+        Ok(i8::from_le_bytes(self.array()?))
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16, PointRainIOError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32, PointRainIOError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    pub(super) fn i32(&mut self) -> Result<i32, PointRainIOError> {
+        Ok(i32::from_le_bytes(self.array()?))
+    }
+
+    pub(super) fn f32(&mut self) -> Result<f32, PointRainIOError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a LEB128 encoded unsigned integer.
+    pub(super) fn varint(&mut self) -> Result<u64, PointRainIOError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Invalid varint in Draco data".to_string().into())
+    }
+
+    pub(super) fn varint_u32(&mut self) -> Result<u32, PointRainIOError> {
+        u32::try_from(self.varint()?).map_err(|_| "Varint out of range".to_string().into())
+    }
+
+    /// Sizes that were `uint32` before bitstream version 2.2 and are varints since.
+    pub(super) fn size(&mut self) -> Result<usize, PointRainIOError> {
+        if self.version < (2, 2) {
+            Ok(self.u32()? as usize)
+        } else {
+            Ok(self.varint()? as usize)
+        }
+    }
+}
+
+pub(super) fn put_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
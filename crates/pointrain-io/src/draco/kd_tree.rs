@@ -0,0 +1,170 @@
+//! Decoder of the integer points of the kd-tree encoding.
+//!
+//! The points are split in halves along one axis at a time, storing how many points fall in
+//! each half, until at most two points remain whose remaining bits are stored directly.
+
+use super::{ans::BitDecoder, buffer::Reader, most_significant_bit};
+use crate::error::PointRainIOError;
+
+/// Axis selection is coded in 4 bits above this many points at compression level 6.
+const SELECT_AXIS_MIN_POINTS: u32 = 64;
+
+struct Status {
+    num_points: u32,
+    last_axis: usize,
+    stack_pos: usize,
+}
+
+/// Decodes `num_points` points of `dimension` components, concatenated.
+pub(super) fn decode_points(
+    reader: &mut Reader,
+    compression_level: u8,
+    dimension: usize,
+    num_points: usize,
+) -> Result<Vec<u32>, PointRainIOError> {
+    if dimension == 0 {
+        return Err("Kd-tree encoding without attributes".to_string().into());
+    }
+
+    let bit_length = reader.u32()?;
+    if bit_length > 32 {
+        return Err(format!("Invalid kd-tree bit length: {bit_length}").into());
+    }
+    let encoded_points = reader.u32()? as usize;
+    if encoded_points != num_points {
+        return Err(format!("Expected {num_points} kd-tree points, got {encoded_points}").into());
+    }
+    if num_points == 0 {
+        return Ok(Vec::new());
+    }
+
+    let (mut numbers, mut remaining_bits, mut axes, mut halves, select_axis) =
+        match compression_level {
+            0 | 1 => (
+                BitDecoder::direct(reader)?,
+                BitDecoder::direct(reader)?,
+                BitDecoder::direct(reader)?,
+                BitDecoder::direct(reader)?,
+                false,
+            ),
+            2 | 3 => (
+                BitDecoder::rabs(reader)?,
+                BitDecoder::direct(reader)?,
+                BitDecoder::direct(reader)?,
+                BitDecoder::direct(reader)?,
+                false,
+            ),
+            4..=6 => (
+                BitDecoder::folded(reader)?,
+                BitDecoder::direct(reader)?,
+                BitDecoder::direct(reader)?,
+                BitDecoder::direct(reader)?,
+                compression_level == 6,
+            ),
+            _ => {
+                return Err(
+                    format!("Invalid kd-tree compression level: {compression_level}").into(),
+                )
+            }
+        };
+
+    let mut points = Vec::with_capacity(num_points * dimension);
+    let mut bases = vec![vec![0; dimension]];
+    let mut levels = vec![vec![0; dimension]];
+    let mut stack = vec![Status {
+        num_points: num_points as u32,
+        last_axis: 0,
+        stack_pos: 0,
+    }];
+
+    while let Some(status) = stack.pop() {
+        let n = status.num_points;
+        let pos = status.stack_pos;
+        if points.len() + n as usize * dimension > num_points * dimension {
+            return Err("Too many kd-tree points".to_string().into());
+        }
+
+        let axis = if !select_axis {
+            (status.last_axis + 1) % dimension
+        } else if n < SELECT_AXIS_MIN_POINTS {
+            // The least refined axis, the first one on ties.
+            (0..dimension).fold(0, |best, a| {
+                if levels[pos][a] < levels[pos][best] {
+                    a
+                } else {
+                    best
+                }
+            })
+        } else {
+            axes.bits(4)? as usize
+        };
+        if axis >= dimension {
+            return Err(format!("Invalid kd-tree axis: {axis}").into());
+        }
+
+        let level = levels[pos][axis];
+        if bit_length == level {
+            for _ in 0..n {
+                points.extend_from_slice(&bases[pos]);
+            }
+            continue;
+        }
+
+        if n <= 2 {
+            for _ in 0..n {
+                let mut p = bases[pos].clone();
+                for a in (0..dimension).map(|i| (axis + i) % dimension) {
+                    let nbits = bit_length - levels[pos][a];
+                    if nbits > 0 {
+                        p[a] |= remaining_bits.bits(nbits)?;
+                    }
+                }
+                points.extend(p);
+            }
+            continue;
+        }
+
+        let modifier = 1 << (bit_length - level - 1);
+        let mut base = bases[pos].clone();
+        base[axis] += modifier;
+
+        let number = numbers.bits(most_significant_bit(n))?;
+        let mut first_half = (n / 2)
+            .checked_sub(number)
+            .ok_or_else(|| "Invalid kd-tree split".to_string())?;
+        let mut second_half = n - first_half;
+        if first_half != second_half && !halves.next_bit()? {
+            std::mem::swap(&mut first_half, &mut second_half);
+        }
+
+        levels[pos][axis] += 1;
+        let next_levels = levels[pos].clone();
+        if bases.len() == pos + 1 {
+            bases.push(base);
+            levels.push(next_levels);
+        } else {
+            bases[pos + 1] = base;
+            levels[pos + 1] = next_levels;
+        }
+
+        if first_half > 0 {
+            stack.push(Status {
+                num_points: first_half,
+                last_axis: axis,
+                stack_pos: pos,
+            });
+        }
+        if second_half > 0 {
+            stack.push(Status {
+                num_points: second_half,
+                last_axis: axis,
+                stack_pos: pos + 1,
+            });
+        }
+    }
+
+    if points.len() != num_points * dimension {
+        return Err("Too few kd-tree points".to_string().into());
+    }
+    Ok(points)
+}
@@ -0,0 +1,214 @@
+//! Octahedral encoding of unit vectors and the canonicalized prediction transform of it.
+
+/// Quantized octahedral coordinates `(s, t)` in `0..=max`.
+///
+/// As in Draco, `max` is one less than the largest quantized value `2^bits - 1`, so that the
+/// diamond has a center. Corrections wrap around modulo the largest quantized value.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Octahedron {
+    max: i32,
+    center: i32,
+}
+
+impl Octahedron {
+    pub(super) fn new(quantization_bits: u8) -> Self {
+        Self::with_max_quantized((1 << quantization_bits) - 1)
+    }
+
+    /// From the largest quantized value, which is odd.
+    pub(super) fn with_max_quantized(max_quantized: i32) -> Self {
+        let max = max_quantized - 1;
+        Self {
+            max,
+            center: max / 2,
+        }
+    }
+
+    /// The largest quantized value, as stored in the bitstream.
+    pub(super) fn max_quantized(&self) -> i32 {
+        self.max + 1
+    }
+
+    pub(super) fn encode(&self, v: [f32; 3]) -> [i32; 2] {
+        let [x, y, z] = v.map(f64::from);
+        let abs_sum = x.abs() + y.abs() + z.abs();
+        let int_vec = if abs_sum > 1e-6 {
+            let scale = 1. / abs_sum;
+            let center = f64::from(self.center);
+            let mut int_vec = [
+                (x * scale * center + 0.5).floor() as i32,
+                (y * scale * center + 0.5).floor() as i32,
+                0,
+            ];
+            int_vec[2] = self.center - int_vec[0].abs() - int_vec[1].abs();
+            if int_vec[2] < 0 {
+                // Take the excess from the second coordinate.
+                if int_vec[1] > 0 {
+                    int_vec[1] += int_vec[2];
+                } else {
+                    int_vec[1] -= int_vec[2];
+                }
+                int_vec[2] = 0;
+            }
+            if z < 0. {
+                int_vec[2] = -int_vec[2];
+            }
+            int_vec
+        } else {
+            [self.center, 0, 0]
+        };
+
+        let [x, y, z] = int_vec;
+        let (s, t) = if x >= 0 {
+            (y + self.center, z + self.center)
+        } else {
+            (
+                if y < 0 { z.abs() } else { self.max - z.abs() },
+                if z < 0 { y.abs() } else { self.max - y.abs() },
+            )
+        };
+        self.canonicalize(s, t)
+    }
+
+    /// Picks one representation of the points on the border, which all map to the same
+    /// vector.
+    fn canonicalize(&self, s: i32, t: i32) -> [i32; 2] {
+        let (max, center) = (self.max, self.center);
+        if (s == 0 && (t == 0 || t == max)) || (s == max && t == 0) {
+            [max, max]
+        } else if s == 0 && t > center {
+            [s, center - (t - center)]
+        } else if s == max && t < center {
+            [s, center + (center - t)]
+        } else if t == max && s < center {
+            [center + (center - s), t]
+        } else if t == 0 && s > center {
+            [center - (s - center), t]
+        } else {
+            [s, t]
+        }
+    }
+
+    pub(super) fn decode(&self, [s, t]: [i32; 2]) -> [f32; 3] {
+        let scale = 1. / self.max as f32;
+        let mut y = s as f32 * scale * 2. - 1.;
+        let mut z = t as f32 * scale * 2. - 1.;
+        let x = 1. - y.abs() - z.abs();
+
+        // Points outside the central diamond are mirrored along its nearest edge.
+        let x_offset = (-x).max(0.);
+        y += if y < 0. { x_offset } else { -x_offset };
+        z += if z < 0. { x_offset } else { -x_offset };
+
+        let norm_squared = x * x + y * y + z * z;
+        if norm_squared < 1e-6 {
+            [0.; 3]
+        } else {
+            let d = norm_squared.sqrt().recip();
+            [x * d, y * d, z * d]
+        }
+    }
+
+    /// Correction of the canonicalized transform, in `0..max_quantized`.
+    pub(super) fn correction(&self, orig: [i32; 2], pred: [i32; 2]) -> [i32; 2] {
+        let mut orig = [orig[0] - self.center, orig[1] - self.center];
+        let mut pred = [pred[0] - self.center, pred[1] - self.center];
+        if !self.is_in_diamond(pred) {
+            orig = self.invert_diamond(orig);
+            pred = self.invert_diamond(pred);
+        }
+        if !is_in_bottom_left(pred) {
+            let rotation = rotation_count(pred);
+            orig = rotate(orig, rotation);
+            pred = rotate(pred, rotation);
+        }
+        [0, 1].map(|i| {
+            let c = orig[i] - pred[i];
+            if c < 0 {
+                c + self.max_quantized()
+            } else {
+                c
+            }
+        })
+    }
+
+    pub(super) fn original(&self, pred: [i32; 2], corr: [i32; 2]) -> [i32; 2] {
+        let mut pred = [pred[0] - self.center, pred[1] - self.center];
+        let in_diamond = self.is_in_diamond(pred);
+        if !in_diamond {
+            pred = self.invert_diamond(pred);
+        }
+        let in_bottom_left = is_in_bottom_left(pred);
+        let rotation = rotation_count(pred);
+        if !in_bottom_left {
+            pred = rotate(pred, rotation);
+        }
+
+        let mut orig = [0, 1].map(|i| {
+            let v = pred[i].wrapping_add(corr[i]);
+            if v > self.center {
+                v - self.max_quantized()
+            } else if v < -self.center {
+                v + self.max_quantized()
+            } else {
+                v
+            }
+        });
+        if !in_bottom_left {
+            orig = rotate(orig, (4 - rotation) % 4);
+        }
+        if !in_diamond {
+            orig = self.invert_diamond(orig);
+        }
+        [orig[0] + self.center, orig[1] + self.center]
+    }
+
+    fn is_in_diamond(&self, [s, t]: [i32; 2]) -> bool {
+        s.abs() + t.abs() <= self.center
+    }
+
+    /// Mirrors a point along the nearest edge of the diamond, with the center at the origin.
+    fn invert_diamond(&self, [s, t]: [i32; 2]) -> [i32; 2] {
+        let (sign_s, sign_t) = if s >= 0 && t >= 0 {
+            (1, 1)
+        } else if s <= 0 && t <= 0 {
+            (-1, -1)
+        } else {
+            (if s > 0 { 1 } else { -1 }, if t > 0 { 1 } else { -1 })
+        };
+        let corner_s = sign_s * self.center;
+        let corner_t = sign_t * self.center;
+        let (s, t) = (2 * s - corner_s, 2 * t - corner_t);
+        let (s, t) = if sign_s * sign_t >= 0 {
+            (-t, -s)
+        } else {
+            (t, s)
+        };
+        [(s + corner_s) / 2, (t + corner_t) / 2]
+    }
+}
+
+fn is_in_bottom_left([s, t]: [i32; 2]) -> bool {
+    (s == 0 && t == 0) || (s < 0 && t <= 0)
+}
+
+fn rotation_count([s, t]: [i32; 2]) -> u32 {
+    match (s.signum(), t.signum()) {
+        (0, 0) => 0,
+        (0, 1) => 3,
+        (0, _) => 1,
+        (1, 0 | 1) => 2,
+        (1, _) => 1,
+        (_, 1) => 3,
+        _ => 0,
+    }
+}
+
+fn rotate([s, t]: [i32; 2], count: u32) -> [i32; 2] {
+    match count {
+        1 => [t, -s],
+        2 => [-s, -t],
+        3 => [-t, s],
+        _ => [s, t],
+    }
+}
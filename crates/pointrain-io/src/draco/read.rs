@@ -0,0 +1,478 @@
+use std::{fs, path::Path};
+
+use pointrain_core::pc::PointCloudBase;
+
+use super::{
+    ans::decode_symbols, buffer::Reader, data_type_size, from_symbol, kd_tree,
+    octahedron::Octahedron, COLOR, DECODER_GENERIC, DECODER_INTEGER, DECODER_NORMALS,
+    DECODER_QUANTIZATION, DT_FLOAT32, KD_TREE_ENCODING, MAGIC, METADATA_FLAG, NORMAL, POINT_CLOUD,
+    POSITION, PREDICTION_DIFFERENCE, PREDICTION_NONE, SEQUENTIAL_ENCODING,
+    TRANSFORM_OCTAHEDRON_CANONICALIZED, TRANSFORM_WRAP, VERSION,
+};
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    point::PointReadable,
+};
+
+#[derive(Debug, Clone, Copy)]
+struct Attribute {
+    attribute_type: u8,
+    data_type: u8,
+    num_components: usize,
+}
+
+/// Reads a Draco point cloud file, see [`draco_decode`].
+pub fn draco_read<PC>(f: impl AsRef<Path>) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    draco_decode(&fs::read(f)?)
+}
+
+/// Decodes a Draco point cloud.
+///
+/// The first position, color and normal attributes are read; other attributes are
+/// ignored. Colors are scaled to `0..=255` if stored as floats. Sequentially and kd-tree
+/// encoded point clouds of bitstream versions 2.0 to 2.2 are supported.
+pub fn draco_decode<PC>(data: &[u8]) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let mut reader = Reader::new(data);
+    let (num_points, attributes) = decode(&mut reader)?;
+
+    let find = |attribute_type: u8, components: &[usize]| {
+        attributes.iter().find(|(a, _)| {
+            a.attribute_type == attribute_type && components.contains(&a.num_components)
+        })
+    };
+    let position = find(POSITION, &[3])
+        .ok_or_else(|| "Draco point cloud has no position attribute".to_string())?;
+    let color = find(COLOR, &[3, 4]);
+    let normal = find(NORMAL, &[3]);
+
+    let field = |name: &str, datatype| PointField {
+        name: name.into(),
+        datatype,
+        count: 1,
+    };
+    let mut fields: Vec<_> = ["x", "y", "z"]
+        .into_iter()
+        .map(|name| field(name, PointFieldType::F64))
+        .collect();
+    if let Some((a, _)) = color {
+        fields.extend(
+            ["r", "g", "b", "a"][..a.num_components]
+                .iter()
+                .map(|name| field(name, PointFieldType::U8)),
+        );
+    }
+    if normal.is_some() {
+        fields.extend(["nx", "ny", "nz"].map(|name| field(name, PointFieldType::F64)));
+    }
+    let func = PC::Point::read_data_func(&fields)?;
+
+    // Colors of other types are scaled to `0..=255`.
+    let color_scale = color.map_or(1., |(a, _)| match a.data_type {
+        4 => 1. / 257.,
+        9 | 10 => 255.,
+        _ => 1.,
+    });
+
+    let mut pc = PC::with_capacity(num_points);
+    let mut data = Vec::with_capacity(fields.len());
+    for i in 0..num_points {
+        data.clear();
+        data.extend(
+            position.1[3 * i..3 * i + 3]
+                .iter()
+                .map(|v| PointFieldDatum::F64(*v)),
+        );
+        if let Some((a, values)) = color {
+            let n = a.num_components;
+            data.extend(
+                values[n * i..n * i + n]
+                    .iter()
+                    .map(|v| PointFieldDatum::U8((v * color_scale).round().clamp(0., 255.) as u8)),
+            );
+        }
+        if let Some((_, values)) = normal {
+            data.extend(
+                values[3 * i..3 * i + 3]
+                    .iter()
+                    .map(|v| PointFieldDatum::F64(*v)),
+            );
+        }
+        pc.push(func(&data)?);
+    }
+
+    Ok(pc)
+}
+
+type Attributes = Vec<(Attribute, Vec<f64>)>;
+
+fn decode(reader: &mut Reader) -> Result<(usize, Attributes), PointRainIOError> {
+    if reader.bytes(MAGIC.len()).ok() != Some(MAGIC) {
+        return Err("Not a Draco file".to_string().into());
+    }
+    let version = (reader.u8()?, reader.u8()?);
+    if version.0 != VERSION.0 || version > VERSION {
+        return Err(format!("Unsupported Draco version: {}.{}", version.0, version.1).into());
+    }
+    reader.version = version;
+
+    if reader.u8()? != POINT_CLOUD {
+        return Err("Draco meshes are not supported".to_string().into());
+    }
+    let method = reader.u8()?;
+    if method != SEQUENTIAL_ENCODING && method != KD_TREE_ENCODING {
+        return Err(format!("Unknown Draco encoding method: {method}").into());
+    }
+    if reader.u16()? & METADATA_FLAG != 0 {
+        skip_metadata(reader)?;
+    }
+
+    let num_points =
+        usize::try_from(reader.i32()?).map_err(|_| "Invalid number of points".to_string())?;
+
+    // All attribute descriptions come before the values.
+    let num_decoders = reader.u8()?;
+    let mut decoders = Vec::with_capacity(usize::from(num_decoders));
+    for _ in 0..num_decoders {
+        let num_attributes = reader.varint_u32()?;
+        if num_attributes == 0 || num_attributes as usize > reader.remaining().len() {
+            return Err(format!("Invalid number of attributes: {num_attributes}").into());
+        }
+        let mut attributes = Vec::new();
+        for _ in 0..num_attributes {
+            let attribute_type = reader.u8()?;
+            let data_type = reader.u8()?;
+            let num_components = usize::from(reader.u8()?);
+            let _normalized = reader.u8()?;
+            let _unique_id = reader.varint()?;
+            if data_type_size(data_type).is_none() || num_components == 0 {
+                return Err(format!(
+                    "Invalid attribute with data type {data_type} and {num_components} components"
+                )
+                .into());
+            }
+            attributes.push(Attribute {
+                attribute_type,
+                data_type,
+                num_components,
+            });
+        }
+        let kinds = if method == SEQUENTIAL_ENCODING {
+            reader.bytes(attributes.len())?.to_vec()
+        } else {
+            Vec::new()
+        };
+        decoders.push((attributes, kinds));
+    }
+
+    let mut decoded = Vec::new();
+    for (attributes, kinds) in decoders {
+        let values = if method == SEQUENTIAL_ENCODING {
+            decode_sequential(reader, &attributes, &kinds, num_points)?
+        } else {
+            decode_kd_tree(reader, &attributes, num_points)?
+        };
+        decoded.extend(attributes.into_iter().zip(values));
+    }
+
+    Ok((num_points, decoded))
+}
+
+fn skip_metadata(reader: &mut Reader) -> Result<(), PointRainIOError> {
+    let num_attribute_metadata = reader.varint()?;
+    for _ in 0..num_attribute_metadata {
+        let _unique_id = reader.varint()?;
+        skip_metadata_block(reader)?;
+    }
+    skip_metadata_block(reader)
+}
+
+/// Skips a metadata block with its nested blocks, which follow after all their names.
+fn skip_metadata_block(reader: &mut Reader) -> Result<(), PointRainIOError> {
+    let mut pending = 1u64;
+    while pending > 0 {
+        pending -= 1;
+        for _ in 0..reader.varint()? {
+            let name_len = reader.u8()?;
+            reader.advance(usize::from(name_len))?;
+            let value_len = reader.varint()?;
+            reader.advance(value_len as usize)?;
+        }
+        let num_nested = reader.varint()?;
+        if num_nested > reader.remaining().len() as u64 {
+            return Err("Invalid Draco metadata".to_string().into());
+        }
+        for _ in 0..num_nested {
+            let name_len = reader.u8()?;
+            reader.advance(usize::from(name_len))?;
+        }
+        pending += num_nested;
+    }
+    Ok(())
+}
+
+enum Portable {
+    Values(Vec<f64>),
+    Integers(Vec<i32>),
+}
+
+fn decode_sequential(
+    reader: &mut Reader,
+    attributes: &[Attribute],
+    kinds: &[u8],
+    num_points: usize,
+) -> Result<Vec<Vec<f64>>, PointRainIOError> {
+    // The values of all attributes come before the data of their transforms.
+    let mut portable = Vec::with_capacity(attributes.len());
+    for (a, &kind) in attributes.iter().zip(kinds) {
+        portable.push(match kind {
+            DECODER_GENERIC => {
+                let size = data_type_size(a.data_type).unwrap();
+                let bytes = reader.bytes(num_points * a.num_components * size)?;
+                Portable::Values(
+                    bytes
+                        .chunks_exact(size)
+                        .map(|b| read_value(b, a.data_type))
+                        .collect(),
+                )
+            }
+            DECODER_INTEGER | DECODER_QUANTIZATION => Portable::Integers(decode_integers(
+                reader,
+                num_points,
+                a.num_components,
+                false,
+            )?),
+            DECODER_NORMALS => {
+                if a.num_components != 3 {
+                    return Err("Normals must have 3 components".to_string().into());
+                }
+                Portable::Integers(decode_integers(reader, num_points, 2, true)?)
+            }
+            _ => return Err(format!("Unknown attribute decoder: {kind}").into()),
+        });
+    }
+
+    attributes
+        .iter()
+        .zip(kinds)
+        .zip(portable)
+        .map(|((a, &kind), portable)| match portable {
+            Portable::Values(values) => Ok(values),
+            Portable::Integers(values) if kind == DECODER_QUANTIZATION => {
+                dequantize(reader, &values, a.num_components)
+            }
+            Portable::Integers(values) if kind == DECODER_NORMALS => {
+                let bits = reader.u8()?;
+                if !(1..=30).contains(&bits) {
+                    return Err(format!("Invalid normal quantization bits: {bits}").into());
+                }
+                let octahedron = Octahedron::new(bits);
+                Ok(values
+                    .chunks_exact(2)
+                    .flat_map(|st| octahedron.decode([st[0], st[1]]))
+                    .map(f64::from)
+                    .collect())
+            }
+            Portable::Integers(values) => Ok(values.into_iter().map(f64::from).collect()),
+        })
+        .collect()
+}
+
+/// Decodes `num_entries * num_components` integers and reverts their prediction.
+fn decode_integers(
+    reader: &mut Reader,
+    num_entries: usize,
+    num_components: usize,
+    normals: bool,
+) -> Result<Vec<i32>, PointRainIOError> {
+    let method = reader.i8()?;
+    let transform = if method == PREDICTION_NONE {
+        None
+    } else if method == PREDICTION_DIFFERENCE {
+        Some(reader.i8()?)
+    } else {
+        return Err(format!("Unsupported prediction scheme: {method}").into());
+    };
+    let expected = if normals {
+        TRANSFORM_OCTAHEDRON_CANONICALIZED
+    } else {
+        TRANSFORM_WRAP
+    };
+    if transform.is_some_and(|t| t != expected) {
+        return Err(format!("Unsupported prediction transform: {}", transform.unwrap()).into());
+    }
+
+    let num_values = num_entries * num_components;
+    if num_values > reader.remaining().len() * 64 {
+        return Err("Invalid number of values".to_string().into());
+    }
+    let symbols = if reader.u8()? > 0 {
+        decode_symbols(reader, num_values, num_components)?
+    } else {
+        let num_bytes = usize::from(reader.u8()?);
+        if !(1..=4).contains(&num_bytes) {
+            return Err(format!("Invalid integer size: {num_bytes}").into());
+        }
+        reader
+            .bytes(num_values * num_bytes)?
+            .chunks_exact(num_bytes)
+            .map(|b| b.iter().rev().fold(0, |v, b| v << 8 | u32::from(*b)))
+            .collect()
+    };
+
+    // Corrections of the canonicalized octahedron transform are never negative.
+    let mut values: Vec<i32> = match transform {
+        Some(TRANSFORM_OCTAHEDRON_CANONICALIZED) => symbols.iter().map(|s| *s as i32).collect(),
+        _ => symbols.into_iter().map(from_symbol).collect(),
+    };
+
+    match transform {
+        None => {}
+        Some(TRANSFORM_WRAP) => {
+            let min = reader.i32()?;
+            let max = reader.i32()?;
+            if max < min {
+                return Err("Invalid wrap transform".to_string().into());
+            }
+            let max_dif = i64::from(max) - i64::from(min) + 1;
+            for i in 0..values.len() {
+                let pred = match i.checked_sub(num_components) {
+                    Some(prev) => values[prev].clamp(min, max),
+                    None => 0i32.clamp(min, max),
+                };
+                let mut v = i64::from(pred) + i64::from(values[i]);
+                if v > i64::from(max) {
+                    v -= max_dif;
+                } else if v < i64::from(min) {
+                    v += max_dif;
+                }
+                values[i] = v as i32;
+            }
+        }
+        Some(_) => {
+            let max = reader.i32()?;
+            if reader.version < (2, 2) {
+                let _center = reader.i32()?;
+            }
+            if max <= 0 || max % 2 == 0 {
+                return Err(format!("Invalid octahedron size: {max}").into());
+            }
+            let octahedron = Octahedron::with_max_quantized(max);
+            let mut pred = [0, 0];
+            for st in values.chunks_exact_mut(2) {
+                if st.iter().any(|c| !(0..max).contains(c)) {
+                    return Err("Invalid normal correction".to_string().into());
+                }
+                pred = octahedron.original(pred, [st[0], st[1]]);
+                st.copy_from_slice(&pred);
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+/// Reads the quantization parameters and dequantizes `values`.
+fn dequantize(
+    reader: &mut Reader,
+    values: &[i32],
+    num_components: usize,
+) -> Result<Vec<f64>, PointRainIOError> {
+    let min = (0..num_components)
+        .map(|_| reader.f32())
+        .collect::<Result<Vec<_>, _>>()?;
+    let range = reader.f32()?;
+    let bits = reader.u8()?;
+    if !(1..=31).contains(&bits) {
+        return Err(format!("Invalid quantization bits: {bits}").into());
+    }
+
+    let delta = range / ((1u32 << bits) - 1) as f32;
+    Ok(values
+        .iter()
+        .zip(min.iter().cycle())
+        .map(|(q, min)| f64::from(*q as f32 * delta + min))
+        .collect())
+}
+
+fn read_value(bytes: &[u8], data_type: u8) -> f64 {
+    let array = |n| {
+        let mut a = [0; 8];
+        a[..n].copy_from_slice(&bytes[..n]);
+        a
+    };
+    match data_type {
+        1 => f64::from(bytes[0] as i8),
+        3 => f64::from(i16::from_le_bytes([bytes[0], bytes[1]])),
+        4 => f64::from(u16::from_le_bytes([bytes[0], bytes[1]])),
+        5 => f64::from(i32::from_le_bytes(bytes.try_into().unwrap())),
+        6 => f64::from(u32::from_le_bytes(bytes.try_into().unwrap())),
+        7 => i64::from_le_bytes(array(8)) as f64,
+        8 => u64::from_le_bytes(array(8)) as f64,
+        DT_FLOAT32 => f64::from(f32::from_le_bytes(bytes.try_into().unwrap())),
+        10 => f64::from_le_bytes(array(8)),
+        _ => f64::from(bytes[0]),
+    }
+}
+
+fn decode_kd_tree(
+    reader: &mut Reader,
+    attributes: &[Attribute],
+    num_points: usize,
+) -> Result<Vec<Vec<f64>>, PointRainIOError> {
+    for a in attributes {
+        if a.data_type != DT_FLOAT32 && !(1..=6).contains(&a.data_type) {
+            return Err(format!("Unsupported kd-tree data type: {}", a.data_type).into());
+        }
+    }
+    let is_signed = |a: &Attribute| matches!(a.data_type, 1 | 3 | 5);
+
+    let compression_level = reader.u8()?;
+    let dimension = attributes.iter().map(|a| a.num_components).sum();
+    let points = kd_tree::decode_points(reader, compression_level, dimension, num_points)?;
+
+    // Quantization parameters of float attributes and then the minimums of signed integer
+    // attributes follow the points.
+    let mut columns = Vec::with_capacity(attributes.len());
+    let mut offset = 0;
+    for a in attributes {
+        let n = a.num_components;
+        let values = (0..num_points).flat_map(|i| {
+            let start = i * dimension + offset;
+            points[start..start + n].iter().copied()
+        });
+        columns.push(if a.data_type == DT_FLOAT32 {
+            let values: Vec<_> = values.map(|v| v as i32).collect();
+            dequantize(reader, &values, n)?
+        } else {
+            let bits = 8 * data_type_size(a.data_type).unwrap();
+            let mask = if bits == 32 {
+                u32::MAX
+            } else {
+                (1 << bits) - 1
+            };
+            values.map(|v| f64::from(v & mask)).collect()
+        });
+        offset += n;
+    }
+    for (a, column) in attributes.iter().zip(&mut columns) {
+        if is_signed(a) {
+            let min = (0..a.num_components)
+                .map(|_| reader.varint_u32().map(from_symbol))
+                .collect::<Result<Vec<_>, _>>()?;
+            for (v, min) in column.iter_mut().zip(min.iter().cycle()) {
+                *v += f64::from(*min);
+            }
+        }
+    }
+
+    Ok(columns)
+}
@@ -0,0 +1,229 @@
+use std::{fs, path::Path};
+
+use pointrain_core::pc::PointCloudBase;
+
+use super::{
+    ans::encode_symbols, buffer::put_varint, most_significant_bit, octahedron::Octahedron,
+    to_symbol, DracoOptions, COLOR, DECODER_GENERIC, DECODER_INTEGER, DECODER_NORMALS,
+    DECODER_QUANTIZATION, DT_FLOAT32, DT_UINT8, MAGIC, NORMAL, POINT_CLOUD, POSITION,
+    PREDICTION_DIFFERENCE, SEQUENTIAL_ENCODING, TRANSFORM_OCTAHEDRON_CANONICALIZED, TRANSFORM_WRAP,
+    VERSION,
+};
+use crate::{error::PointRainIOError, mesh::WritableColumns, point::PointWritable};
+
+/// Writes a Draco point cloud file, see [`draco_encode`].
+pub fn draco_write<PC>(
+    f: impl AsRef<Path>,
+    pc: &PC,
+    options: &DracoOptions,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    fs::write(f, draco_encode(pc, options)?)?;
+    Ok(())
+}
+
+/// Encodes a point cloud with the sequential Draco encoding.
+///
+/// Positions are quantized over their bounding box, colors are stored losslessly as
+/// `uint8` (with alpha if the point type has one) and normals as octahedral coordinates.
+/// Points with a non-finite position are skipped.
+pub fn draco_encode<PC>(pc: &PC, options: &DracoOptions) -> Result<Vec<u8>, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let position_bits = options.position_quantization_bits;
+    let normal_bits = options.normal_quantization_bits;
+    if position_bits > 30 {
+        return Err(format!("Invalid position quantization bits: {position_bits}").into());
+    }
+    if !(2..=30).contains(&normal_bits) {
+        return Err(format!("Invalid normal quantization bits: {normal_bits}").into());
+    }
+
+    let columns = WritableColumns::new(PC::Point::columns())?;
+    let channels = if columns.alpha.is_some() { 4 } else { 3 };
+
+    let mut positions = Vec::with_capacity(pc.len());
+    let mut colors = Vec::new();
+    let mut normals = Vec::new();
+    let mut data = Vec::new();
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        let position = columns.xyz.map(|i| data[i].to_float());
+        if !position.iter().all(|v| v.is_finite()) {
+            continue;
+        }
+        positions.push(position);
+        if let Some(rgb) = columns.rgb {
+            for i in rgb.into_iter().chain(columns.alpha) {
+                colors.push(i32::from(data[i].to_color_channel()?));
+            }
+        }
+        if let Some(normal) = columns.normal {
+            normals.push(normal.map(|i| data[i].to_float()));
+        }
+    }
+    if positions.is_empty() {
+        return Err("Point cloud has no finite points".to_string().into());
+    }
+
+    let mut out = Vec::new();
+    out.extend(MAGIC);
+    out.extend([VERSION.0, VERSION.1, POINT_CLOUD, SEQUENTIAL_ENCODING]);
+    out.extend(0u16.to_le_bytes());
+    out.extend((positions.len() as i32).to_le_bytes());
+
+    // One decoder with the type, data type, components, normalization and decoder of
+    // every attribute.
+    let position_decoder = if position_bits == 0 {
+        DECODER_GENERIC
+    } else {
+        DECODER_QUANTIZATION
+    };
+    let mut attributes = vec![(POSITION, DT_FLOAT32, 3, 0, position_decoder)];
+    if columns.rgb.is_some() {
+        attributes.push((COLOR, DT_UINT8, channels, 1, DECODER_INTEGER));
+    }
+    if columns.normal.is_some() {
+        attributes.push((NORMAL, DT_FLOAT32, 3, 0, DECODER_NORMALS));
+    }
+    out.push(1);
+    put_varint(&mut out, attributes.len() as u64);
+    for (id, &(attribute_type, data_type, components, normalized, _)) in
+        attributes.iter().enumerate()
+    {
+        out.extend([attribute_type, data_type, components, normalized]);
+        put_varint(&mut out, id as u64);
+    }
+    out.extend(attributes.iter().map(|a| a.4));
+
+    // The values of all attributes come before the data of their transforms.
+    let quantization = (position_bits > 0).then(|| quantize(&positions, position_bits));
+    match &quantization {
+        Some((values, _, _)) => encode_integers(&mut out, values, 3, Transform::Wrap),
+        None => out.extend(positions.iter().flatten().flat_map(|v| v.to_le_bytes())),
+    }
+    if !colors.is_empty() {
+        encode_integers(&mut out, &colors, usize::from(channels), Transform::Wrap);
+    }
+    let octahedron = Octahedron::new(normal_bits);
+    if !normals.is_empty() {
+        let values: Vec<_> = normals.iter().flat_map(|n| octahedron.encode(*n)).collect();
+        encode_integers(&mut out, &values, 2, Transform::Octahedron(octahedron));
+    }
+
+    if let Some((_, min, range)) = quantization {
+        for v in min.into_iter().chain([range]) {
+            out.extend(v.to_le_bytes());
+        }
+        out.push(position_bits);
+    }
+    if !normals.is_empty() {
+        out.push(normal_bits);
+    }
+
+    Ok(out)
+}
+
+/// Quantizes positions over the cube at their minimum, returning the values, the minimum
+/// and the size of the cube.
+fn quantize(positions: &[[f32; 3]], bits: u8) -> (Vec<i32>, [f32; 3], f32) {
+    let (min, max) = positions
+        .iter()
+        .fold(([f32::MAX; 3], [f32::MIN; 3]), |(min, max), p| {
+            (
+                std::array::from_fn(|i| min[i].min(p[i])),
+                std::array::from_fn(|i| max[i].max(p[i])),
+            )
+        });
+    let range = (0..3).map(|i| max[i] - min[i]).fold(0., f32::max);
+    let range = if range > 0. { range } else { 1. };
+
+    let inverse_delta = ((1u32 << bits) - 1) as f32 / range;
+    let values = positions
+        .iter()
+        .flat_map(|p| (0..3).map(move |i| ((p[i] - min[i]) * inverse_delta + 0.5).floor() as i32))
+        .collect();
+
+    (values, min, range)
+}
+
+enum Transform {
+    /// Corrections wrapped into the range of the values.
+    Wrap,
+    Octahedron(Octahedron),
+}
+
+/// Writes `values` delta coded and compressed, followed by the prediction data.
+fn encode_integers(out: &mut Vec<u8>, values: &[i32], num_components: usize, transform: Transform) {
+    let transform_type = match transform {
+        Transform::Wrap => TRANSFORM_WRAP,
+        Transform::Octahedron(_) => TRANSFORM_OCTAHEDRON_CANONICALIZED,
+    };
+    out.extend([PREDICTION_DIFFERENCE as u8, transform_type as u8]);
+
+    let (symbols, prediction_data): (Vec<u32>, Vec<i32>) = match transform {
+        Transform::Wrap => {
+            let min = *values.iter().min().unwrap();
+            let max = *values.iter().max().unwrap();
+            let max_dif = i64::from(max) - i64::from(min) + 1;
+            let max_correction = if max_dif % 2 == 0 {
+                max_dif / 2 - 1
+            } else {
+                max_dif / 2
+            };
+            let min_correction = -(max_dif / 2);
+
+            let symbols = (0..values.len())
+                .map(|i| {
+                    let pred = i.checked_sub(num_components).map_or(0, |prev| values[prev]);
+                    let mut c = i64::from(values[i]) - i64::from(pred.clamp(min, max));
+                    if c < min_correction {
+                        c += max_dif;
+                    } else if c > max_correction {
+                        c -= max_dif;
+                    }
+                    to_symbol(c as i32)
+                })
+                .collect();
+            (symbols, vec![min, max])
+        }
+        Transform::Octahedron(octahedron) => {
+            let mut pred = [0, 0];
+            let symbols = values
+                .chunks_exact(2)
+                .flat_map(|st| {
+                    let orig = [st[0], st[1]];
+                    let correction = octahedron.correction(orig, pred);
+                    pred = orig;
+                    correction.map(|c| c as u32)
+                })
+                .collect();
+            (symbols, vec![octahedron.max_quantized()])
+        }
+    };
+
+    let start = out.len();
+    out.push(1);
+    if !encode_symbols(out, &symbols) {
+        // Too many distinct values for the entropy coder, store them with as few bytes as
+        // needed.
+        out.truncate(start);
+        let bits = most_significant_bit(symbols.iter().fold(0, |m, s| m | s).max(1)) + 1;
+        let num_bytes = (bits as usize).div_ceil(8);
+        out.extend([0, num_bytes as u8]);
+        for s in &symbols {
+            out.extend(&s.to_le_bytes()[..num_bytes]);
+        }
+    }
+
+    for v in prediction_data {
+        out.extend(v.to_le_bytes());
+    }
+}
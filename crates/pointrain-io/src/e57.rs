@@ -0,0 +1,5 @@
+mod bitpack;
+mod read;
+mod xml;
+
+pub use read::{e57_read, e57_read_scans, E57Scan};
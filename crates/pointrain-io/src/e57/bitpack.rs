@@ -0,0 +1,60 @@
+use super::xml::FieldKind;
+
+/// Decodes `count` values of a bitpack codec bytestream.
+pub(super) fn decode(kind: &FieldKind, bytes: &[u8], count: usize) -> Result<Vec<f64>, String> {
+    match *kind {
+        FieldKind::Float { double: false } => decode_fixed(bytes, count, 4, |b| {
+            f32::from_le_bytes(b.try_into().unwrap()).into()
+        }),
+        FieldKind::Float { double: true } => decode_fixed(bytes, count, 8, |b| {
+            f64::from_le_bytes(b.try_into().unwrap())
+        }),
+        FieldKind::Integer { min, max } => decode_integer(bytes, count, min, max)
+            .map(|v| v.into_iter().map(|v| v as f64).collect()),
+        FieldKind::ScaledInteger {
+            min,
+            max,
+            scale,
+            offset,
+        } => decode_integer(bytes, count, min, max)
+            .map(|v| v.into_iter().map(|v| v as f64 * scale + offset).collect()),
+    }
+}
+
+fn decode_fixed(
+    bytes: &[u8],
+    count: usize,
+    size: usize,
+    func: impl Fn(&[u8]) -> f64,
+) -> Result<Vec<f64>, String> {
+    match count.checked_mul(size) {
+        Some(len) if len <= bytes.len() => {}
+        _ => return Err("Not enough data in bytestream".into()),
+    }
+    Ok(bytes.chunks_exact(size).take(count).map(func).collect())
+}
+
+/// Integers are stored as `value - min` with the minimum number of bits, least significant
+/// bit first.
+fn decode_integer(bytes: &[u8], count: usize, min: i64, max: i64) -> Result<Vec<i64>, String> {
+    let range = max.abs_diff(min);
+    let bits = (u64::BITS - range.leading_zeros()) as usize;
+
+    // Also bounds `i * bits + b` below, which stays under `bytes.len() * 8`.
+    match count.checked_mul(bits) {
+        Some(len) if len.div_ceil(8) <= bytes.len() => {}
+        _ => return Err("Not enough data in bytestream".into()),
+    }
+
+    let mut values = Vec::with_capacity(count);
+    for i in 0..count {
+        let mut raw = 0u64;
+        for b in 0..bits {
+            let pos = i * bits + b;
+            let bit = (bytes[pos / 8] >> (pos % 8)) & 1;
+            raw |= u64::from(bit) << b;
+        }
+        values.push(min.wrapping_add(raw as i64));
+    }
+    Ok(values)
+}
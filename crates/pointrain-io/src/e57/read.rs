@@ -0,0 +1,283 @@
+use std::{fs, path::Path};
+
+use nalgebra::{Isometry3, Point3};
+use pointrain_core::pc::PointCloudBase;
+
+use super::{
+    bitpack,
+    xml::{parse_data3d, Data3D, Field},
+};
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    point::PointReadable,
+};
+
+const SIGNATURE: &[u8] = b"ASTM-E57";
+const HEADER_SIZE: usize = 48;
+/// Bytes at the end of every page holding its checksum.
+const CHECKSUM_SIZE: usize = 4;
+const COMPRESSED_VECTOR_SECTION: u8 = 1;
+const DATA_PACKET: u8 = 1;
+
+/// A single scan of an E57 file.
+#[derive(Debug, Clone)]
+pub struct E57Scan<PC> {
+    pub name: Option<String>,
+    pub guid: Option<String>,
+    /// Transform from the scan's own frame to the file frame.
+    pub pose: Isometry3<f64>,
+    /// Points in the scan's own frame.
+    pub pc: PC,
+}
+
+/// Reads all scans of an E57 file into one point cloud in the file frame.
+///
+/// Cartesian and spherical coordinates, `intensity` and `colorRed`/`colorGreen`/`colorBlue`
+/// are read. Colors are scaled to `0..=255` by the color limits of the scan; intensity is
+/// kept as stored. Points flagged as invalid are dropped. Page checksums are not verified.
+pub fn e57_read<PC>(f: impl AsRef<Path>) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let (file, scans) = E57File::open(f)?;
+
+    let mut pc = PC::new();
+    for scan in &scans {
+        file.read_scan(scan, Some(&scan.pose), &mut pc)?;
+    }
+
+    Ok(pc)
+}
+
+/// Reads every scan of an E57 file separately, without applying its pose.
+pub fn e57_read_scans<PC>(f: impl AsRef<Path>) -> Result<Vec<E57Scan<PC>>, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let (file, scans) = E57File::open(f)?;
+
+    scans
+        .into_iter()
+        .map(|scan| {
+            let mut pc = PC::new();
+            file.read_scan(&scan, None, &mut pc)?;
+            Ok(E57Scan {
+                name: scan.name,
+                guid: scan.guid,
+                pose: scan.pose,
+                pc,
+            })
+        })
+        .collect()
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Result<u16, PointRainIOError> {
+    bytes
+        .get(at..at + 2)
+        .map(|b| u16::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| "Unexpected end of file".to_string().into())
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> Result<u64, PointRainIOError> {
+    bytes
+        .get(at..at + 8)
+        .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| "Unexpected end of file".to_string().into())
+}
+
+/// The file contents with the page checksums removed.
+struct E57File {
+    data: Vec<u8>,
+    page_size: usize,
+}
+
+impl E57File {
+    fn open(f: impl AsRef<Path>) -> Result<(Self, Vec<Data3D>), PointRainIOError> {
+        let raw = fs::read(f)?;
+
+        if raw.len() < HEADER_SIZE || &raw[..SIGNATURE.len()] != SIGNATURE {
+            return Err("Not an E57 file".to_string().into());
+        }
+        let major = u32::from_le_bytes(raw[8..12].try_into().unwrap());
+        if major != 1 {
+            return Err(format!("Unsupported E57 version: {major}").into());
+        }
+        let xml_offset = read_u64(&raw, 24)?;
+        let xml_length = read_u64(&raw, 32)? as usize;
+        let page_size = read_u64(&raw, 40)? as usize;
+        if page_size <= CHECKSUM_SIZE {
+            return Err(format!("Invalid page size: {page_size}").into());
+        }
+
+        let data = raw
+            .chunks(page_size)
+            .flat_map(|page| &page[..page.len().min(page_size - CHECKSUM_SIZE)])
+            .copied()
+            .collect();
+        let file = Self { data, page_size };
+
+        let xml = file.slice(file.logical_offset(xml_offset), xml_length)?;
+        let xml = std::str::from_utf8(xml).map_err(|e| format!("Invalid XML section: {e}"))?;
+        let scans = parse_data3d(xml)?;
+
+        Ok((file, scans))
+    }
+
+    fn logical_offset(&self, physical: u64) -> usize {
+        let physical = physical as usize;
+        physical / self.page_size * (self.page_size - CHECKSUM_SIZE) + physical % self.page_size
+    }
+
+    fn slice(&self, offset: usize, len: usize) -> Result<&[u8], PointRainIOError> {
+        self.data
+            .get(offset..offset + len)
+            .ok_or_else(|| "Unexpected end of file".to_string().into())
+    }
+
+    /// Decodes the compressed vector of a scan into one column per prototype field.
+    fn read_columns(&self, scan: &Data3D) -> Result<Vec<Vec<f64>>, PointRainIOError> {
+        let section = self.logical_offset(scan.file_offset);
+        if self.slice(section, 1)?[0] != COMPRESSED_VECTOR_SECTION {
+            return Err("Invalid compressed vector section".to_string().into());
+        }
+        let section_end = section + read_u64(&self.data, section + 8)? as usize;
+        let mut offset = self.logical_offset(read_u64(&self.data, section + 16)?);
+
+        let mut streams = vec![Vec::new(); scan.prototype.len()];
+        while offset < section_end {
+            let packet_type = self.slice(offset, 1)?[0];
+            let packet_length = read_u16(&self.data, offset + 2)? as usize + 1;
+
+            // Index and empty packets carry no point data.
+            if packet_type == DATA_PACKET {
+                let count = read_u16(&self.data, offset + 4)? as usize;
+                if count != streams.len() {
+                    return Err(format!(
+                        "Data packet has {count} bytestreams, expected {}",
+                        streams.len()
+                    )
+                    .into());
+                }
+
+                let mut start = offset + 6 + 2 * count;
+                for (i, stream) in streams.iter_mut().enumerate() {
+                    let len = read_u16(&self.data, offset + 6 + 2 * i)? as usize;
+                    stream.extend_from_slice(self.slice(start, len)?);
+                    start += len;
+                }
+            }
+
+            offset += packet_length;
+        }
+
+        scan.prototype
+            .iter()
+            .zip(&streams)
+            .map(|(field, stream)| {
+                bitpack::decode(&field.kind, stream, scan.record_count)
+                    .map_err(|e| format!("{}: {e}", field.name).into())
+            })
+            .collect()
+    }
+
+    fn read_scan<PC>(
+        &self,
+        scan: &Data3D,
+        pose: Option<&Isometry3<f64>>,
+        pc: &mut PC,
+    ) -> Result<(), PointRainIOError>
+    where
+        PC: PointCloudBase,
+        PC::Point: PointReadable,
+    {
+        let columns = self.read_columns(scan)?;
+        let find = |name: &str| scan.prototype.iter().position(|f| f.name == name);
+
+        let cartesian = find("cartesianX")
+            .zip(find("cartesianY"))
+            .zip(find("cartesianZ"));
+        let spherical = find("sphericalRange")
+            .zip(find("sphericalAzimuth"))
+            .zip(find("sphericalElevation"));
+        if cartesian.is_none() && spherical.is_none() {
+            return Err(missing_coordinates(&scan.prototype));
+        }
+        let invalid = if cartesian.is_some() {
+            find("cartesianInvalidState")
+        } else {
+            find("sphericalInvalidState")
+        };
+        let intensity = find("intensity");
+        let color = find("colorRed")
+            .zip(find("colorGreen"))
+            .zip(find("colorBlue"));
+
+        let mut names = vec!["x", "y", "z"];
+        if intensity.is_some() {
+            names.push("intensity");
+        }
+        if color.is_some() {
+            names.extend(["r", "g", "b"]);
+        }
+        let fields: Vec<_> = names
+            .into_iter()
+            .map(|name| PointField {
+                name: name.into(),
+                datatype: PointFieldType::F64,
+                count: 1,
+            })
+            .collect();
+        let func = PC::Point::read_data_func(&fields)?;
+
+        let color_channel = |field: usize, v: f64| {
+            let (min, max) = scan
+                .color_limits
+                .or(scan.prototype[field].limits)
+                .unwrap_or((0., 255.));
+            let scaled = if max > min {
+                (v - min) / (max - min)
+            } else {
+                0.
+            };
+            PointFieldDatum::U8((scaled * 255.).round().clamp(0., 255.) as u8)
+        };
+
+        let mut data = Vec::with_capacity(fields.len());
+        let valid = |i: &usize| invalid.map_or(true, |s| columns[s][*i] == 0.);
+        for i in (0..scan.record_count).filter(valid) {
+            let position = match (cartesian, spherical) {
+                (Some(((x, y), z)), _) => Point3::new(columns[x][i], columns[y][i], columns[z][i]),
+                (_, Some(((r, a), e))) => {
+                    let (range, azimuth, elevation) = (columns[r][i], columns[a][i], columns[e][i]);
+                    Point3::new(
+                        range * elevation.cos() * azimuth.cos(),
+                        range * elevation.cos() * azimuth.sin(),
+                        range * elevation.sin(),
+                    )
+                }
+                (None, None) => unreachable!(),
+            };
+            let position = pose.map_or(position, |pose| pose * position);
+
+            data.clear();
+            data.extend(position.iter().map(|v| PointFieldDatum::F64(*v)));
+            if let Some(intensity) = intensity {
+                data.push(PointFieldDatum::F64(columns[intensity][i]));
+            }
+            if let Some(((r, g), b)) = color {
+                data.extend([r, g, b].map(|c| color_channel(c, columns[c][i])));
+            }
+            pc.push(func(&data)?);
+        }
+
+        Ok(())
+    }
+}
+
+fn missing_coordinates(prototype: &[Field]) -> PointRainIOError {
+    let names: Vec<_> = prototype.iter().map(|f| f.name.as_str()).collect();
+    format!("No cartesian or spherical coordinates in {names:?}").into()
+}
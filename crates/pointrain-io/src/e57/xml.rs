@@ -0,0 +1,168 @@
+use nalgebra::{Isometry3, Quaternion, Translation3, UnitQuaternion};
+use roxmltree::Node;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum FieldKind {
+    Float {
+        double: bool,
+    },
+    Integer {
+        min: i64,
+        max: i64,
+    },
+    ScaledInteger {
+        min: i64,
+        max: i64,
+        scale: f64,
+        offset: f64,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Field {
+    pub name: String,
+    pub kind: FieldKind,
+    /// Lower and upper bound of the decoded value, if known.
+    pub limits: Option<(f64, f64)>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Data3D {
+    pub name: Option<String>,
+    pub guid: Option<String>,
+    pub pose: Isometry3<f64>,
+    pub file_offset: u64,
+    pub record_count: usize,
+    pub prototype: Vec<Field>,
+    pub color_limits: Option<(f64, f64)>,
+}
+
+pub(super) fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|n| n.is_element() && n.tag_name().name() == name)
+}
+
+fn text_value<T: std::str::FromStr>(node: Node, name: &str) -> Option<T> {
+    child(node, name)?.text()?.trim().parse().ok()
+}
+
+fn attribute<T: std::str::FromStr>(node: Node, name: &str) -> Result<Option<T>, String> {
+    node.attribute(name)
+        .map(|v| {
+            v.trim()
+                .parse()
+                .map_err(|_| format!("Invalid attribute {name}: {v}"))
+        })
+        .transpose()
+}
+
+fn parse_field(node: Node) -> Result<Field, String> {
+    let name = node.tag_name().name().to_string();
+
+    let (kind, limits) = match node.attribute("type") {
+        Some("Float") => {
+            let kind = FieldKind::Float {
+                double: node.attribute("precision") != Some("single"),
+            };
+            let min = attribute(node, "minimum")?;
+            let max = attribute(node, "maximum")?;
+            (kind, min.zip(max))
+        }
+        Some("Integer") => {
+            let min = attribute(node, "minimum")?.unwrap_or(i64::MIN);
+            let max = attribute(node, "maximum")?.unwrap_or(i64::MAX);
+            (
+                FieldKind::Integer { min, max },
+                Some((min as f64, max as f64)),
+            )
+        }
+        Some("ScaledInteger") => {
+            let min = attribute(node, "minimum")?.unwrap_or(i64::MIN);
+            let max = attribute(node, "maximum")?.unwrap_or(i64::MAX);
+            let scale = attribute(node, "scale")?.unwrap_or(1.);
+            let offset = attribute(node, "offset")?.unwrap_or(0.);
+            (
+                FieldKind::ScaledInteger {
+                    min,
+                    max,
+                    scale,
+                    offset,
+                },
+                Some((min as f64 * scale + offset, max as f64 * scale + offset)),
+            )
+        }
+        t => return Err(format!("Unsupported prototype type of {name}: {t:?}")),
+    };
+
+    Ok(Field { name, kind, limits })
+}
+
+fn parse_pose(node: Option<Node>) -> Isometry3<f64> {
+    let Some(node) = node else {
+        return Isometry3::identity();
+    };
+
+    let rotation = child(node, "rotation")
+        .map(|r| {
+            UnitQuaternion::from_quaternion(Quaternion::new(
+                text_value(r, "w").unwrap_or(1.),
+                text_value(r, "x").unwrap_or(0.),
+                text_value(r, "y").unwrap_or(0.),
+                text_value(r, "z").unwrap_or(0.),
+            ))
+        })
+        .unwrap_or_else(UnitQuaternion::identity);
+    let translation = child(node, "translation")
+        .map(|t| {
+            Translation3::new(
+                text_value(t, "x").unwrap_or(0.),
+                text_value(t, "y").unwrap_or(0.),
+                text_value(t, "z").unwrap_or(0.),
+            )
+        })
+        .unwrap_or_else(Translation3::identity);
+
+    Isometry3::from_parts(translation, rotation)
+}
+
+fn parse_limits(node: Option<Node>, min: &str, max: &str) -> Option<(f64, f64)> {
+    let node = node?;
+    Some((text_value(node, min)?, text_value(node, max)?))
+}
+
+pub(super) fn parse_data3d(xml: &str) -> Result<Vec<Data3D>, String> {
+    let doc = roxmltree::Document::parse(xml).map_err(|e| format!("Invalid XML section: {e}"))?;
+    let root = doc.root_element();
+
+    let Some(data3d) = child(root, "data3D") else {
+        return Ok(Vec::new());
+    };
+
+    data3d
+        .children()
+        .filter(Node::is_element)
+        .map(|scan| {
+            let points = child(scan, "points").ok_or("data3D has no points")?;
+            let prototype = child(points, "prototype").ok_or("points has no prototype")?;
+
+            Ok(Data3D {
+                name: child(scan, "name").and_then(|n| n.text()).map(Into::into),
+                guid: child(scan, "guid").and_then(|n| n.text()).map(Into::into),
+                pose: parse_pose(child(scan, "pose")),
+                file_offset: attribute(points, "fileOffset")?.ok_or("points has no fileOffset")?,
+                record_count: attribute(points, "recordCount")?
+                    .ok_or("points has no recordCount")?,
+                prototype: prototype
+                    .children()
+                    .filter(Node::is_element)
+                    .map(parse_field)
+                    .collect::<Result<_, _>>()?,
+                color_limits: parse_limits(
+                    child(scan, "colorLimits"),
+                    "colorRedMinimum",
+                    "colorRedMaximum",
+                ),
+            })
+        })
+        .collect()
+}
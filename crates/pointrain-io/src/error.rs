@@ -21,6 +21,14 @@ pub enum PointRainIOError {
     ParseIntError(#[from] num::ParseIntError),
     #[error("parse float error")]
     ParseFloatError(#[from] num::ParseFloatError),
+    #[cfg(feature = "arrow")]
+    #[error("Arrow error")]
+    ArrowError(#[from] arrow_schema::ArrowError),
+    #[cfg(feature = "arrow")]
+    #[error("Parquet error")]
+    ParquetError(#[from] parquet::errors::ParquetError),
+    #[error("Zip error")]
+    ZipError(#[from] zip::result::ZipError),
     #[error("Missing field: {0}")]
     MissingFieldError(&'static str),
     #[error("{msg}")]
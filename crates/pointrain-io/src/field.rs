@@ -1,4 +1,6 @@
-use bytes::Buf;
+use std::fmt;
+
+use bytes::{Buf, BufMut};
 use pointrain_core::types::{Float, Rgb};
 
 use crate::error::ParseNumberError;
@@ -51,7 +53,24 @@ impl PointFieldType {
         })
     }
 
-    fn bytes(self) -> usize {
+    /// Accepts both the legacy (`unsigned_char`) and the XML (`UInt8`) VTK names.
+    pub fn from_vtk_type(r#type: &str) -> Result<Self, String> {
+        Ok(match r#type {
+            "char" | "Int8" => Self::I8,
+            "unsigned_char" | "UInt8" => Self::U8,
+            "short" | "Int16" => Self::I16,
+            "unsigned_short" | "UInt16" => Self::U16,
+            "int" | "Int32" => Self::I32,
+            "unsigned_int" | "UInt32" => Self::U32,
+            "float" | "Float32" => Self::F32,
+            "double" | "Float64" => Self::F64,
+            _ => {
+                return Err(format!("Unsupported VTK data type: {}", r#type));
+            }
+        })
+    }
+
+    pub fn bytes(self) -> usize {
         match self {
             Self::U8 => 1,
             Self::U16 => 2,
@@ -106,6 +125,14 @@ impl PointFieldDatum {
         })
     }
 
+    /// Parses a token whose type is not declared, as an integer if possible.
+    pub(crate) fn parse_number(s: &str) -> Result<Self, ParseNumberError> {
+        Ok(match s.parse() {
+            Ok(v) => Self::I32(v),
+            Err(_) => Self::F64(s.parse()?),
+        })
+    }
+
     pub(crate) fn from_bytes_le(bytes: &mut &[u8], r#type: PointFieldType) -> Self {
         use PointFieldType::*;
 
@@ -136,6 +163,32 @@ impl PointFieldDatum {
         }
     }
 
+    pub(crate) fn put_le(self, buf: &mut impl BufMut) {
+        match self {
+            Self::U8(v) => buf.put_u8(v),
+            Self::U16(v) => buf.put_u16_le(v),
+            Self::U32(v) => buf.put_u32_le(v),
+            Self::I8(v) => buf.put_i8(v),
+            Self::I16(v) => buf.put_i16_le(v),
+            Self::I32(v) => buf.put_i32_le(v),
+            Self::F32(v) => buf.put_f32_le(v),
+            Self::F64(v) => buf.put_f64_le(v),
+        }
+    }
+
+    pub(crate) fn put_be(self, buf: &mut impl BufMut) {
+        match self {
+            Self::U8(v) => buf.put_u8(v),
+            Self::U16(v) => buf.put_u16(v),
+            Self::U32(v) => buf.put_u32(v),
+            Self::I8(v) => buf.put_i8(v),
+            Self::I16(v) => buf.put_i16(v),
+            Self::I32(v) => buf.put_i32(v),
+            Self::F32(v) => buf.put_f32(v),
+            Self::F64(v) => buf.put_f64(v),
+        }
+    }
+
     pub(crate) fn to_float(self) -> Float {
         match self {
             Self::U8(v) => v.into(),
@@ -149,20 +202,97 @@ impl PointFieldDatum {
         }
     }
 
-    pub(crate) fn as_u8(self) -> Result<u8, String> {
-        match self {
-            Self::U8(v) => Ok(v),
-            _ => Err(format!("{:?} cannot be parsed as u8", self)),
-        }
+    /// Converts a single color channel.
+    ///
+    /// 16-bit values are scaled down and floating point values are expected in `[0, 1]`.
+    pub(crate) fn to_color_channel(self) -> Result<u8, String> {
+        let err = || format!("{:?} cannot be parsed as a color channel", self);
+
+        Ok(match self {
+            Self::U8(v) => v,
+            Self::U16(v) => (v >> 8) as u8,
+            Self::U32(v) => u8::try_from(v).map_err(|_| err())?,
+            Self::I8(v) => u8::try_from(v).map_err(|_| err())?,
+            Self::I16(v) => u8::try_from(v).map_err(|_| err())?,
+            Self::I32(v) => u8::try_from(v).map_err(|_| err())?,
+            Self::F32(v) if (0.0..=1.0).contains(&v) => (v * 255.).round() as u8,
+            Self::F64(v) if (0.0..=1.0).contains(&v) => (v * 255.).round() as u8,
+            _ => return Err(err()),
+        })
     }
 
-    pub(crate) fn to_color(self) -> Result<Rgb, String> {
+    /// Reinterprets a packed `0xAARRGGBB` value as stored by PCL.
+    fn to_packed(self) -> Result<u32, String> {
         Ok(match self {
-            Self::F32(v) => {
-                let bytes = v.to_le_bytes();
-                Rgb::new(bytes[0], bytes[1], bytes[2])
-            }
-            _ => return Err(format!("{:?} cannot be parsed as RGB", self)),
+            Self::F32(v) => v.to_bits(),
+            Self::U32(v) => v,
+            Self::I32(v) => v as u32,
+            _ => return Err(format!("{:?} cannot be parsed as packed RGB", self)),
         })
     }
+
+    pub(crate) fn to_color(self) -> Result<Rgb, String> {
+        let [_, r, g, b] = self.to_packed()?.to_be_bytes();
+        Ok(Rgb::new(r, g, b))
+    }
+
+    pub(crate) fn to_color_alpha(self) -> Result<(Rgb, u8), String> {
+        let [a, r, g, b] = self.to_packed()?.to_be_bytes();
+        Ok((Rgb::new(r, g, b), a))
+    }
+}
+
+impl fmt::Display for PointFieldDatum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::U8(v) => v.fmt(f),
+            Self::U16(v) => v.fmt(f),
+            Self::U32(v) => v.fmt(f),
+            Self::I8(v) => v.fmt(f),
+            Self::I16(v) => v.fmt(f),
+            Self::I32(v) => v.fmt(f),
+            Self::F32(v) => v.fmt(f),
+            Self::F64(v) => v.fmt(f),
+        }
+    }
+}
+
+/// Where the color of a point is stored.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ColorField {
+    /// Packed `0x00RRGGBB` value (e.g. PCD `rgb`). Alpha is assumed opaque.
+    Packed(usize),
+    /// Packed `0xAARRGGBB` value (e.g. PCD `rgba`).
+    PackedAlpha(usize),
+    /// One field per channel, alpha is optional.
+    Channels {
+        r: usize,
+        g: usize,
+        b: usize,
+        a: Option<usize>,
+    },
+}
+
+impl ColorField {
+    pub(crate) fn read(self, data: &[PointFieldDatum]) -> Result<(Rgb, u8), String> {
+        match self {
+            Self::Packed(i) => Ok((data[i].to_color()?, u8::MAX)),
+            Self::PackedAlpha(i) => data[i].to_color_alpha(),
+            Self::Channels { r, g, b, a } => Ok((
+                Rgb::new(
+                    data[r].to_color_channel()?,
+                    data[g].to_color_channel()?,
+                    data[b].to_color_channel()?,
+                ),
+                match a {
+                    Some(a) => data[a].to_color_channel()?,
+                    None => u8::MAX,
+                },
+            )),
+        }
+    }
+
+    pub(crate) fn read_rgb(self, data: &[PointFieldDatum]) -> Result<Rgb, String> {
+        self.read(data).map(|(rgb, _)| rgb)
+    }
 }
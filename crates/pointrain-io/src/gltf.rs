@@ -0,0 +1,191 @@
+//! Binary glTF 2.0 (`.glb`) export of point clouds as a `POINTS` primitive.
+
+use std::{fs, path::Path};
+
+use pointrain_core::{pc::PointCloudBase, types::Position};
+use serde_json::{json, Value};
+
+use crate::{error::PointRainIOError, mesh::WritableColumns, point::PointWritable};
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+const ARRAY_BUFFER: u32 = 34962;
+const UNSIGNED_BYTE: u32 = 5121;
+const FLOAT: u32 = 5126;
+const MODE_POINTS: u32 = 0;
+
+/// Writes a GLB file with one `POINTS` primitive.
+///
+/// Positions are written as `POSITION`, colors as normalized `COLOR_0` bytes (with alpha if
+/// the point type has one) and normals as `NORMAL`. Points with a non-finite position are
+/// skipped; normals are normalized, and zero-length normals are written as `+Z`.
+pub fn glb_write<PC>(f: impl AsRef<Path>, pc: &PC) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = WritableColumns::new(PC::Point::columns())?;
+
+    let mut positions = Vec::new();
+    let mut colors = Vec::new();
+    let mut normals = Vec::new();
+    let mut data = Vec::new();
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        let [x, y, z] = columns.xyz.map(|i| data[i].to_float());
+        let position = Position::new(x, y, z);
+        if !position.iter().all(|v| v.is_finite()) {
+            continue;
+        }
+        positions.push(position);
+
+        // Colors always take four bytes to keep every element 4-byte aligned.
+        if let Some(rgb) = columns.rgb {
+            let alpha = columns
+                .alpha
+                .map_or(Ok(u8::MAX), |a| data[a].to_color_channel());
+            colors.push([
+                data[rgb[0]].to_color_channel()?,
+                data[rgb[1]].to_color_channel()?,
+                data[rgb[2]].to_color_channel()?,
+                alpha?,
+            ]);
+        }
+        if let Some(normal) = columns.normal {
+            let [nx, ny, nz] = normal.map(|i| data[i].to_float());
+            let normal = nalgebra::Vector3::new(nx, ny, nz)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(nalgebra::Vector3::z);
+            normals.push(normal);
+        }
+    }
+
+    if positions.is_empty() {
+        return Err("Point cloud has no finite points".to_string().into());
+    }
+
+    let mut bin = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut attributes = serde_json::Map::new();
+
+    let mut push_view = |bytes: Vec<u8>, stride: Option<usize>, accessor: Value| {
+        let mut view = json!({
+            "buffer": 0,
+            "byteOffset": bin.len(),
+            "byteLength": bytes.len(),
+            "target": ARRAY_BUFFER,
+        });
+        if let Some(stride) = stride {
+            view["byteStride"] = stride.into();
+        }
+        buffer_views.push(view);
+        bin.extend(bytes);
+
+        let mut accessor = accessor;
+        accessor["bufferView"] = json!(buffer_views.len() - 1);
+        accessors.push(accessor);
+        accessors.len() - 1
+    };
+
+    let (min, max) = bounds(positions.iter().map(|p| p.coords));
+    let index = push_view(
+        positions
+            .iter()
+            .flat_map(|p| p.iter().flat_map(|v| v.to_le_bytes()))
+            .collect(),
+        None,
+        json!({
+            "componentType": FLOAT,
+            "count": positions.len(),
+            "type": "VEC3",
+            "min": min,
+            "max": max,
+        }),
+    );
+    attributes.insert("POSITION".into(), index.into());
+
+    if !colors.is_empty() {
+        let r#type = if columns.alpha.is_some() {
+            "VEC4"
+        } else {
+            "VEC3"
+        };
+        let index = push_view(
+            colors.iter().flatten().copied().collect(),
+            Some(4),
+            json!({
+                "componentType": UNSIGNED_BYTE,
+                "normalized": true,
+                "count": colors.len(),
+                "type": r#type,
+            }),
+        );
+        attributes.insert("COLOR_0".into(), index.into());
+    }
+
+    if !normals.is_empty() {
+        let (min, max) = bounds(normals.iter().copied());
+        let index = push_view(
+            normals
+                .iter()
+                .flat_map(|n| n.iter().flat_map(|v| v.to_le_bytes()))
+                .collect(),
+            None,
+            json!({
+                "componentType": FLOAT,
+                "count": normals.len(),
+                "type": "VEC3",
+                "min": min,
+                "max": max,
+            }),
+        );
+        attributes.insert("NORMAL".into(), index.into());
+    }
+
+    let document = json!({
+        "asset": { "version": "2.0", "generator": "pointrain" },
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "mesh": 0 }],
+        "meshes": [{
+            "primitives": [{ "attributes": attributes, "mode": MODE_POINTS }],
+        }],
+        "buffers": [{ "byteLength": bin.len() }],
+        "bufferViews": buffer_views,
+        "accessors": accessors,
+    });
+
+    let mut json = serde_json::to_vec(&document).map_err(|e| e.to_string())?;
+    json.resize(json.len().next_multiple_of(4), b' ');
+    bin.resize(bin.len().next_multiple_of(4), 0);
+
+    let length = 12 + 8 + json.len() + 8 + bin.len();
+    let mut glb = Vec::with_capacity(length);
+    for v in [GLB_MAGIC, 2, length as u32] {
+        glb.extend(v.to_le_bytes());
+    }
+    glb.extend((json.len() as u32).to_le_bytes());
+    glb.extend(CHUNK_JSON.to_le_bytes());
+    glb.extend(json);
+    glb.extend((bin.len() as u32).to_le_bytes());
+    glb.extend(CHUNK_BIN.to_le_bytes());
+    glb.extend(bin);
+
+    fs::write(f, glb)?;
+
+    Ok(())
+}
+
+fn bounds(values: impl Iterator<Item = nalgebra::Vector3<f32>>) -> ([f32; 3], [f32; 3]) {
+    values.fold(([f32::MAX; 3], [f32::MIN; 3]), |(min, max), v| {
+        (
+            std::array::from_fn(|i| min[i].min(v[i])),
+            std::array::from_fn(|i| max[i].max(v[i])),
+        )
+    })
+}
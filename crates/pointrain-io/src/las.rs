@@ -0,0 +1,3 @@
+mod read;
+
+pub use read::{las_read, las_read_chunks};
@@ -0,0 +1,192 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    point::PointReadable,
+};
+
+const SIGNATURE: &[u8] = b"LASF";
+/// Size of the public header block of LAS 1.0 to 1.2, a prefix of the later ones.
+const HEADER_SIZE: usize = 227;
+/// Offset of the 64-bit number of point records added in LAS 1.4.
+const POINT_COUNT_AT: usize = 247;
+/// Bit of the point data format set by LAZ compressed files.
+const COMPRESSED_FLAG: u8 = 0x80;
+
+struct LasHeader {
+    point_offset: u64,
+    format: u8,
+    record_len: usize,
+    points: u64,
+    scale: [f64; 3],
+    offset: [f64; 3],
+}
+
+impl LasHeader {
+    fn read(reader: &mut impl Read) -> Result<Self, PointRainIOError> {
+        let mut header = vec![0; HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        if &header[..SIGNATURE.len()] != SIGNATURE {
+            return Err("Not a LAS file".to_string().into());
+        }
+
+        let u16_at = |h: &[u8], at: usize| u16::from_le_bytes(h[at..at + 2].try_into().unwrap());
+        let u32_at = |h: &[u8], at: usize| u32::from_le_bytes(h[at..at + 4].try_into().unwrap());
+        let f64_at = |h: &[u8], at: usize| f64::from_le_bytes(h[at..at + 8].try_into().unwrap());
+
+        let header_size = usize::from(u16_at(&header, 94));
+        if header_size < HEADER_SIZE {
+            return Err(format!("Invalid LAS header size: {header_size}").into());
+        }
+        header.resize(header_size, 0);
+        reader.read_exact(&mut header[HEADER_SIZE..])?;
+
+        let format = header[104];
+        if format & COMPRESSED_FLAG != 0 {
+            return Err("Compressed LAZ files are not supported".to_string().into());
+        }
+        let min_record_len = match format {
+            0 => 20,
+            1 => 28,
+            2 => 26,
+            3 => 34,
+            _ => return Err(format!("Unsupported LAS point format: {format}").into()),
+        };
+        let record_len = usize::from(u16_at(&header, 105));
+        if record_len < min_record_len {
+            return Err(
+                format!("Invalid record length {record_len} of point format {format}").into(),
+            );
+        }
+
+        // LAS 1.4 files may leave the legacy 32-bit count zero.
+        let mut points = u64::from(u32_at(&header, 107));
+        if points == 0 && header_size >= POINT_COUNT_AT + 8 {
+            points = u64::from_le_bytes(
+                header[POINT_COUNT_AT..POINT_COUNT_AT + 8]
+                    .try_into()
+                    .unwrap(),
+            );
+        }
+
+        Ok(Self {
+            point_offset: u32_at(&header, 96).into(),
+            format,
+            record_len,
+            points,
+            scale: std::array::from_fn(|a| f64_at(&header, 131 + 8 * a)),
+            offset: std::array::from_fn(|a| f64_at(&header, 155 + 8 * a)),
+        })
+    }
+
+    /// Byte offset of the colors in a record, if the point format has them.
+    fn color_at(&self) -> Option<usize> {
+        match self.format {
+            2 => Some(20),
+            3 => Some(28),
+            _ => None,
+        }
+    }
+
+    fn fields(&self) -> Vec<PointField> {
+        let field = |name: &str, datatype| PointField {
+            name: name.into(),
+            datatype,
+            count: 1,
+        };
+
+        let mut fields: Vec<_> = ["x", "y", "z"]
+            .into_iter()
+            .map(|name| field(name, PointFieldType::F64))
+            .collect();
+        fields.push(field("intensity", PointFieldType::U16));
+        if self.color_at().is_some() {
+            fields.extend(["r", "g", "b"].map(|name| field(name, PointFieldType::U16)));
+        }
+        fields
+    }
+
+    fn decode(&self, record: &[u8], data: &mut Vec<PointFieldDatum>) {
+        let u16_at = |at: usize| u16::from_le_bytes(record[at..at + 2].try_into().unwrap());
+
+        data.extend((0..3).map(|a| {
+            let v = i32::from_le_bytes(record[4 * a..4 * a + 4].try_into().unwrap());
+            PointFieldDatum::F64(f64::from(v) * self.scale[a] + self.offset[a])
+        }));
+        data.push(PointFieldDatum::U16(u16_at(12)));
+        if let Some(at) = self.color_at() {
+            data.extend((0..3).map(|c| PointFieldDatum::U16(u16_at(at + 2 * c))));
+        }
+    }
+}
+
+/// Reads a LAS file of point format 0 to 3.
+///
+/// Positions are scaled and offset as given by the header. `intensity` is read as stored and
+/// the 16-bit colors of formats 2 and 3 are reduced to their high byte. Compressed (LAZ) files
+/// are not supported.
+pub fn las_read<PC>(f: impl AsRef<Path>) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let mut pc = None;
+    las_read_chunks(f, usize::MAX, |chunk| {
+        pc = Some(chunk);
+        Ok(())
+    })?;
+
+    Ok(pc.unwrap_or_else(PC::new))
+}
+
+/// Reads a LAS file like [`las_read`] in chunks of at most `chunk_len` points, so that the
+/// whole cloud is never held in memory. `on_chunk` is called with every chunk in file order.
+pub fn las_read_chunks<PC>(
+    f: impl AsRef<Path>,
+    chunk_len: usize,
+    mut on_chunk: impl FnMut(PC) -> Result<(), PointRainIOError>,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    assert!(chunk_len > 0, "chunk_len must be positive");
+
+    let file = File::open(f)?;
+    let file_len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+    let header = LasHeader::read(&mut reader)?;
+    let func = PC::Point::read_data_func(&header.fields())?;
+
+    // The declared count is only trusted as far as the file can hold it; reading past the
+    // end fails instead.
+    let available = file_len.saturating_sub(header.point_offset) / header.record_len as u64;
+    let capacity = header.points.min(available).min(chunk_len as u64) as usize;
+
+    reader.seek(SeekFrom::Start(header.point_offset))?;
+    let mut pc = PC::with_capacity(capacity);
+    let mut record = vec![0; header.record_len];
+    let mut data = Vec::new();
+    for _ in 0..header.points {
+        reader.read_exact(&mut record)?;
+        data.clear();
+        header.decode(&record, &mut data);
+        pc.push(func(&data)?);
+
+        if pc.len() >= chunk_len {
+            on_chunk(std::mem::replace(&mut pc, PC::with_capacity(capacity)))?;
+        }
+    }
+    if !pc.is_empty() {
+        on_chunk(pc)?;
+    }
+
+    Ok(())
+}
@@ -1,13 +1,82 @@
 mod field;
+pub mod point;
 
-mod xyz;
-pub use xyz::xyz_read;
+pub mod xyz;
+pub use xyz::{
+    xyz_read, xyz_read_with_format, xyz_read_with_options, xyz_write, xyz_write_with_format,
+    XyzFormat,
+};
 
 pub mod pcd;
-pub use pcd::pcd_read;
+pub use pcd::{pcd_read, pcd_read_chunks, pcd_read_with_options};
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "arrow")]
+pub use arrow::{from_record_batch, parquet_read, parquet_write, to_record_batch};
+
+#[cfg(feature = "async")]
+pub mod async_io;
+#[cfg(feature = "async")]
+pub use async_io::{
+    pcd_read_async, pcd_read_async_with_options, pcd_write_async, ply_read_async,
+    ply_read_async_with_options, ply_write_async, xyz_read_async_with_format,
+    xyz_write_async_with_format,
+};
+
+pub mod compression;
+
+#[cfg(feature = "draco")]
+pub mod draco;
+#[cfg(feature = "draco")]
+pub use draco::{draco_decode, draco_encode, draco_read, draco_write, DracoOptions};
+
+pub mod e57;
+pub use e57::{e57_read, e57_read_scans, E57Scan};
+
+pub mod gltf;
+pub use gltf::glb_write;
+
+pub mod las;
+pub use las::{las_read, las_read_chunks};
+
+pub mod npy;
+pub use npy::{npy_read, npy_write, npz_read, npz_write};
+
+pub mod obj;
+pub use obj::{obj_read, obj_write};
+
+pub mod off;
+pub use off::{off_read, off_write};
+
+pub mod potree;
+pub use potree::{potree_convert, potree_convert_las, potree_convert_pcd, PotreeOptions};
 
 pub mod ply;
-pub use ply::ply_read;
+pub use ply::{ply_read, ply_read_with_options};
+
+pub mod ptx;
+pub use ptx::{ptx_read, ptx_write, PtxScan};
+
+pub mod pts;
+pub use pts::{pts_read, pts_write};
+
+pub mod tiles;
+pub use tiles::{pnts_write, tileset_write, TilesetOptions};
+
+pub mod vtk;
+pub use vtk::{vtk_read, vtk_write, VtkEncoding};
+
+pub mod vtp;
+pub use vtp::{vtp_read, vtp_write};
+
+mod lod;
+
+mod mesh;
+pub use mesh::Mesh;
 
 mod error;
 pub use error::PointRainIOError;
+
+mod options;
+pub use options::{InvalidRecordPolicy, ReadMode, ReadOptions, ReadReport, RecordIssue};
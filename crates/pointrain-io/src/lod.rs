@@ -0,0 +1,131 @@
+//! Octree of grid-sampled levels of detail, shared by the streaming formats.
+
+use std::collections::HashSet;
+
+/// How the points are distributed over the nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LodOptions {
+    /// Nodes with at most this many points are not split.
+    pub max_points_per_node: usize,
+    pub max_depth: usize,
+    /// Cells per axis of the sampling grid of every node.
+    pub grid_size: usize,
+}
+
+/// A node of the octree and the indices of the points stored in it.
+///
+/// Each node holds a grid-sampled subset of the points of its cube that are not in any of
+/// its ancestors.
+#[derive(Debug, Default)]
+pub(crate) struct Node {
+    pub level: usize,
+    pub points: Vec<usize>,
+    /// Children indexed by `x << 2 | y << 1 | z`, where a bit is set for the upper half.
+    pub children: [Option<Box<Node>>; 8],
+}
+
+impl Node {
+    pub(crate) fn build(
+        positions: &[[f64; 3]],
+        min: [f64; 3],
+        size: f64,
+        options: &LodOptions,
+    ) -> Self {
+        let indices = (0..positions.len()).collect();
+        Self::build_node(positions, indices, min, size, 0, options)
+    }
+
+    fn build_node(
+        positions: &[[f64; 3]],
+        indices: Vec<usize>,
+        min: [f64; 3],
+        size: f64,
+        level: usize,
+        options: &LodOptions,
+    ) -> Self {
+        if indices.len() <= options.max_points_per_node || level >= options.max_depth {
+            return Self {
+                level,
+                points: indices,
+                ..Default::default()
+            };
+        }
+
+        // Keep the first point of every grid cell, pass the others down.
+        let grid = options.grid_size;
+        let cell = |p: &[f64; 3], axis: usize| {
+            (((p[axis] - min[axis]) / size * grid as f64) as usize).min(grid - 1)
+        };
+        let mut occupied = HashSet::new();
+        let mut points = Vec::new();
+        let mut remaining: [Vec<usize>; 8] = Default::default();
+        for i in indices {
+            let p = &positions[i];
+            if occupied.insert([cell(p, 0), cell(p, 1), cell(p, 2)]) {
+                points.push(i);
+            } else {
+                remaining[child_index(p, min, size)].push(i);
+            }
+        }
+
+        let half = size / 2.;
+        let children = std::array::from_fn(|c| {
+            let indices = std::mem::take(&mut remaining[c]);
+            if indices.is_empty() {
+                return None;
+            }
+            Some(Box::new(Self::build_node(
+                positions,
+                indices,
+                child_min(min, size, c),
+                half,
+                level + 1,
+                options,
+            )))
+        });
+
+        Self {
+            level,
+            points,
+            children,
+        }
+    }
+
+    pub(crate) fn child_mask(&self) -> u8 {
+        self.children
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.is_some())
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    pub(crate) fn is_leaf(&self) -> bool {
+        self.child_mask() == 0
+    }
+
+    /// Nodes breadth first, children in order of their index.
+    pub(crate) fn breadth_first(&self) -> Vec<&Self> {
+        let mut nodes = vec![self];
+        let mut i = 0;
+        while let Some(node) = nodes.get(i) {
+            nodes.extend(node.children.iter().flatten().map(AsRef::as_ref));
+            i += 1;
+        }
+        nodes
+    }
+}
+
+/// Minimum corner of the child `c` of the cube at `min` of `size`.
+pub(crate) fn child_min(min: [f64; 3], size: f64, c: usize) -> [f64; 3] {
+    std::array::from_fn(|axis| {
+        let upper = c & (0b100 >> axis) != 0;
+        min[axis] + if upper { size / 2. } else { 0. }
+    })
+}
+
+fn child_index(p: &[f64; 3], min: [f64; 3], size: f64) -> usize {
+    let half = size / 2.;
+    (0..3).fold(0, |index, axis| {
+        index << 1 | usize::from(p[axis] - min[axis] >= half)
+    })
+}
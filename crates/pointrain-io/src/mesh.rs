@@ -0,0 +1,48 @@
+use crate::field::PointFieldDatum;
+
+/// Vertices with optional polygonal faces indexing into them.
+#[derive(Debug, Default, Clone)]
+pub struct Mesh<PC> {
+    pub vertices: PC,
+    /// Zero-based vertex indices of each face.
+    pub faces: Vec<Vec<usize>>,
+}
+
+/// Mesh formats store colors as either `[0, 1]` floats or `0..=255` integers.
+///
+/// A color is integer only if every channel token parses as one, so that `0 0 1` is a dark
+/// blue and `0 0 1.0` a bright one; all channels are then scaled alike by [`color_channel`].
+pub(crate) fn is_float_color(tokens: &[&str]) -> bool {
+    tokens.iter().any(|t| t.parse::<i64>().is_err())
+}
+
+pub(crate) fn color_channel(v: f64, float: bool) -> PointFieldDatum {
+    let v = if float { v * 255. } else { v };
+    PointFieldDatum::U8(v.round().clamp(0., 255.) as u8)
+}
+
+/// Positions of the scalar, color and normal columns of a point type.
+pub(crate) struct WritableColumns {
+    pub xyz: [usize; 3],
+    pub intensity: Option<usize>,
+    pub rgb: Option<[usize; 3]>,
+    pub alpha: Option<usize>,
+    pub normal: Option<[usize; 3]>,
+    pub curvature: Option<usize>,
+}
+
+impl WritableColumns {
+    pub(crate) fn new(columns: &[&str]) -> Result<Self, String> {
+        let find = |name: &str| columns.iter().position(|c| *c == name);
+        let find3 = |a, b, c| Some([find(a)?, find(b)?, find(c)?]);
+
+        Ok(Self {
+            xyz: find3("x", "y", "z").ok_or("Point type has no position")?,
+            intensity: find("intensity"),
+            rgb: find3("r", "g", "b"),
+            alpha: find("a"),
+            normal: find3("nx", "ny", "nz"),
+            curvature: find("curvature"),
+        })
+    }
+}
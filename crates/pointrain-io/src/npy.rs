@@ -0,0 +1,77 @@
+//! NumPy `.npy` arrays and `.npz` archives of them.
+//!
+//! A `.npy` file holds the positions as an `(N, 3)` array. An `.npz` archive holds one array
+//! per attribute, named after the point cloud accessors (see [`NPZ_ARRAYS`]).
+
+mod read;
+mod write;
+
+pub use read::{npy_read, npz_read};
+pub use write::{npy_write, npz_write};
+
+use crate::field::{PointFieldDatum, PointFieldType};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Names of the arrays of an `.npz` archive and the point columns stored in them.
+pub const NPZ_ARRAYS: &[(&str, &[&str])] = &[
+    ("positions", &["x", "y", "z"]),
+    ("colors", &["r", "g", "b"]),
+    ("alphas", &["a"]),
+    ("normals", &["nx", "ny", "nz"]),
+    ("intensities", &["intensity"]),
+    ("curvatures", &["curvature"]),
+];
+
+/// Colors are stored as `uint8`, everything else as `float32`.
+fn is_color_array(name: &str) -> bool {
+    matches!(name, "colors" | "alphas")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Dtype {
+    datatype: PointFieldType,
+    big_endian: bool,
+}
+
+impl Dtype {
+    /// Parses a `descr` of a numeric array such as `<f4` or `|u1`.
+    fn parse(descr: &str) -> Result<Self, String> {
+        let err = || format!("Unsupported dtype: {descr}");
+
+        let mut chars = descr.chars();
+        let big_endian = match chars.next() {
+            Some('<' | '|' | '=') => false,
+            Some('>') => true,
+            _ => return Err(err()),
+        };
+        let datatype = match chars.as_str() {
+            "u1" => PointFieldType::U8,
+            "u2" => PointFieldType::U16,
+            "u4" => PointFieldType::U32,
+            "i1" => PointFieldType::I8,
+            "i2" => PointFieldType::I16,
+            "i4" => PointFieldType::I32,
+            "f4" => PointFieldType::F32,
+            "f8" => PointFieldType::F64,
+            _ => return Err(err()),
+        };
+
+        Ok(Self {
+            datatype,
+            big_endian,
+        })
+    }
+
+    fn is_float(self) -> bool {
+        matches!(self.datatype, PointFieldType::F32 | PointFieldType::F64)
+    }
+}
+
+/// A C-order array.
+#[derive(Debug, Clone)]
+struct Array {
+    dtype: Dtype,
+    shape: Vec<usize>,
+    data: Vec<PointFieldDatum>,
+}
@@ -0,0 +1,197 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+use pointrain_core::{
+    pc::{PointCloud, PointCloudBase},
+    point::Point,
+    types::Position,
+};
+use zip::{result::ZipError, ZipArchive};
+
+use super::{is_color_array, Array, Dtype, MAGIC, NPZ_ARRAYS};
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    point::PointReadable,
+};
+
+/// Reads positions from an `(N, 3)` floating point array.
+pub fn npy_read(f: impl AsRef<Path>) -> Result<PointCloud, PointRainIOError> {
+    let array = read_array(&mut BufReader::new(File::open(f)?))?;
+    validate("positions", &array, 3)?;
+
+    let mut pc = PointCloud::with_capacity(array.shape[0]);
+    for p in array.data.chunks_exact(3) {
+        pc.push(Point {
+            position: Position::new(p[0].to_float(), p[1].to_float(), p[2].to_float()),
+        });
+    }
+
+    Ok(pc)
+}
+
+/// Reads the arrays of [`super::NPZ_ARRAYS`] present in the archive; other arrays are
+/// ignored.
+///
+/// Colors and alphas must be `uint8`, the other arrays floating point, and all of them must
+/// have the same number of points.
+pub fn npz_read<PC>(f: impl AsRef<Path>) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let mut archive = ZipArchive::new(BufReader::new(File::open(f)?))?;
+
+    let mut fields = Vec::new();
+    let mut arrays = Vec::new();
+    for (name, columns) in NPZ_ARRAYS {
+        let array = match archive.by_name(&format!("{name}.npy")) {
+            Ok(mut file) => read_array(&mut file)?,
+            Err(ZipError::FileNotFound) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        validate(name, &array, columns.len())?;
+
+        fields.extend(columns.iter().map(|c| PointField {
+            name: c.to_string(),
+            datatype: array.dtype.datatype,
+            count: 1,
+        }));
+        arrays.push(array);
+    }
+
+    let n = arrays.first().map_or(0, |a| a.shape[0]);
+    if arrays.iter().any(|a| a.shape[0] != n) {
+        return Err("Arrays have different numbers of points".to_string().into());
+    }
+
+    let func = PC::Point::read_data_func(&fields)?;
+    let mut pc = PC::with_capacity(n);
+    let mut data = Vec::with_capacity(fields.len());
+    for i in 0..n {
+        data.clear();
+        for array in &arrays {
+            let c = array.data.len() / n;
+            data.extend_from_slice(&array.data[c * i..c * i + c]);
+        }
+        pc.push(func(&data)?);
+    }
+
+    Ok(pc)
+}
+
+/// Checks the dtype and that the shape is `(N, columns)` (or `(N,)` for a single column).
+fn validate(name: &str, array: &Array, columns: usize) -> Result<(), PointRainIOError> {
+    let dtype_ok = if is_color_array(name) {
+        array.dtype.datatype == PointFieldType::U8
+    } else {
+        array.dtype.is_float()
+    };
+    if !dtype_ok {
+        return Err(format!("Invalid dtype of {name}: {:?}", array.dtype.datatype).into());
+    }
+
+    let shape_ok = match array.shape[..] {
+        [_] => columns == 1,
+        [_, c] => c == columns,
+        _ => false,
+    };
+    if !shape_ok {
+        return Err(format!("Invalid shape of {name}: {:?}", array.shape).into());
+    }
+
+    Ok(())
+}
+
+fn read_array(reader: &mut impl Read) -> Result<Array, PointRainIOError> {
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic[..MAGIC.len()] != MAGIC {
+        return Err("Not a npy file".to_string().into());
+    }
+
+    let header_len = match magic[MAGIC.len()] {
+        1 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        v => return Err(format!("Unsupported npy version: {v}").into()),
+    };
+    let mut header = vec![0; header_len];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8_lossy(&header);
+
+    let dtype = Dtype::parse(header_value(&header, "descr")?.trim_matches('\''))?;
+    let fortran_order = header_value(&header, "fortran_order")? == "True";
+    let shape = header_value(&header, "shape")?
+        .trim_matches(|c| c == '(' || c == ')')
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    let too_large = || format!("Array shape is too large: {shape:?}");
+    let count = shape
+        .iter()
+        .try_fold(1usize, |count, &d| count.checked_mul(d))
+        .ok_or_else(too_large)?;
+    let len = count
+        .checked_mul(dtype.datatype.bytes())
+        .ok_or_else(too_large)?;
+
+    // Read no more than the file holds rather than allocating the claimed size upfront.
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() < len {
+        return Err(format!("Expected {len} bytes of array data, got {}", bytes.len()).into());
+    }
+
+    let mut bytes = &bytes[..];
+    let mut data: Vec<_> = (0..count)
+        .map(|_| match dtype.big_endian {
+            false => PointFieldDatum::from_bytes_le(&mut bytes, dtype.datatype),
+            true => PointFieldDatum::from_bytes_be(&mut bytes, dtype.datatype),
+        })
+        .collect();
+
+    if fortran_order {
+        if let [rows, cols] = shape[..] {
+            data = (0..rows * cols)
+                .map(|i| data[(i % cols) * rows + i / cols])
+                .collect();
+        }
+    }
+
+    Ok(Array { dtype, shape, data })
+}
+
+/// Extracts the value of a key of the header dictionary, e.g. `(3, 3)` for `shape`.
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, PointRainIOError> {
+    let missing = || format!("Missing {key} in npy header");
+
+    let start = header.find(&format!("'{key}'")).ok_or_else(missing)? + key.len() + 2;
+    let value = header[start..]
+        .trim_start()
+        .strip_prefix(':')
+        .ok_or_else(missing)?
+        .trim_start();
+
+    let end = match value.chars().next() {
+        Some('(') => value.find(')').map(|i| i + 1),
+        Some('\'') => value[1..].find('\'').map(|i| i + 2),
+        _ => value.find([',', '}']),
+    }
+    .ok_or_else(missing)?;
+
+    Ok(value[..end].trim())
+}
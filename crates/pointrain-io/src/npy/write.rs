@@ -0,0 +1,115 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use super::{is_color_array, MAGIC, NPZ_ARRAYS};
+use crate::{error::PointRainIOError, field::PointFieldDatum, point::PointWritable};
+
+/// Writes the positions as an `(N, 3)` `float32` array.
+pub fn npy_write<PC: PointCloudBase>(f: impl AsRef<Path>, pc: &PC) -> Result<(), PointRainIOError> {
+    let data: Vec<_> = pc
+        .positions()
+        .iter()
+        .flat_map(|p| p.iter().map(|v| PointFieldDatum::F32(*v)))
+        .collect();
+
+    let mut writer = BufWriter::new(File::create(f)?);
+    write_array(&mut writer, "<f4", &[pc.len(), 3], &data)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Writes an uncompressed archive with an array for every attribute of the point type.
+pub fn npz_write<PC>(f: impl AsRef<Path>, pc: &PC) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = PC::Point::columns();
+    let arrays: Vec<_> = NPZ_ARRAYS
+        .iter()
+        .filter_map(|(name, names)| {
+            let indices = names
+                .iter()
+                .map(|n| columns.iter().position(|c| c == n))
+                .collect::<Option<Vec<_>>>()?;
+            Some((*name, indices))
+        })
+        .collect();
+
+    let mut values = vec![Vec::new(); arrays.len()];
+    let mut data = Vec::new();
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        for ((name, indices), values) in arrays.iter().zip(&mut values) {
+            for i in indices {
+                values.push(if is_color_array(name) {
+                    PointFieldDatum::U8(data[*i].to_color_channel()?)
+                } else {
+                    PointFieldDatum::F32(data[*i].to_float())
+                });
+            }
+        }
+    }
+
+    let mut zip = ZipWriter::new(BufWriter::new(File::create(f)?));
+    for ((name, indices), values) in arrays.iter().zip(&values) {
+        let descr = if is_color_array(name) { "|u1" } else { "<f4" };
+        let shape = match indices.len() {
+            1 => vec![pc.len()],
+            n => vec![pc.len(), n],
+        };
+
+        let options = FileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(values.len() * 4 >= u32::MAX as usize);
+        zip.start_file(format!("{name}.npy"), options)?;
+        write_array(&mut zip, descr, &shape, values)?;
+    }
+    zip.finish()?.flush()?;
+
+    Ok(())
+}
+
+fn write_array(
+    writer: &mut impl Write,
+    descr: &str,
+    shape: &[usize],
+    data: &[PointFieldDatum],
+) -> Result<(), PointRainIOError> {
+    let shape = match shape {
+        [n] => format!("({n},)"),
+        _ => {
+            let dims: Vec<_> = shape.iter().map(|d| d.to_string()).collect();
+            format!("({})", dims.join(", "))
+        }
+    };
+    let mut header = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}");
+
+    // The data starts at a multiple of 64 bytes, the header ends with a newline.
+    let prefix = MAGIC.len() + 2 + 2;
+    let padding = (64 - (prefix + header.len() + 1) % 64) % 64;
+    header.extend(std::iter::repeat(' ').take(padding));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+
+    let mut bytes = Vec::with_capacity(4 * data.len());
+    for v in data {
+        v.put_le(&mut bytes);
+    }
+    writer.write_all(&bytes)?;
+
+    Ok(())
+}
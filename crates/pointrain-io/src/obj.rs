@@ -0,0 +1,5 @@
+mod read;
+mod write;
+
+pub use read::obj_read;
+pub use write::obj_write;
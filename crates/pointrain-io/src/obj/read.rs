@@ -0,0 +1,143 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    mesh::{color_channel, is_float_color, Mesh},
+    point::PointReadable,
+};
+
+/// Reads `v` (with optional `r g b` vertex colors), `vn` and `f` entries.
+///
+/// Colors are `0..=255` if all three channels are integers, `[0, 1]` floats otherwise.
+///
+/// Normals are assigned to vertices through the `v//vn` references of the faces, or by index if
+/// the file has as many normals as vertices and no references.
+pub fn obj_read<PC>(f: impl AsRef<Path>) -> Result<Mesh<PC>, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let file = File::open(f)?;
+
+    let mut positions = Vec::new();
+    let mut colors = Vec::new();
+    let mut normals = Vec::new();
+    let mut vertex_normals = Vec::new();
+    let mut faces = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("v") => {
+                let tokens: Vec<_> = tokens.collect();
+                let values = tokens
+                    .iter()
+                    .map(|t| t.parse())
+                    .collect::<Result<Vec<f64>, _>>()?;
+                match values.len() {
+                    3 | 4 => colors.push(None),
+                    6 => colors.push(Some((
+                        [values[3], values[4], values[5]],
+                        is_float_color(&tokens[3..]),
+                    ))),
+                    n => {
+                        return Err(format!("Invalid number of values in vertex: {n}").into());
+                    }
+                }
+                positions.push([values[0], values[1], values[2]]);
+                vertex_normals.push(None);
+            }
+            Some("vn") => {
+                let values = tokens.map(str::parse).collect::<Result<Vec<f32>, _>>()?;
+                if values.len() != 3 {
+                    return Err("Invalid number of values in vertex normal"
+                        .to_string()
+                        .into());
+                }
+                normals.push([values[0], values[1], values[2]]);
+            }
+            Some("f") => {
+                let mut face = Vec::new();
+                for token in tokens {
+                    let mut refs = token.split('/');
+                    let v = obj_index(refs.next().unwrap_or_default(), positions.len())?;
+                    if let Some(vn) = refs.nth(1).filter(|vn| !vn.is_empty()) {
+                        vertex_normals[v] = Some(obj_index(vn, normals.len())?);
+                    }
+                    face.push(v);
+                }
+                faces.push(face);
+            }
+            _ => {}
+        }
+    }
+
+    if normals.len() == positions.len() && vertex_normals.iter().all(Option::is_none) {
+        vertex_normals = (0..normals.len()).map(Some).collect();
+    }
+
+    let has_colors = colors.iter().any(Option::is_some);
+    let has_normals = vertex_normals.iter().any(Option::is_some);
+
+    let mut names = vec!["x", "y", "z"];
+    if has_colors {
+        names.extend(["r", "g", "b"]);
+    }
+    if has_normals {
+        names.extend(["nx", "ny", "nz"]);
+    }
+    let fields: Vec<_> = names
+        .into_iter()
+        .map(|name| PointField {
+            name: name.into(),
+            datatype: PointFieldType::F64,
+            count: 1,
+        })
+        .collect();
+    let func = PC::Point::read_data_func(&fields)?;
+
+    let mut pc = PC::with_capacity(positions.len());
+    let mut data = Vec::with_capacity(fields.len());
+    for (i, position) in positions.iter().enumerate() {
+        data.clear();
+        data.extend(position.iter().map(|v| PointFieldDatum::F64(*v)));
+        if has_colors {
+            let (color, float) = colors[i].unwrap_or(([1., 1., 1.], true));
+            data.extend(color.iter().map(|c| color_channel(*c, float)));
+        }
+        if has_normals {
+            let normal = vertex_normals[i].map_or([0.; 3], |n| normals[n]);
+            data.extend(normal.iter().map(|n| PointFieldDatum::F32(*n)));
+        }
+        pc.push(func(&data)?);
+    }
+
+    Ok(Mesh {
+        vertices: pc,
+        faces,
+    })
+}
+
+/// Converts a one-based (or negative, relative) OBJ index.
+fn obj_index(token: &str, len: usize) -> Result<usize, PointRainIOError> {
+    let index: isize = token.parse()?;
+    let resolved = match index {
+        1.. => index - 1,
+        ..=-1 => len as isize + index,
+        0 => -1,
+    };
+
+    usize::try_from(resolved)
+        .ok()
+        .filter(|i| *i < len)
+        .ok_or_else(|| format!("Index out of range: {index}").into())
+}
@@ -0,0 +1,47 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{error::PointRainIOError, mesh::WritableColumns, point::PointWritable};
+
+/// Writes the points as `v` (with colors if the point type has them) and `vn` entries.
+pub fn obj_write<PC>(f: impl AsRef<Path>, pc: &PC) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = WritableColumns::new(PC::Point::columns())?;
+    let mut writer = BufWriter::new(File::create(f)?);
+    let mut data = Vec::new();
+
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        let [x, y, z] = columns.xyz;
+        write!(writer, "v {} {} {}", data[x], data[y], data[z])?;
+        if let Some(rgb) = columns.rgb {
+            for c in rgb {
+                // Always with a decimal point, so that the color reads back as `[0, 1]` floats.
+                write!(writer, " {:?}", data[c].to_float() / 255.)?;
+            }
+        }
+        writeln!(writer)?;
+    }
+
+    if let Some([nx, ny, nz]) = columns.normal {
+        for p in pc.iter() {
+            data.clear();
+            PC::Point::write_data(&p, &mut data);
+            writeln!(writer, "vn {} {} {}", data[nx], data[ny], data[nz])?;
+        }
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
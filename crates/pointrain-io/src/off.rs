@@ -0,0 +1,5 @@
+mod read;
+mod write;
+
+pub use read::off_read;
+pub use write::off_write;
@@ -0,0 +1,142 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    mesh::{color_channel, is_float_color, Mesh},
+    point::PointReadable,
+};
+
+/// Reads `OFF`, `COFF`, `NOFF` and `CNOFF` files.
+///
+/// Vertex lines are `x y z [nx ny nz] [r g b [a]]`.
+pub fn off_read<PC>(f: impl AsRef<Path>) -> Result<Mesh<PC>, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let file = File::open(f)?;
+
+    let mut lines = BufReader::new(file).lines().filter(|line| match line {
+        Ok(line) => {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        }
+        Err(_) => true,
+    });
+    let mut next_line = || -> Result<String, PointRainIOError> {
+        Ok(lines
+            .next()
+            .ok_or_else(|| "Unexpected end of file".to_string())??)
+    };
+
+    let first = next_line()?;
+    let mut tokens = first.split_whitespace();
+    let keyword = tokens.next().unwrap_or_default();
+    let prefix = keyword
+        .strip_suffix("OFF")
+        .ok_or_else(|| format!("Unknown keyword: {keyword}"))?;
+    let has_normals = prefix.contains('N');
+    let has_colors = prefix.contains('C');
+
+    // The counts may follow the keyword on the same line.
+    let mut counts: Vec<usize> = tokens.map(str::parse).collect::<Result<_, _>>()?;
+    if counts.is_empty() {
+        counts = next_line()?
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+    }
+    let (num_vertices, num_faces) = match counts[..] {
+        [v, f, ..] => (v, f),
+        _ => return Err("Invalid vertex/face counts".to_string().into()),
+    };
+
+    let mut names = vec!["x", "y", "z"];
+    if has_normals {
+        names.extend(["nx", "ny", "nz"]);
+    }
+    if has_colors {
+        names.extend(["r", "g", "b", "a"]);
+    }
+    let fields: Vec<_> = names
+        .into_iter()
+        .map(|name| PointField {
+            name: name.into(),
+            datatype: PointFieldType::F64,
+            count: 1,
+        })
+        .collect();
+    let func = PC::Point::read_data_func(&fields)?;
+
+    let mut pc = PC::with_capacity(num_vertices);
+    let mut data = Vec::with_capacity(fields.len());
+    for _ in 0..num_vertices {
+        let line = next_line()?;
+        let tokens: Vec<_> = line.split_whitespace().collect();
+        let values = tokens
+            .iter()
+            .map(|t| t.parse())
+            .collect::<Result<Vec<f64>, _>>()?;
+
+        let colors_at = if has_normals { 6 } else { 3 };
+        if values.len() < colors_at + if has_colors { 3 } else { 0 } {
+            return Err("Invalid number of values in vertex".to_string().into());
+        }
+
+        data.clear();
+        data.extend(values[..colors_at].iter().map(|v| PointFieldDatum::F64(*v)));
+        if has_colors {
+            let float = is_float_color(&tokens[colors_at..]);
+            data.extend(
+                values[colors_at..colors_at + 3]
+                    .iter()
+                    .map(|c| color_channel(*c, float)),
+            );
+            data.push(
+                values
+                    .get(colors_at + 3)
+                    .map_or(PointFieldDatum::U8(u8::MAX), |a| color_channel(*a, float)),
+            );
+        }
+        pc.push(func(&data)?);
+    }
+
+    let mut faces = Vec::with_capacity(num_faces);
+    for _ in 0..num_faces {
+        let line = next_line()?;
+        let mut tokens = line.split_whitespace();
+
+        let n: usize = tokens
+            .next()
+            .ok_or_else(|| "Empty face".to_string())?
+            .parse()?;
+        let face = tokens
+            .by_ref()
+            .take(n)
+            .map(str::parse)
+            .collect::<Result<Vec<usize>, _>>()?;
+        if face.len() != n {
+            return Err("Invalid number of indices in face".to_string().into());
+        }
+        if let Some(i) = face.iter().find(|i| **i >= num_vertices) {
+            return Err(format!("Index out of range: {i}").into());
+        }
+        // Anything after the indices is a face color, integer or float.
+        for token in tokens {
+            token.parse::<f64>()?;
+        }
+        faces.push(face);
+    }
+
+    Ok(Mesh {
+        vertices: pc,
+        faces,
+    })
+}
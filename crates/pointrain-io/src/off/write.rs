@@ -0,0 +1,49 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{error::PointRainIOError, mesh::WritableColumns, point::PointWritable};
+
+/// Writes the points without faces, as `COFF`/`NOFF`/`CNOFF` if the point type has colors
+/// or normals.
+pub fn off_write<PC>(f: impl AsRef<Path>, pc: &PC) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = WritableColumns::new(PC::Point::columns())?;
+    let mut writer = BufWriter::new(File::create(f)?);
+    let mut data = Vec::new();
+
+    let color_prefix = if columns.rgb.is_some() { "C" } else { "" };
+    let normal_prefix = if columns.normal.is_some() { "N" } else { "" };
+    writeln!(writer, "{color_prefix}{normal_prefix}OFF")?;
+    writeln!(writer, "{} 0 0", pc.len())?;
+
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        let [x, y, z] = columns.xyz;
+        write!(writer, "{} {} {}", data[x], data[y], data[z])?;
+        if let Some([nx, ny, nz]) = columns.normal {
+            write!(writer, " {} {} {}", data[nx], data[ny], data[nz])?;
+        }
+        if let Some([r, g, b]) = columns.rgb {
+            write!(writer, " {} {} {}", data[r], data[g], data[b])?;
+            match columns.alpha {
+                Some(a) => write!(writer, " {}", data[a])?,
+                None => write!(writer, " 255")?,
+            }
+        }
+        writeln!(writer)?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
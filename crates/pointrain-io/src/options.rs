@@ -0,0 +1,117 @@
+use pointrain_core::{pc::PointCloudBase, point::PointBase, types::Position};
+
+use crate::error::PointRainIOError;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReadMode {
+    /// Reject the whole file on the first malformed record or count mismatch.
+    #[default]
+    Strict,
+    /// Repair what can be repaired and describe it in the [`ReadReport`].
+    Lenient,
+}
+
+/// What to do with a record that cannot be parsed in [`ReadMode::Lenient`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidRecordPolicy {
+    /// Drop the record.
+    #[default]
+    Skip,
+    /// Keep a point with NaN position (other attributes are default) so that
+    /// the index of every following point stays unchanged.
+    FillNan,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReadOptions {
+    pub mode: ReadMode,
+    pub invalid_record: InvalidRecordPolicy,
+}
+
+impl ReadOptions {
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    pub fn lenient() -> Self {
+        Self {
+            mode: ReadMode::Lenient,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_invalid_record(mut self, policy: InvalidRecordPolicy) -> Self {
+        self.invalid_record = policy;
+        self
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.mode == ReadMode::Strict
+    }
+
+    /// Returns `err` in strict mode, otherwise applies the invalid record
+    /// policy and records it in `report`.
+    pub(crate) fn recover<PC: PointCloudBase>(
+        &self,
+        report: &mut ReadReport,
+        pc: &mut PC,
+        index: usize,
+        err: PointRainIOError,
+    ) -> Result<(), PointRainIOError> {
+        if self.is_strict() {
+            return Err(err);
+        }
+
+        let issue = RecordIssue {
+            index,
+            reason: err.to_string(),
+        };
+
+        match self.invalid_record {
+            InvalidRecordPolicy::Skip => report.skipped.push(issue),
+            InvalidRecordPolicy::FillNan => {
+                let mut p = PC::Point::default();
+                *p.position_mut() = Position::new(f32::NAN, f32::NAN, f32::NAN);
+                pc.push(p);
+                report.nan_filled.push(issue);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordIssue {
+    /// Zero-based index of the record in the data section.
+    pub index: usize,
+    pub reason: String,
+}
+
+/// What a reader had to repair to load a file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReadReport {
+    pub skipped: Vec<RecordIssue>,
+    pub nan_filled: Vec<RecordIssue>,
+    pub blank_lines: usize,
+    /// Bytes at the end of a binary data section too short for a whole record.
+    pub trailing_bytes: usize,
+    /// Number of points declared in the header, if the format has one.
+    pub declared_points: Option<usize>,
+    pub read_points: usize,
+}
+
+impl ReadReport {
+    pub fn count_mismatch(&self) -> bool {
+        self.declared_points
+            .is_some_and(|declared| declared != self.read_points)
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.skipped.is_empty()
+            && self.nan_filled.is_empty()
+            && self.blank_lines == 0
+            && self.trailing_bytes == 0
+            && !self.count_mismatch()
+    }
+}
@@ -1,4 +1,6 @@
 pub mod point;
-mod read;
+pub(crate) mod read;
+#[cfg(feature = "async")]
+pub(crate) mod write;
 
-pub use read::pcd_read;
+pub use read::{pcd_read, pcd_read_chunks, pcd_read_with_options};
@@ -1,20 +1,26 @@
 use pointrain_core::{
-    point::{Point, PointIntensity, PointIntensityNormal, PointNormal, PointRgb, PointRgbNormal},
+    point::{
+        Point, PointDescriptor, PointIntensity, PointIntensityNormal, PointNormal, PointRgb,
+        PointRgbNormal, PointRgba,
+    },
     types::{Normal, Position},
 };
 
 use crate::{
     error::{MissingField, PointRainIOError},
-    field::{PointField, PointFieldDatum},
+    field::{ColorField, PointField, PointFieldDatum},
 };
 
+/// Returns the offset of the first datum of `name` in a record.
 fn find_field(fields: &[PointField], name: &'static str) -> Result<usize, MissingField> {
-    fields
-        .iter()
-        .enumerate()
-        .find(|(_, f)| f.name == name)
-        .map(|(i, _)| i)
-        .ok_or(MissingField(name))
+    let mut offset = 0;
+    for field in fields {
+        if field.name == name {
+            return Ok(offset);
+        }
+        offset += field.count;
+    }
+    Err(MissingField(name))
 }
 
 fn find_xyz(fields: &[PointField]) -> Result<(usize, usize, usize), MissingField> {
@@ -38,8 +44,40 @@ fn find_intensity(fields: &[PointField]) -> Result<usize, MissingField> {
     find_field(fields, "intensity")
 }
 
-fn find_rgb(fields: &[PointField]) -> Result<usize, MissingField> {
-    find_field(fields, "rgb")
+fn find_rgb(fields: &[PointField]) -> Result<ColorField, MissingField> {
+    if let Ok(rgb) = find_field(fields, "rgb") {
+        return Ok(ColorField::Packed(rgb));
+    }
+    if let Ok(rgba) = find_field(fields, "rgba") {
+        return Ok(ColorField::PackedAlpha(rgba));
+    }
+
+    Ok(ColorField::Channels {
+        r: find_field(fields, "r").map_err(|_| MissingField("rgb"))?,
+        g: find_field(fields, "g").map_err(|_| MissingField("rgb"))?,
+        b: find_field(fields, "b").map_err(|_| MissingField("rgb"))?,
+        a: find_field(fields, "a").ok(),
+    })
+}
+
+const DESCRIPTOR_NAMES: [&str; 6] = ["fpfh", "shot", "descriptor", "pfh", "vfh", "histogram"];
+
+/// Finds a field with `count` elements, preferring the names used by PCL's feature types.
+fn find_descriptor(fields: &[PointField], count: usize) -> Result<usize, MissingField> {
+    let mut offset = 0;
+    let mut fallback = None;
+    for field in fields {
+        if field.count == count {
+            if DESCRIPTOR_NAMES.contains(&field.name.as_str()) {
+                return Ok(offset);
+            }
+            if count > 1 {
+                fallback.get_or_insert(offset);
+            }
+        }
+        offset += field.count;
+    }
+    fallback.ok_or(MissingField("descriptor"))
 }
 
 pub type PointMapper<T> = Box<dyn Fn(&[PointFieldDatum]) -> Result<T, PointRainIOError>>;
@@ -130,7 +168,7 @@ impl PointReadable for PointRgb {
         let closure = move |data: &[PointFieldDatum]| {
             Ok(Self {
                 position: Position::new(data[x].to_float(), data[y].to_float(), data[z].to_float()),
-                color: data[rgb].to_color()?,
+                color: rgb.read_rgb(data)?,
             })
         };
 
@@ -147,7 +185,7 @@ impl PointReadable for PointRgbNormal {
         let closure = move |data: &[PointFieldDatum]| {
             Ok(Self {
                 position: Position::new(data[x].to_float(), data[y].to_float(), data[z].to_float()),
-                color: data[rgb].to_color()?,
+                color: rgb.read_rgb(data)?,
                 normal: Normal::new(
                     data[nx].to_float(),
                     data[ny].to_float(),
@@ -160,3 +198,43 @@ impl PointReadable for PointRgbNormal {
         Ok(Box::new(closure))
     }
 }
+
+impl PointReadable for PointRgba {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
+        let (x, y, z) = find_xyz(fields)?;
+        let rgb = find_rgb(fields)?;
+
+        let closure = move |data: &[PointFieldDatum]| {
+            let (color, alpha) = rgb.read(data)?;
+            Ok(Self {
+                position: Position::new(data[x].to_float(), data[y].to_float(), data[z].to_float()),
+                color,
+                alpha,
+            })
+        };
+
+        Ok(Box::new(closure))
+    }
+}
+
+/// Files without `x`, `y` and `z` (e.g. PCL's `FPFHSignature33`) give NaN positions.
+impl<const N: usize> PointReadable for PointDescriptor<N> {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
+        let xyz = find_xyz(fields).ok();
+        let descriptor = find_descriptor(fields, N)?;
+
+        let closure = move |data: &[PointFieldDatum]| {
+            Ok(Self {
+                position: match xyz {
+                    Some((x, y, z)) => {
+                        Position::new(data[x].to_float(), data[y].to_float(), data[z].to_float())
+                    }
+                    None => Position::new(f32::NAN, f32::NAN, f32::NAN),
+                },
+                descriptor: std::array::from_fn(|i| data[descriptor + i].to_float()),
+            })
+        };
+
+        Ok(Box::new(closure))
+    }
+}
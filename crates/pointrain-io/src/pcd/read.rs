@@ -10,7 +10,7 @@ use pointrain_core::pc::PointCloudBase;
 use super::point::PointReadable;
 use crate::{
     field::{PointField, PointFieldDatum, PointFieldType},
-    PointRainIOError,
+    PointRainIOError, ReadOptions, ReadReport,
 };
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -42,6 +42,7 @@ struct PcdHeader {
     fields: Vec<PointField>,
     width: usize,
     height: usize,
+    points: usize,
     origin: Vector3<f32>,
     orientation: Quaternion<f32>,
 }
@@ -52,14 +53,37 @@ where
     PC: PointCloudBase,
     PC::Point: PointReadable,
 {
-    let file = File::open(f)?;
+    pcd_read_with_options(f, &ReadOptions::default()).map(|(pc, _)| pc)
+}
 
-    let (header, mut reader) = pcd_read_header(&file)?;
-    pcd_read_data::<PC>(&header, &mut reader)
+pub fn pcd_read_with_options<PC>(
+    f: impl AsRef<Path>,
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    pcd_read_from(BufReader::new(File::open(f)?), options)
 }
 
-fn pcd_read_header(file: &File) -> Result<(PcdHeader, BufReader<&File>), PointRainIOError> {
-    let mut reader = BufReader::new(file);
+/// Reads a PCD stream, shared by the file and the async readers.
+pub(crate) fn pcd_read_from<PC>(
+    mut reader: impl BufRead,
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let header = pcd_read_header(&mut reader, options)?;
+    pcd_read_data::<PC>(&header, reader, options)
+}
+
+fn pcd_read_header(
+    reader: &mut impl BufRead,
+    options: &ReadOptions,
+) -> Result<PcdHeader, PointRainIOError> {
     let mut line = String::new();
     let mut header = PcdHeader::default();
     let mut field_sizes = Vec::new();
@@ -173,13 +197,14 @@ fn pcd_read_header(file: &File) -> Result<(PcdHeader, BufReader<&File>), PointRa
             }
             "POINTS" => {
                 let size = tokens[1].parse()?;
+                header.points = size;
 
                 if header.width == 0 && header.height == 0 {
                     header.width = size;
                     header.height = 1;
                 }
 
-                if size != header.width * header.height {
+                if size != header.width * header.height && options.is_strict() {
                     return Err(PointRainIOError::Error {
                         msg: format!(
                             "[POINTS] HEIGHT ({}) x WIDTH ({}) != number of points ({size})",
@@ -213,38 +238,137 @@ fn pcd_read_header(file: &File) -> Result<(PcdHeader, BufReader<&File>), PointRa
         header.height = 1;
     }
 
-    Ok((header, reader))
+    if header.points == 0 {
+        header.points = header.width * header.height;
+    }
+
+    Ok(header)
+}
+
+/// Reads a PCD file in chunks of at most `chunk_len` points, so that the whole cloud is never
+/// held in memory. `on_chunk` is called with every chunk in file order.
+pub fn pcd_read_chunks<PC>(
+    f: impl AsRef<Path>,
+    chunk_len: usize,
+    options: &ReadOptions,
+    on_chunk: impl FnMut(PC) -> Result<(), PointRainIOError>,
+) -> Result<ReadReport, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    assert!(chunk_len > 0, "chunk_len must be positive");
+
+    let mut reader = BufReader::new(File::open(f)?);
+    let header = pcd_read_header(&mut reader, options)?;
+    pcd_read_data_chunks(&header, reader, options, chunk_len, on_chunk)
 }
 
 fn pcd_read_data<PC>(
     header: &PcdHeader,
-    reader: &mut BufReader<&File>,
-) -> Result<PC, PointRainIOError>
+    reader: impl BufRead,
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
 where
     PC: PointCloudBase,
     PC::Point: PointReadable,
 {
-    let mut pc = PC::with_capacity(header.width * header.height);
+    let mut pc = None;
+    let report = pcd_read_data_chunks(header, reader, options, usize::MAX, |chunk| {
+        pc = Some(chunk);
+        Ok(())
+    })?;
+
+    Ok((pc.unwrap_or_else(PC::new), report))
+}
+
+fn pcd_read_data_chunks<PC>(
+    header: &PcdHeader,
+    mut reader: impl BufRead,
+    options: &ReadOptions,
+    chunk_len: usize,
+    mut on_chunk: impl FnMut(PC) -> Result<(), PointRainIOError>,
+) -> Result<ReadReport, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let capacity = header.points.min(chunk_len);
+    let mut pc = PC::with_capacity(capacity);
+    let mut report = ReadReport {
+        declared_points: Some(header.points),
+        ..Default::default()
+    };
     let func = PC::Point::read_data_func(&header.fields)?;
 
+    let mut flush = |pc: &mut PC, report: &mut ReadReport, force: bool| {
+        if pc.len() >= chunk_len || (force && !pc.is_empty()) {
+            report.read_points += pc.len();
+            let capacity = if force { 0 } else { capacity };
+            on_chunk(std::mem::replace(pc, PC::with_capacity(capacity)))?;
+        }
+        Ok::<_, PointRainIOError>(())
+    };
+
     match header.format {
         PcdDataFormat::Ascii => {
+            let mut index = 0;
             for line in reader.lines() {
-                let data = pcd_read_ascii_datum(header, line?.as_str())?;
-                pc.push(func(&data)?);
+                let line = line?;
+                if line.trim().is_empty() && !options.is_strict() {
+                    report.blank_lines += 1;
+                    continue;
+                }
+
+                match pcd_read_ascii_datum(header, line.as_str()).and_then(|data| func(&data)) {
+                    Ok(p) => {
+                        pc.push(p);
+                    }
+                    Err(e) => options.recover(&mut report, &mut pc, index, e)?,
+                }
+                index += 1;
+                flush(&mut pc, &mut report, false)?;
             }
         }
         PcdDataFormat::Binary => {
-            let chunk_size = header.fields.iter().map(PointField::bytes).sum();
-            let mut chunk = vec![0; chunk_size];
-            while reader.read_exact(&mut chunk).is_ok() {
-                let data = pcd_read_binary_datum(header, &mut chunk.as_slice());
-                pc.push(func(&data)?);
-            }
-            if !reader.fill_buf()?.is_empty() {
-                return Err(PointRainIOError::Error {
-                    msg: "extra data remains".into(),
-                });
+            let chunk_size: usize = header.fields.iter().map(PointField::bytes).sum();
+            let read_size = chunk_size * chunk_len.min(usize::MAX / chunk_size.max(1));
+            let mut data = Vec::new();
+            let mut index = 0;
+
+            loop {
+                data.clear();
+                (&mut reader)
+                    .take(read_size as u64)
+                    .read_to_end(&mut data)?;
+
+                let mut chunks = data.chunks_exact(chunk_size);
+                for mut chunk in &mut chunks {
+                    let data = pcd_read_binary_datum(header, &mut chunk);
+                    match func(&data) {
+                        Ok(p) => {
+                            pc.push(p);
+                        }
+                        Err(e) => options.recover(&mut report, &mut pc, index, e)?,
+                    }
+                    index += 1;
+                }
+
+                // Reads are whole records, so only the last one can be short.
+                let remains = chunks.remainder().len();
+                if remains > 0 {
+                    if options.is_strict() {
+                        return Err(PointRainIOError::Error {
+                            msg: "extra data remains".into(),
+                        });
+                    }
+                    report.trailing_bytes = remains;
+                }
+                flush(&mut pc, &mut report, false)?;
+
+                if data.len() < read_size {
+                    break;
+                }
             }
         }
         PcdDataFormat::BinaryCompressed => {
@@ -252,11 +376,13 @@ where
         }
     }
 
-    if pc.len() != header.width * header.height {
+    flush(&mut pc, &mut report, true)?;
+
+    if report.read_points != header.points && options.is_strict() {
         return Err(PointRainIOError::Error {
             msg: format!(
                 "The number of points ({}) does not match the number of points specified in the header ({} x {} = {})",
-                pc.len(),
+                report.read_points,
                 header.width,
                 header.height,
                 header.width * header.height
@@ -265,7 +391,7 @@ where
         });
     }
 
-    Ok(pc)
+    Ok(report)
 }
 
 fn pcd_read_ascii_datum(
@@ -273,29 +399,35 @@ fn pcd_read_ascii_datum(
     line: &str,
 ) -> Result<Vec<PointFieldDatum>, PointRainIOError> {
     let tokens: Vec<_> = line.trim().split(&[' ', '\t', '\r']).collect();
+    let expected = header.fields.iter().map(|field| field.count).sum::<usize>();
 
-    if tokens.len() != header.fields.len() {
+    if tokens.len() != expected {
         return Err(PointRainIOError::Error {
             msg: format!(
                 "Invalid number of tokens: expected {}, got {}",
-                header.fields.len(),
+                expected,
                 tokens.len()
             )
             .into(),
         });
     }
 
-    Ok(tokens
-        .iter()
-        .zip(header.fields.iter())
-        .map(|(token, field)| PointFieldDatum::parse(token, field.datatype))
-        .collect::<Result<_, _>>()?)
+    let mut tokens = tokens.iter();
+    let mut data = Vec::with_capacity(expected);
+    for field in &header.fields {
+        for token in tokens.by_ref().take(field.count) {
+            data.push(PointFieldDatum::parse(token, field.datatype)?);
+        }
+    }
+
+    Ok(data)
 }
 
 fn pcd_read_binary_datum(header: &PcdHeader, chunk: &mut &[u8]) -> Vec<PointFieldDatum> {
     header
         .fields
         .iter()
-        .map(|field| PointFieldDatum::from_bytes_le(chunk, field.datatype))
+        .flat_map(|field| std::iter::repeat(field.datatype).take(field.count))
+        .map(|datatype| PointFieldDatum::from_bytes_le(chunk, datatype))
         .collect()
 }
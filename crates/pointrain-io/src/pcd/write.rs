@@ -0,0 +1,102 @@
+use std::io::Write;
+
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{error::PointRainIOError, mesh::WritableColumns, point::PointWritable};
+
+/// A PCD field and the point columns it is made of.
+enum PcdField {
+    Float(&'static str, usize),
+    /// Channels packed into `0xAARRGGBB`, alpha is opaque if missing.
+    Packed(&'static str, [usize; 3], Option<usize>),
+}
+
+/// Writes a binary PCD stream.
+pub(crate) fn pcd_write_to<PC>(writer: &mut impl Write, pc: &PC) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = WritableColumns::new(PC::Point::columns())?;
+
+    let [x, y, z] = columns.xyz;
+    let mut fields = vec![
+        PcdField::Float("x", x),
+        PcdField::Float("y", y),
+        PcdField::Float("z", z),
+    ];
+    if let Some(i) = columns.intensity {
+        fields.push(PcdField::Float("intensity", i));
+    }
+    if let Some(rgb) = columns.rgb {
+        let name = if columns.alpha.is_some() {
+            "rgba"
+        } else {
+            "rgb"
+        };
+        fields.push(PcdField::Packed(name, rgb, columns.alpha));
+    }
+    if let Some([nx, ny, nz]) = columns.normal {
+        fields.extend([
+            PcdField::Float("normal_x", nx),
+            PcdField::Float("normal_y", ny),
+            PcdField::Float("normal_z", nz),
+        ]);
+        if let Some(c) = columns.curvature {
+            fields.push(PcdField::Float("curvature", c));
+        }
+    }
+
+    let line = |f: &dyn Fn(&PcdField) -> &str| fields.iter().map(f).collect::<Vec<_>>().join(" ");
+    writeln!(writer, "# .PCD v0.7 - Point Cloud Data file format")?;
+    writeln!(writer, "VERSION 0.7")?;
+    writeln!(
+        writer,
+        "FIELDS {}",
+        line(&|f| match f {
+            PcdField::Float(name, _) | PcdField::Packed(name, _, _) => name,
+        })
+    )?;
+    writeln!(writer, "SIZE {}", line(&|_| "4"))?;
+    writeln!(
+        writer,
+        "TYPE {}",
+        line(&|f| match f {
+            PcdField::Float(..) => "F",
+            PcdField::Packed(..) => "U",
+        })
+    )?;
+    writeln!(writer, "COUNT {}", line(&|_| "1"))?;
+    writeln!(writer, "WIDTH {}", pc.len())?;
+    writeln!(writer, "HEIGHT 1")?;
+    writeln!(writer, "VIEWPOINT 0 0 0 1 0 0 0")?;
+    writeln!(writer, "POINTS {}", pc.len())?;
+    writeln!(writer, "DATA binary")?;
+
+    let mut data = Vec::new();
+    let mut record = Vec::with_capacity(4 * fields.len());
+    for p in pc.iter() {
+        data.clear();
+        record.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        for field in &fields {
+            match *field {
+                PcdField::Float(_, i) => record.extend(data[i].to_float().to_le_bytes()),
+                PcdField::Packed(_, [r, g, b], a) => {
+                    let a = a.map_or(Ok(u8::MAX), |a| data[a].to_color_channel())?;
+                    let packed = [
+                        a,
+                        data[r].to_color_channel()?,
+                        data[g].to_color_channel()?,
+                        data[b].to_color_channel()?,
+                    ];
+                    record.extend(u32::from_be_bytes(packed).to_le_bytes());
+                }
+            }
+        }
+        writer.write_all(&record)?;
+    }
+
+    Ok(())
+}
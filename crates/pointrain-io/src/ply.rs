@@ -1,4 +1,6 @@
 pub mod point;
-mod read;
+pub(crate) mod read;
+#[cfg(feature = "async")]
+pub(crate) mod write;
 
-pub use read::ply_read;
+pub use read::{ply_read, ply_read_with_options};
@@ -1,11 +1,11 @@
 use pointrain_core::{
-    point::{Point, PointNormal, PointRgb, PointRgbNormal},
-    types::{Normal, Position, Rgb},
+    point::{Point, PointNormal, PointRgb, PointRgbNormal, PointRgba},
+    types::{Normal, Position},
 };
 
 use crate::{
     error::{MissingField, PointRainIOError},
-    field::{PointField, PointFieldDatum},
+    field::{ColorField, PointField, PointFieldDatum},
 };
 
 fn find_field(fields: &[PointField], name: &'static str) -> Result<usize, MissingField> {
@@ -34,12 +34,22 @@ fn find_normal(fields: &[PointField]) -> Result<(usize, usize, usize, usize), Mi
     ))
 }
 
-fn find_rgb(fields: &[PointField]) -> Result<(usize, usize, usize), MissingField> {
-    Ok((
-        find_field(fields, "red")?,
-        find_field(fields, "green")?,
-        find_field(fields, "blue")?,
-    ))
+fn find_rgb(fields: &[PointField]) -> Result<ColorField, MissingField> {
+    if let Ok(r) = find_field(fields, "red") {
+        return Ok(ColorField::Channels {
+            r,
+            g: find_field(fields, "green")?,
+            b: find_field(fields, "blue")?,
+            a: find_field(fields, "alpha").ok(),
+        });
+    }
+
+    Ok(ColorField::Channels {
+        r: find_field(fields, "diffuse_red").map_err(|_| MissingField("red"))?,
+        g: find_field(fields, "diffuse_green")?,
+        b: find_field(fields, "diffuse_blue")?,
+        a: find_field(fields, "diffuse_alpha").ok(),
+    })
 }
 
 pub type PointMapper<T> = Box<dyn Fn(&[PointFieldDatum]) -> Result<T, PointRainIOError>>;
@@ -86,12 +96,12 @@ impl PointReadable for PointNormal {
 impl PointReadable for PointRgb {
     fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
         let (x, y, z) = find_xyz(fields)?;
-        let (r, g, b) = find_rgb(fields)?;
+        let rgb = find_rgb(fields)?;
 
         let closure = move |data: &[PointFieldDatum]| {
             Ok(Self {
                 position: Position::new(data[x].to_float(), data[y].to_float(), data[z].to_float()),
-                color: Rgb::new(data[r].as_u8()?, data[g].as_u8()?, data[b].as_u8()?),
+                color: rgb.read_rgb(data)?,
             })
         };
 
@@ -102,13 +112,13 @@ impl PointReadable for PointRgb {
 impl PointReadable for PointRgbNormal {
     fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
         let (x, y, z) = find_xyz(fields)?;
-        let (r, g, b) = find_rgb(fields)?;
+        let rgb = find_rgb(fields)?;
         let (nx, ny, nz, curvature) = find_normal(fields)?;
 
         let closure = move |data: &[PointFieldDatum]| {
             Ok(Self {
                 position: Position::new(data[x].to_float(), data[y].to_float(), data[z].to_float()),
-                color: Rgb::new(data[r].as_u8()?, data[g].as_u8()?, data[b].as_u8()?),
+                color: rgb.read_rgb(data)?,
                 normal: Normal::new(
                     data[nx].to_float(),
                     data[ny].to_float(),
@@ -121,3 +131,21 @@ impl PointReadable for PointRgbNormal {
         Ok(Box::new(closure))
     }
 }
+
+impl PointReadable for PointRgba {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
+        let (x, y, z) = find_xyz(fields)?;
+        let rgb = find_rgb(fields)?;
+
+        let closure = move |data: &[PointFieldDatum]| {
+            let (color, alpha) = rgb.read(data)?;
+            Ok(Self {
+                position: Position::new(data[x].to_float(), data[y].to_float(), data[z].to_float()),
+                color,
+                alpha,
+            })
+        };
+
+        Ok(Box::new(closure))
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
     fs::File,
-    io::{BufRead, BufReader, Read},
+    io::{BufRead, BufReader},
     path::Path,
 };
 
@@ -9,7 +9,7 @@ use pointrain_core::pc::PointCloudBase;
 use super::point::PointReadable;
 use crate::{
     field::{PointField, PointFieldDatum, PointFieldType},
-    PointRainIOError,
+    PointRainIOError, ReadOptions, ReadReport,
 };
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -47,14 +47,34 @@ where
     PC: PointCloudBase,
     PC::Point: PointReadable,
 {
-    let file = File::open(f)?;
+    ply_read_with_options(f, &ReadOptions::default()).map(|(pc, _)| pc)
+}
 
-    let (mut reader, header) = ply_read_header(&file)?;
-    ply_read_data::<PC>(&mut reader, &header)
+pub fn ply_read_with_options<PC>(
+    f: impl AsRef<Path>,
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    ply_read_from(BufReader::new(File::open(f)?), options)
 }
 
-fn ply_read_header(file: &File) -> Result<(BufReader<&File>, PlyHeader), PointRainIOError> {
-    let mut reader = BufReader::new(file);
+/// Reads a PLY stream, shared by the file and the async readers.
+pub(crate) fn ply_read_from<PC>(
+    mut reader: impl BufRead,
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let header = ply_read_header(&mut reader)?;
+    ply_read_data::<PC>(reader, &header, options)
+}
+
+fn ply_read_header(reader: &mut impl BufRead) -> Result<PlyHeader, PointRainIOError> {
     let mut header = PlyHeader::default();
     let mut line = String::new();
     let mut current_element: Option<String> = None;
@@ -93,39 +113,83 @@ fn ply_read_header(file: &File) -> Result<(BufReader<&File>, PlyHeader), PointRa
         line.clear();
     }
 
-    Ok((reader, header))
+    Ok(header)
 }
 
 fn ply_read_data<PC>(
-    reader: &mut BufReader<&File>,
+    mut reader: impl BufRead,
     header: &PlyHeader,
-) -> Result<PC, PointRainIOError>
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
 where
     PC: PointCloudBase,
     PC::Point: PointReadable,
 {
     let mut pc = PC::with_capacity(header.vertices_size);
+    let mut report = ReadReport {
+        declared_points: Some(header.vertices_size),
+        ..Default::default()
+    };
     let func = PC::Point::read_data_func(&header.vertices)?;
 
     match header.format {
         PlyDataFormat::Ascii => {
-            for line in reader.lines().take(header.vertices_size) {
-                let data = ply_read_ascii_datum(header, line?.as_str())?;
-                pc.push(func(&data)?);
+            let mut index = 0;
+            let mut lines = reader.lines();
+            while index < header.vertices_size {
+                let Some(line) = lines.next() else {
+                    break;
+                };
+                let line = line?;
+                if line.trim().is_empty() && !options.is_strict() {
+                    report.blank_lines += 1;
+                    continue;
+                }
+
+                match ply_read_ascii_datum(header, line.as_str()).and_then(|data| func(&data)) {
+                    Ok(p) => {
+                        pc.push(p);
+                    }
+                    Err(e) => options.recover(&mut report, &mut pc, index, e)?,
+                }
+                index += 1;
             }
         }
         PlyDataFormat::BinaryLE | PlyDataFormat::BinaryBE => {
             let chunk_size = header.vertices.iter().map(PointField::bytes).sum();
             let mut chunk = vec![0; chunk_size];
-            for _ in 0..header.vertices_size {
-                reader.read_exact(&mut chunk)?;
+            for index in 0..header.vertices_size {
+                if let Err(e) = reader.read_exact(&mut chunk) {
+                    if options.is_strict() {
+                        return Err(e.into());
+                    }
+                    break;
+                }
                 let data = pcd_read_binary_datum(header, &mut chunk.as_slice());
-                pc.push(func(&data)?);
+                match func(&data) {
+                    Ok(p) => {
+                        pc.push(p);
+                    }
+                    Err(e) => options.recover(&mut report, &mut pc, index, e)?,
+                }
             }
         }
     }
 
-    Ok(pc)
+    report.read_points = pc.len();
+
+    if pc.len() != header.vertices_size && options.is_strict() {
+        return Err(PointRainIOError::Error {
+            msg: format!(
+                "The number of vertices ({}) does not match the number of vertices specified in the header ({})",
+                pc.len(),
+                header.vertices_size
+            )
+            .into(),
+        });
+    }
+
+    Ok((pc, report))
 }
 
 fn ply_read_ascii_datum(
@@ -0,0 +1,76 @@
+use std::io::Write;
+
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{error::PointRainIOError, mesh::WritableColumns, point::PointWritable};
+
+/// Writes a binary PLY stream.
+pub(crate) fn ply_write_to<PC>(writer: &mut impl Write, pc: &PC) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = WritableColumns::new(PC::Point::columns())?;
+
+    // Property name, column and whether it is a color channel.
+    let mut properties = Vec::new();
+    properties.extend(
+        ["x", "y", "z"]
+            .into_iter()
+            .zip(columns.xyz)
+            .map(|(n, i)| (n, i, false)),
+    );
+    if let Some(i) = columns.intensity {
+        properties.push(("intensity", i, false));
+    }
+    if let Some(rgb) = columns.rgb {
+        properties.extend(
+            ["red", "green", "blue"]
+                .into_iter()
+                .zip(rgb)
+                .map(|(n, i)| (n, i, true)),
+        );
+    }
+    if let Some(a) = columns.alpha {
+        properties.push(("alpha", a, true));
+    }
+    if let Some(normal) = columns.normal {
+        properties.extend(
+            ["nx", "ny", "nz"]
+                .into_iter()
+                .zip(normal)
+                .map(|(n, i)| (n, i, false)),
+        );
+    }
+    if let Some(c) = columns.curvature {
+        properties.push(("curvature", c, false));
+    }
+
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format binary_little_endian 1.0")?;
+    writeln!(writer, "element vertex {}", pc.len())?;
+    for &(name, _, color) in &properties {
+        let type_ = if color { "uchar" } else { "float" };
+        writeln!(writer, "property {type_} {name}")?;
+    }
+    writeln!(writer, "end_header")?;
+
+    let mut data = Vec::new();
+    let mut record = Vec::new();
+    for p in pc.iter() {
+        data.clear();
+        record.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        for &(_, i, color) in &properties {
+            if color {
+                record.push(data[i].to_color_channel()?);
+            } else {
+                record.extend(data[i].to_float().to_le_bytes());
+            }
+        }
+        writer.write_all(&record)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,325 @@
+//! Column mapping shared by the formats without a fixed schema.
+//!
+//! Columns are identified by canonical names: `x`, `y`, `z`, `intensity`, `r`, `g`, `b`, `a`,
+//! `nx`, `ny`, `nz` and `curvature`.
+
+use pointrain_core::{
+    point::{
+        Point, PointBase, PointIntensity, PointIntensityNormal, PointNormal, PointRgb,
+        PointRgbNormal, PointRgba,
+    },
+    types::{Normal, Position},
+};
+
+use crate::{
+    error::{MissingField, PointRainIOError},
+    field::{ColorField, PointField, PointFieldDatum},
+};
+
+/// Maps common spellings of a column name (e.g. CloudCompare's `//X`, `Nx`, `red`) to
+/// its canonical name. Unknown names are lowercased and otherwise kept.
+pub fn canonical_column_name(name: &str) -> String {
+    let name = name.trim().trim_start_matches("//").to_lowercase();
+
+    match name.as_str() {
+        "red" | "diffuse_red" => "r",
+        "green" | "diffuse_green" => "g",
+        "blue" | "diffuse_blue" => "b",
+        "alpha" | "diffuse_alpha" => "a",
+        "i" | "scalar_intensity" | "reflectance" => "intensity",
+        "normal_x" => "nx",
+        "normal_y" => "ny",
+        "normal_z" => "nz",
+        _ => return name,
+    }
+    .into()
+}
+
+fn find_field(fields: &[PointField], name: &'static str) -> Result<usize, MissingField> {
+    fields
+        .iter()
+        .enumerate()
+        .find(|(_, f)| f.name == name)
+        .map(|(i, _)| i)
+        .ok_or(MissingField(name))
+}
+
+fn find_xyz(fields: &[PointField]) -> Result<(usize, usize, usize), MissingField> {
+    Ok((
+        find_field(fields, "x")?,
+        find_field(fields, "y")?,
+        find_field(fields, "z")?,
+    ))
+}
+
+fn find_normal(
+    fields: &[PointField],
+) -> Result<(usize, usize, usize, Option<usize>), MissingField> {
+    Ok((
+        find_field(fields, "nx")?,
+        find_field(fields, "ny")?,
+        find_field(fields, "nz")?,
+        find_field(fields, "curvature").ok(),
+    ))
+}
+
+fn find_intensity(fields: &[PointField]) -> Result<usize, MissingField> {
+    find_field(fields, "intensity")
+}
+
+fn find_rgb(fields: &[PointField]) -> Result<ColorField, MissingField> {
+    Ok(ColorField::Channels {
+        r: find_field(fields, "r")?,
+        g: find_field(fields, "g")?,
+        b: find_field(fields, "b")?,
+        a: find_field(fields, "a").ok(),
+    })
+}
+
+fn read_position(data: &[PointFieldDatum], (x, y, z): (usize, usize, usize)) -> Position {
+    Position::new(data[x].to_float(), data[y].to_float(), data[z].to_float())
+}
+
+fn read_normal(
+    data: &[PointFieldDatum],
+    (nx, ny, nz, curvature): (usize, usize, usize, Option<usize>),
+) -> (Normal, f32) {
+    (
+        Normal::new(
+            data[nx].to_float(),
+            data[ny].to_float(),
+            data[nz].to_float(),
+        ),
+        curvature.map_or(0., |c| data[c].to_float()),
+    )
+}
+
+pub type PointMapper<T> = Box<dyn Fn(&[PointFieldDatum]) -> Result<T, PointRainIOError>>;
+
+pub trait PointReadable: Sized {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError>;
+}
+
+impl PointReadable for Point {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
+        let xyz = find_xyz(fields)?;
+
+        let closure = move |data: &[PointFieldDatum]| {
+            Ok(Self {
+                position: read_position(data, xyz),
+            })
+        };
+
+        Ok(Box::new(closure))
+    }
+}
+
+impl PointReadable for PointNormal {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
+        let xyz = find_xyz(fields)?;
+        let normal = find_normal(fields)?;
+
+        let closure = move |data: &[PointFieldDatum]| {
+            let (normal, curvature) = read_normal(data, normal);
+            Ok(Self {
+                position: read_position(data, xyz),
+                normal,
+                curvature,
+            })
+        };
+
+        Ok(Box::new(closure))
+    }
+}
+
+impl PointReadable for PointIntensity {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
+        let xyz = find_xyz(fields)?;
+        let intensity = find_intensity(fields)?;
+
+        let closure = move |data: &[PointFieldDatum]| {
+            Ok(Self {
+                position: read_position(data, xyz),
+                intensity: data[intensity].to_float(),
+            })
+        };
+
+        Ok(Box::new(closure))
+    }
+}
+
+impl PointReadable for PointIntensityNormal {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
+        let xyz = find_xyz(fields)?;
+        let intensity = find_intensity(fields)?;
+        let normal = find_normal(fields)?;
+
+        let closure = move |data: &[PointFieldDatum]| {
+            let (normal, curvature) = read_normal(data, normal);
+            Ok(Self {
+                position: read_position(data, xyz),
+                intensity: data[intensity].to_float(),
+                normal,
+                curvature,
+            })
+        };
+
+        Ok(Box::new(closure))
+    }
+}
+
+impl PointReadable for PointRgb {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
+        let xyz = find_xyz(fields)?;
+        let rgb = find_rgb(fields)?;
+
+        let closure = move |data: &[PointFieldDatum]| {
+            Ok(Self {
+                position: read_position(data, xyz),
+                color: rgb.read_rgb(data)?,
+            })
+        };
+
+        Ok(Box::new(closure))
+    }
+}
+
+impl PointReadable for PointRgbNormal {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
+        let xyz = find_xyz(fields)?;
+        let rgb = find_rgb(fields)?;
+        let normal = find_normal(fields)?;
+
+        let closure = move |data: &[PointFieldDatum]| {
+            let (normal, curvature) = read_normal(data, normal);
+            Ok(Self {
+                position: read_position(data, xyz),
+                color: rgb.read_rgb(data)?,
+                normal,
+                curvature,
+            })
+        };
+
+        Ok(Box::new(closure))
+    }
+}
+
+impl PointReadable for PointRgba {
+    fn read_data_func(fields: &[PointField]) -> Result<PointMapper<Self>, PointRainIOError> {
+        let xyz = find_xyz(fields)?;
+        let rgb = find_rgb(fields)?;
+
+        let closure = move |data: &[PointFieldDatum]| {
+            let (color, alpha) = rgb.read(data)?;
+            Ok(Self {
+                position: read_position(data, xyz),
+                color,
+                alpha,
+            })
+        };
+
+        Ok(Box::new(closure))
+    }
+}
+
+/// Flattens a point into canonical columns.
+pub trait PointWritable: PointBase {
+    fn columns() -> &'static [&'static str];
+    fn write_data(p: &Self::Ref<'_>, data: &mut Vec<PointFieldDatum>);
+}
+
+fn write_position(p: &Position, data: &mut Vec<PointFieldDatum>) {
+    data.extend([
+        PointFieldDatum::F32(p.x),
+        PointFieldDatum::F32(p.y),
+        PointFieldDatum::F32(p.z),
+    ]);
+}
+
+fn write_normal(n: &Normal, curvature: f32, data: &mut Vec<PointFieldDatum>) {
+    data.extend([
+        PointFieldDatum::F32(n.x),
+        PointFieldDatum::F32(n.y),
+        PointFieldDatum::F32(n.z),
+        PointFieldDatum::F32(curvature),
+    ]);
+}
+
+impl PointWritable for Point {
+    fn columns() -> &'static [&'static str] {
+        &["x", "y", "z"]
+    }
+
+    fn write_data(p: &Self::Ref<'_>, data: &mut Vec<PointFieldDatum>) {
+        write_position(p.position, data);
+    }
+}
+
+impl PointWritable for PointNormal {
+    fn columns() -> &'static [&'static str] {
+        &["x", "y", "z", "nx", "ny", "nz", "curvature"]
+    }
+
+    fn write_data(p: &Self::Ref<'_>, data: &mut Vec<PointFieldDatum>) {
+        write_position(p.position, data);
+        write_normal(p.normal, *p.curvature, data);
+    }
+}
+
+impl PointWritable for PointIntensity {
+    fn columns() -> &'static [&'static str] {
+        &["x", "y", "z", "intensity"]
+    }
+
+    fn write_data(p: &Self::Ref<'_>, data: &mut Vec<PointFieldDatum>) {
+        write_position(p.position, data);
+        data.push(PointFieldDatum::F32(*p.intensity));
+    }
+}
+
+impl PointWritable for PointIntensityNormal {
+    fn columns() -> &'static [&'static str] {
+        &["x", "y", "z", "intensity", "nx", "ny", "nz", "curvature"]
+    }
+
+    fn write_data(p: &Self::Ref<'_>, data: &mut Vec<PointFieldDatum>) {
+        write_position(p.position, data);
+        data.push(PointFieldDatum::F32(*p.intensity));
+        write_normal(p.normal, *p.curvature, data);
+    }
+}
+
+impl PointWritable for PointRgb {
+    fn columns() -> &'static [&'static str] {
+        &["x", "y", "z", "r", "g", "b"]
+    }
+
+    fn write_data(p: &Self::Ref<'_>, data: &mut Vec<PointFieldDatum>) {
+        write_position(p.position, data);
+        data.extend(p.color.iter().map(|c| PointFieldDatum::U8(*c)));
+    }
+}
+
+impl PointWritable for PointRgbNormal {
+    fn columns() -> &'static [&'static str] {
+        &["x", "y", "z", "r", "g", "b", "nx", "ny", "nz", "curvature"]
+    }
+
+    fn write_data(p: &Self::Ref<'_>, data: &mut Vec<PointFieldDatum>) {
+        write_position(p.position, data);
+        data.extend(p.color.iter().map(|c| PointFieldDatum::U8(*c)));
+        write_normal(p.normal, *p.curvature, data);
+    }
+}
+
+impl PointWritable for PointRgba {
+    fn columns() -> &'static [&'static str] {
+        &["x", "y", "z", "r", "g", "b", "a"]
+    }
+
+    fn write_data(p: &Self::Ref<'_>, data: &mut Vec<PointFieldDatum>) {
+        write_position(p.position, data);
+        data.extend(p.color.iter().map(|c| PointFieldDatum::U8(*c)));
+        data.push(PointFieldDatum::U8(*p.alpha));
+    }
+}
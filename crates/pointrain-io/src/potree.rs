@@ -0,0 +1,314 @@
+//! Conversion of point clouds into the Potree 2.0 format (`metadata.json`, `hierarchy.bin`
+//! and `octree.bin`) for streaming in web viewers.
+//!
+//! The input is a point cloud, or a PCD or LAS file read in chunks. Either way the octree is
+//! built in memory from the encoded attributes of all points (at most 34 bytes per point), so
+//! clouds whose attributes do not fit into memory cannot be converted.
+
+use std::{fs, path::Path};
+
+use pointrain_core::pc::PointCloudBase;
+use serde_json::json;
+
+use crate::{
+    error::PointRainIOError,
+    las::las_read_chunks,
+    lod::{LodOptions, Node},
+    mesh::WritableColumns,
+    pcd::{pcd_read_chunks, point::PointReadable as PcdPointReadable},
+    point::{PointReadable, PointWritable},
+    ReadOptions,
+};
+
+/// Size of a node record of `hierarchy.bin`.
+const NODE_RECORD_SIZE: usize = 22;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PotreeOptions {
+    pub name: String,
+    /// Nodes with at most this many points are not split.
+    pub max_points_per_node: usize,
+    pub max_depth: usize,
+    /// Cells per axis of the sampling grid of every node. The spacing of the root is its
+    /// size divided by this.
+    pub grid_size: usize,
+    /// Resolution of the integer encoded positions. It is coarsened if needed to fit the
+    /// bounding box into `i32`.
+    pub scale: f64,
+}
+
+impl Default for PotreeOptions {
+    fn default() -> Self {
+        Self {
+            name: "pointrain".into(),
+            max_points_per_node: 20_000,
+            max_depth: 20,
+            grid_size: 128,
+            scale: 0.001,
+        }
+    }
+}
+
+/// Points are read from PCD and LAS files in chunks of this many points.
+const CHUNK_LEN: usize = 1 << 16;
+
+/// Builds a Potree 2.0 octree of `pc` in the directory `dir`.
+///
+/// Each node holds a grid-sampled subset of the points of its cube that are not in any of
+/// its ancestors, so that the points of all nodes add up to the input. Intensity is encoded
+/// as `uint16` (scaled from `[0, 1]` if all intensities are in that range) and colors as
+/// 16-bit `rgb`. Points with a non-finite position are skipped.
+pub fn potree_convert<PC>(
+    pc: &PC,
+    dir: impl AsRef<Path>,
+    options: &PotreeOptions,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let mut attributes = Attributes::new::<PC>(pc.len())?;
+    attributes.extend(pc)?;
+    attributes.write(dir.as_ref(), options)
+}
+
+/// Builds a Potree 2.0 octree of the PCD file `f` in the directory `dir`, like
+/// [`potree_convert`].
+///
+/// The file is streamed in chunks and only the encoded attributes of the points are kept,
+/// not a point cloud of `PC`. `PC` selects which attributes are read. The octree itself is
+/// still built in memory, so the attributes of all points have to fit.
+pub fn potree_convert_pcd<PC>(
+    f: impl AsRef<Path>,
+    dir: impl AsRef<Path>,
+    options: &PotreeOptions,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PcdPointReadable + PointWritable,
+{
+    let mut attributes = Attributes::new::<PC>(0)?;
+    pcd_read_chunks::<PC>(f, CHUNK_LEN, &ReadOptions::default(), |chunk| {
+        attributes.extend(&chunk)
+    })?;
+    attributes.write(dir.as_ref(), options)
+}
+
+/// Builds a Potree 2.0 octree of the LAS file `f` in the directory `dir`, streaming it like
+/// [`potree_convert_pcd`].
+pub fn potree_convert_las<PC>(
+    f: impl AsRef<Path>,
+    dir: impl AsRef<Path>,
+    options: &PotreeOptions,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable + PointWritable,
+{
+    let mut attributes = Attributes::new::<PC>(0)?;
+    las_read_chunks::<PC>(f, CHUNK_LEN, |chunk| attributes.extend(&chunk))?;
+    attributes.write(dir.as_ref(), options)
+}
+
+/// The attributes of the finite points, the input of the octree.
+struct Attributes {
+    columns: WritableColumns,
+    positions: Vec<[f64; 3]>,
+    intensities: Vec<f32>,
+    colors: Vec<[u16; 3]>,
+}
+
+impl Attributes {
+    fn new<PC>(capacity: usize) -> Result<Self, PointRainIOError>
+    where
+        PC: PointCloudBase,
+        PC::Point: PointWritable,
+    {
+        Ok(Self {
+            columns: WritableColumns::new(PC::Point::columns())?,
+            positions: Vec::with_capacity(capacity),
+            intensities: Vec::new(),
+            colors: Vec::new(),
+        })
+    }
+
+    fn extend<PC>(&mut self, pc: &PC) -> Result<(), PointRainIOError>
+    where
+        PC: PointCloudBase,
+        PC::Point: PointWritable,
+    {
+        let columns = &self.columns;
+        let mut data = Vec::new();
+        for p in pc.iter() {
+            data.clear();
+            PC::Point::write_data(&p, &mut data);
+
+            let position = columns.xyz.map(|i| f64::from(data[i].to_float()));
+            if !position.iter().all(|v| v.is_finite()) {
+                continue;
+            }
+            self.positions.push(position);
+            if let Some(i) = columns.intensity {
+                self.intensities.push(data[i].to_float());
+            }
+            if let Some(rgb) = columns.rgb {
+                let color = rgb
+                    .iter()
+                    .map(|c| data[*c].to_color_channel().map(|c| u16::from(c) * 257))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.colors.push([color[0], color[1], color[2]]);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write(self, dir: &Path, options: &PotreeOptions) -> Result<(), PointRainIOError> {
+        let Self {
+            positions,
+            intensities,
+            colors,
+            ..
+        } = self;
+        if positions.is_empty() {
+            return Err("Point cloud has no finite points".to_string().into());
+        }
+
+        let intensities = encode_intensities(&intensities);
+
+        let (min, max) = positions
+            .iter()
+            .fold(([f64::MAX; 3], [f64::MIN; 3]), |(min, max), p| {
+                (
+                    std::array::from_fn(|i| min[i].min(p[i])),
+                    std::array::from_fn(|i| max[i].max(p[i])),
+                )
+            });
+        let size = (0..3).map(|i| max[i] - min[i]).fold(0., f64::max);
+        let scale = options.scale.max(size / f64::from(i32::MAX));
+        let size = size.max(scale);
+        let cube_max = min.map(|v| v + size);
+
+        let lod = LodOptions {
+            max_points_per_node: options.max_points_per_node,
+            max_depth: options.max_depth,
+            grid_size: options.grid_size,
+        };
+        let root = Node::build(&positions, min, size, &lod);
+
+        // Nodes are stored breadth first, children in order of their index.
+        let mut octree = Vec::new();
+        let mut hierarchy = Vec::new();
+        let mut depth = 0;
+        for node in root.breadth_first() {
+            depth = depth.max(node.level);
+
+            let offset = octree.len();
+            for &i in &node.points {
+                for (v, min) in positions[i].iter().zip(&min) {
+                    octree.extend((((v - min) / scale).round() as i32).to_le_bytes());
+                }
+                if let Some(intensities) = &intensities {
+                    octree.extend(intensities[i].to_le_bytes());
+                }
+                if !colors.is_empty() {
+                    for c in colors[i] {
+                        octree.extend(c.to_le_bytes());
+                    }
+                }
+            }
+
+            hierarchy.push(u8::from(node.is_leaf()));
+            hierarchy.push(node.child_mask());
+            hierarchy.extend((node.points.len() as u32).to_le_bytes());
+            hierarchy.extend((offset as u64).to_le_bytes());
+            hierarchy.extend(((octree.len() - offset) as u64).to_le_bytes());
+        }
+        debug_assert_eq!(hierarchy.len() % NODE_RECORD_SIZE, 0);
+
+        let mut attributes = vec![json!({
+            "name": "position",
+            "description": "",
+            "size": 12,
+            "numElements": 3,
+            "elementSize": 4,
+            "type": "int32",
+            "min": min,
+            "max": max,
+        })];
+        if let Some(intensities) = &intensities {
+            attributes.push(json!({
+                "name": "intensity",
+                "description": "",
+                "size": 2,
+                "numElements": 1,
+                "elementSize": 2,
+                "type": "uint16",
+                "min": [intensities.iter().min()],
+                "max": [intensities.iter().max()],
+            }));
+        }
+        if !colors.is_empty() {
+            let channel = |f: fn(u16, u16) -> u16| -> [u16; 3] {
+                std::array::from_fn(|i| colors.iter().map(|c| c[i]).reduce(f).unwrap())
+            };
+            attributes.push(json!({
+                "name": "rgb",
+                "description": "",
+                "size": 6,
+                "numElements": 3,
+                "elementSize": 2,
+                "type": "uint16",
+                "min": channel(u16::min),
+                "max": channel(u16::max),
+            }));
+        }
+
+        let metadata = json!({
+            "version": "2.0",
+            "name": options.name,
+            "description": "",
+            "points": positions.len(),
+            "projection": "",
+            "hierarchy": {
+                "firstChunkSize": hierarchy.len(),
+                "stepSize": depth + 1,
+                "depth": depth,
+            },
+            "offset": min,
+            "scale": [scale, scale, scale],
+            "spacing": size / options.grid_size as f64,
+            "boundingBox": { "min": min, "max": cube_max },
+            "encoding": "DEFAULT",
+            "attributes": attributes,
+        });
+
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("octree.bin"), octree)?;
+        fs::write(dir.join("hierarchy.bin"), hierarchy)?;
+        let metadata = serde_json::to_string_pretty(&metadata).map_err(|e| e.to_string())?;
+        fs::write(dir.join("metadata.json"), metadata)?;
+
+        Ok(())
+    }
+}
+
+/// Intensities as `uint16`, `None` if the point type has none.
+fn encode_intensities(intensities: &[f32]) -> Option<Vec<u16>> {
+    if intensities.is_empty() {
+        return None;
+    }
+
+    let factor = if intensities.iter().all(|i| (0.0..=1.0).contains(i)) {
+        f32::from(u16::MAX)
+    } else {
+        1.
+    };
+
+    Some(
+        intensities
+            .iter()
+            .map(|i| (i * factor).round().clamp(0., f32::from(u16::MAX)) as u16)
+            .collect(),
+    )
+}
@@ -0,0 +1,5 @@
+mod read;
+mod write;
+
+pub use read::pts_read;
+pub use write::pts_write;
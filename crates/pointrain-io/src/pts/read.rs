@@ -0,0 +1,83 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    point::PointReadable,
+};
+
+/// Reads `x y z [i] [r g b]` points, following the point counts of every block of the file.
+///
+/// Intensity is kept as stored (usually `-2048..=2047`).
+pub fn pts_read<PC>(f: impl AsRef<Path>) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let file = File::open(f)?;
+
+    let mut pc = PC::new();
+    let mut declared = 0;
+    let mut func = None;
+    let mut data = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let tokens: Vec<_> = line.split_whitespace().collect();
+
+        match tokens.len() {
+            0 => continue,
+            1 => {
+                declared += tokens[0].parse::<usize>()?;
+                continue;
+            }
+            _ => {}
+        }
+
+        data.clear();
+        for token in tokens {
+            data.push(PointFieldDatum::parse_number(token)?);
+        }
+
+        // Every point has as many values as the first one.
+        let (arity, func) = match &func {
+            Some(func) => func,
+            None => func.insert((data.len(), PC::Point::read_data_func(&fields(data.len())?)?)),
+        };
+        if data.len() != *arity {
+            return Err(format!("Expected {arity} values in point, got {}", data.len()).into());
+        }
+        pc.push(func(&data)?);
+    }
+
+    if pc.len() != declared {
+        return Err(format!("Expected {declared} points, got {}", pc.len()).into());
+    }
+
+    Ok(pc)
+}
+
+fn fields(n: usize) -> Result<Vec<PointField>, PointRainIOError> {
+    let names: &[&str] = match n {
+        3 => &["x", "y", "z"],
+        4 => &["x", "y", "z", "intensity"],
+        6 => &["x", "y", "z", "r", "g", "b"],
+        7 => &["x", "y", "z", "intensity", "r", "g", "b"],
+        _ => return Err(format!("Invalid number of values in point: {n}").into()),
+    };
+
+    Ok(names
+        .iter()
+        .map(|name| PointField {
+            name: name.to_string(),
+            datatype: PointFieldType::F64,
+            count: 1,
+        })
+        .collect())
+}
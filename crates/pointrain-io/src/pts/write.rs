@@ -0,0 +1,41 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{error::PointRainIOError, mesh::WritableColumns, point::PointWritable};
+
+/// Writes the points as a single block of `x y z [i] [r g b]`.
+pub fn pts_write<PC>(f: impl AsRef<Path>, pc: &PC) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = WritableColumns::new(PC::Point::columns())?;
+    let mut writer = BufWriter::new(File::create(f)?);
+    let mut data = Vec::new();
+
+    writeln!(writer, "{}", pc.len())?;
+
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        let [x, y, z] = columns.xyz;
+        write!(writer, "{} {} {}", data[x], data[y], data[z])?;
+        if let Some(i) = columns.intensity {
+            write!(writer, " {}", data[i])?;
+        }
+        if let Some([r, g, b]) = columns.rgb {
+            write!(writer, " {} {} {}", data[r], data[g], data[b])?;
+        }
+        writeln!(writer)?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+mod read;
+mod write;
+
+use pointrain_core::types::Float;
+
+pub use read::ptx_read;
+pub use write::ptx_write;
+
+/// A single scan of a PTX file, organized as a `rows` x `cols` grid.
+#[derive(Debug, Clone)]
+pub struct PtxScan<PC> {
+    /// Points in column-major order. Missing returns have a NaN position.
+    pub pc: PC,
+    pub rows: usize,
+    pub cols: usize,
+    /// Registration of the scan, already applied to `pc`.
+    pub transform: nalgebra::Matrix4<Float>,
+}
+
+impl<PC> PtxScan<PC> {
+    /// Index into `pc` of the point at `row` and `col`.
+    pub fn index(&self, row: usize, col: usize) -> usize {
+        col * self.rows + row
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use nalgebra::{Matrix4, Transform3};
+use pointrain_core::{pc::PointCloudBase, point::PointBase, types::Position};
+
+use super::PtxScan;
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    point::PointReadable,
+};
+
+/// Reads every scan of a PTX file and applies its registration matrix.
+///
+/// Points are `x y z i [r g b]`; `0 0 0` marks a missing return and is read as NaN so that
+/// the grid stays complete.
+pub fn ptx_read<PC>(f: impl AsRef<Path>) -> Result<Vec<PtxScan<PC>>, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let file = File::open(f)?;
+
+    let mut lines = BufReader::new(file)
+        .lines()
+        .filter(|l| l.as_ref().map_or(true, |l| !l.trim().is_empty()));
+    let mut scans = Vec::new();
+
+    while let Some(line) = lines.next() {
+        let cols: usize = line?.trim().parse()?;
+        let rows: usize = next_line(&mut lines)?.trim().parse()?;
+
+        // Scanner position and axes in the registered frame, implied by the matrix.
+        for _ in 0..4 {
+            next_values(&mut lines, 3)?;
+        }
+        let mut transform = Matrix4::identity();
+        for r in 0..4 {
+            let values = next_values(&mut lines, 4)?;
+            // The matrix transforms row vectors, so it is stored transposed.
+            for (c, v) in values.iter().enumerate() {
+                transform[(c, r)] = *v as f32;
+            }
+        }
+
+        let len = rows
+            .checked_mul(cols)
+            .ok_or_else(|| format!("Grid of {rows} x {cols} points is too large"))?;
+
+        // Not preallocated, the header is not trusted.
+        let mut pc = PC::new();
+        let mut func = None;
+        let mut data = Vec::new();
+        for _ in 0..len {
+            let line = next_line(&mut lines)?;
+            data.clear();
+            data.extend(
+                line.split_whitespace()
+                    .map(PointFieldDatum::parse_number)
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+
+            // Every point has as many values as the first one, at least `x y z`.
+            let (arity, func) = match &func {
+                Some(func) => func,
+                None => func.insert((data.len(), PC::Point::read_data_func(&fields(data.len())?)?)),
+            };
+            if data.len() != *arity {
+                return Err(format!("Expected {arity} values in point, got {}", data.len()).into());
+            }
+
+            let mut p = func(&data)?;
+            if data[..3].iter().all(|v| v.to_float() == 0.) {
+                *p.position_mut() = Position::new(f32::NAN, f32::NAN, f32::NAN);
+            }
+            pc.push(p);
+        }
+
+        pc.transform_mut(Transform3::from_matrix_unchecked(transform));
+        scans.push(PtxScan {
+            pc,
+            rows,
+            cols,
+            transform,
+        });
+    }
+
+    Ok(scans)
+}
+
+fn next_line(
+    lines: &mut impl Iterator<Item = std::io::Result<String>>,
+) -> Result<String, PointRainIOError> {
+    lines
+        .next()
+        .ok_or_else(|| PointRainIOError::from("Unexpected end of file".to_string()))?
+        .map_err(Into::into)
+}
+
+fn next_values(
+    lines: &mut impl Iterator<Item = std::io::Result<String>>,
+    n: usize,
+) -> Result<Vec<f64>, PointRainIOError> {
+    let values = next_line(lines)?
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<f64>, _>>()?;
+    if values.len() != n {
+        return Err(format!("Expected {n} values in header, got {}", values.len()).into());
+    }
+    Ok(values)
+}
+
+fn fields(n: usize) -> Result<Vec<PointField>, PointRainIOError> {
+    let names: &[&str] = match n {
+        3 => &["x", "y", "z"],
+        4 => &["x", "y", "z", "intensity"],
+        7 => &["x", "y", "z", "intensity", "r", "g", "b"],
+        _ => return Err(format!("Invalid number of values in point: {n}").into()),
+    };
+
+    Ok(names
+        .iter()
+        .map(|name| PointField {
+            name: name.to_string(),
+            datatype: PointFieldType::F64,
+            count: 1,
+        })
+        .collect())
+}
@@ -0,0 +1,81 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use pointrain_core::{pc::PointCloudBase, types::Position};
+
+use super::PtxScan;
+use crate::{error::PointRainIOError, mesh::WritableColumns, point::PointWritable};
+
+/// Writes the scans as `x y z i [r g b]` in their unregistered frame, with the transform in
+/// the header.
+///
+/// Points without intensity are written with `0.5`; NaN positions as missing returns.
+pub fn ptx_write<PC>(f: impl AsRef<Path>, scans: &[PtxScan<PC>]) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = WritableColumns::new(PC::Point::columns())?;
+    let mut writer = BufWriter::new(File::create(f)?);
+    let mut data = Vec::new();
+
+    for scan in scans {
+        if scan.rows * scan.cols != scan.pc.len() {
+            return Err(format!(
+                "Scan of {} x {} has {} points",
+                scan.rows,
+                scan.cols,
+                scan.pc.len()
+            )
+            .into());
+        }
+        let inverse = scan
+            .transform
+            .try_inverse()
+            .ok_or_else(|| "Scan transform is not invertible".to_string())?;
+
+        writeln!(writer, "{}", scan.cols)?;
+        writeln!(writer, "{}", scan.rows)?;
+        let m = &scan.transform;
+        // Scanner position followed by its axes.
+        for c in [3, 0, 1, 2] {
+            let values = (0..3).map(|r| m[(r, c)].to_string()).collect::<Vec<_>>();
+            writeln!(writer, "{}", values.join(" "))?;
+        }
+        // The matrix transforms row vectors, so it is stored transposed.
+        for c in 0..4 {
+            let values = (0..4).map(|r| m[(r, c)].to_string()).collect::<Vec<_>>();
+            writeln!(writer, "{}", values.join(" "))?;
+        }
+
+        for p in scan.pc.iter() {
+            data.clear();
+            PC::Point::write_data(&p, &mut data);
+
+            let intensity = columns.intensity.map(|i| data[i].to_string());
+            let intensity = intensity.as_deref().unwrap_or("0.5");
+            let [x, y, z] = columns.xyz.map(|i| data[i].to_float());
+            let position = inverse.transform_point(&Position::new(x, y, z));
+            if position.iter().any(|v| v.is_nan()) {
+                write!(writer, "0 0 0 {intensity}")?;
+            } else {
+                write!(
+                    writer,
+                    "{} {} {} {intensity}",
+                    position.x, position.y, position.z
+                )?;
+            }
+            if let Some([r, g, b]) = columns.rgb {
+                write!(writer, " {} {} {}", data[r], data[g], data[b])?;
+            }
+            writeln!(writer)?;
+        }
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
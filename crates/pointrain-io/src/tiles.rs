@@ -0,0 +1,310 @@
+//! Cesium 3D Tiles export: `.pnts` point cloud tiles and `tileset.json`.
+
+use std::{fs, path::Path};
+
+use nalgebra::Matrix4;
+use pointrain_core::pc::PointCloudBase;
+use serde_json::{json, Value};
+
+use crate::{
+    error::PointRainIOError,
+    lod::{child_min, LodOptions, Node},
+    mesh::WritableColumns,
+    point::PointWritable,
+};
+
+const PNTS_MAGIC: &[u8] = b"pnts";
+const PNTS_VERSION: u32 = 1;
+const PNTS_HEADER_SIZE: usize = 28;
+/// Sections of a tile have to start at multiples of this.
+const ALIGNMENT: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TilesetOptions {
+    /// Tiles with at most this many points are not split.
+    pub max_points_per_tile: usize,
+    pub max_depth: usize,
+    /// Cells per axis of the sampling grid of every tile. The geometric error of a tile is
+    /// its size divided by this.
+    pub grid_size: usize,
+    /// Writes positions as `POSITION_QUANTIZED` (`uint16`) instead of `POSITION` (`float32`).
+    pub quantize_positions: bool,
+    /// Transform of the root tile, e.g. from a local frame to ECEF.
+    pub transform: Option<Matrix4<f64>>,
+}
+
+impl Default for TilesetOptions {
+    fn default() -> Self {
+        Self {
+            max_points_per_tile: 50_000,
+            max_depth: 20,
+            grid_size: 128,
+            quantize_positions: false,
+            transform: None,
+        }
+    }
+}
+
+/// Writes all points of `pc` into a single `.pnts` tile.
+///
+/// Positions are stored relative to the center of the bounding box (`RTC_CENTER`), or
+/// quantized to it if `quantize_positions` is set.
+pub fn pnts_write<PC>(
+    f: impl AsRef<Path>,
+    pc: &PC,
+    quantize_positions: bool,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let points = TilePoints::new(pc)?;
+    let (min, max) = points.bounds();
+    let indices: Vec<_> = (0..points.positions.len()).collect();
+    let size = std::array::from_fn(|i| max[i] - min[i]);
+
+    fs::write(f, points.encode(&indices, min, size, quantize_positions))?;
+    Ok(())
+}
+
+/// Writes a 3D Tiles tileset of `pc` into the directory `dir`.
+///
+/// The points are split into an octree of `.pnts` tiles with additive refinement: every tile
+/// holds a grid-sampled subset of the points of its cube that are not in any of its
+/// ancestors. Colors are written as `RGB` or `RGBA`, normals as `NORMAL`. Points with a
+/// non-finite position are skipped.
+pub fn tileset_write<PC>(
+    pc: &PC,
+    dir: impl AsRef<Path>,
+    options: &TilesetOptions,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let points = TilePoints::new(pc)?;
+    let (min, max) = points.bounds();
+    let size = (0..3)
+        .map(|i| max[i] - min[i])
+        .fold(0., f64::max)
+        .max(f64::EPSILON);
+
+    let lod = LodOptions {
+        max_points_per_node: options.max_points_per_tile,
+        max_depth: options.max_depth,
+        grid_size: options.grid_size,
+    };
+    let root = Node::build(&points.positions, min, size, &lod);
+
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let mut root = write_tile(&points, &root, "r", min, size, dir, options)?;
+    if let Some(transform) = &options.transform {
+        root["transform"] = json!(transform.as_slice());
+    }
+
+    let tileset = json!({
+        "asset": { "version": "1.0" },
+        "geometricError": size,
+        "root": root,
+    });
+    let tileset = serde_json::to_string_pretty(&tileset).map_err(|e| e.to_string())?;
+    fs::write(dir.join("tileset.json"), tileset)?;
+
+    Ok(())
+}
+
+/// Writes the tile of `node` and its descendants, returning its `tileset.json` entry.
+fn write_tile(
+    points: &TilePoints,
+    node: &Node,
+    name: &str,
+    min: [f64; 3],
+    size: f64,
+    dir: &Path,
+    options: &TilesetOptions,
+) -> Result<Value, PointRainIOError> {
+    let half = size / 2.;
+    let center = min.map(|v| v + half);
+
+    let mut tile = json!({
+        "boundingVolume": {
+            "box": [
+                center[0], center[1], center[2],
+                half, 0., 0.,
+                0., half, 0.,
+                0., 0., half,
+            ],
+        },
+        "geometricError": if node.is_leaf() { 0. } else { size / options.grid_size as f64 },
+        "refine": "ADD",
+    });
+
+    if !node.points.is_empty() {
+        let uri = format!("{name}.pnts");
+        let bytes = points.encode(&node.points, min, [size; 3], options.quantize_positions);
+        fs::write(dir.join(&uri), bytes)?;
+        tile["content"] = json!({ "uri": uri });
+    }
+
+    let mut children = Vec::new();
+    for (c, child) in node.children.iter().enumerate() {
+        let Some(child) = child else {
+            continue;
+        };
+        let name = format!("{name}{c}");
+        let min = child_min(min, size, c);
+        children.push(write_tile(points, child, &name, min, half, dir, options)?);
+    }
+    if !children.is_empty() {
+        tile["children"] = Value::Array(children);
+    }
+
+    Ok(tile)
+}
+
+/// The finite points of a cloud with the attributes a tile can hold.
+struct TilePoints {
+    positions: Vec<[f64; 3]>,
+    colors: Vec<[u8; 4]>,
+    has_alpha: bool,
+    normals: Vec<[f32; 3]>,
+}
+
+impl TilePoints {
+    fn new<PC>(pc: &PC) -> Result<Self, PointRainIOError>
+    where
+        PC: PointCloudBase,
+        PC::Point: PointWritable,
+    {
+        let columns = WritableColumns::new(PC::Point::columns())?;
+
+        let mut positions = Vec::with_capacity(pc.len());
+        let mut colors = Vec::new();
+        let mut normals = Vec::new();
+        let mut data = Vec::new();
+        for p in pc.iter() {
+            data.clear();
+            PC::Point::write_data(&p, &mut data);
+
+            let position = columns.xyz.map(|i| f64::from(data[i].to_float()));
+            if !position.iter().all(|v| v.is_finite()) {
+                continue;
+            }
+            positions.push(position);
+            if let Some(rgb) = columns.rgb {
+                let alpha = columns
+                    .alpha
+                    .map_or(Ok(u8::MAX), |a| data[a].to_color_channel());
+                colors.push([
+                    data[rgb[0]].to_color_channel()?,
+                    data[rgb[1]].to_color_channel()?,
+                    data[rgb[2]].to_color_channel()?,
+                    alpha?,
+                ]);
+            }
+            if let Some(normal) = columns.normal {
+                normals.push(normal.map(|i| data[i].to_float()));
+            }
+        }
+        if positions.is_empty() {
+            return Err("Point cloud has no finite points".to_string().into());
+        }
+
+        Ok(Self {
+            positions,
+            colors,
+            has_alpha: columns.alpha.is_some(),
+            normals,
+        })
+    }
+
+    fn bounds(&self) -> ([f64; 3], [f64; 3]) {
+        self.positions
+            .iter()
+            .fold(([f64::MAX; 3], [f64::MIN; 3]), |(min, max), p| {
+                (
+                    std::array::from_fn(|i| min[i].min(p[i])),
+                    std::array::from_fn(|i| max[i].max(p[i])),
+                )
+            })
+    }
+
+    /// Encodes the points `indices` into a `.pnts` tile covering the box at `min` of `size`.
+    fn encode(&self, indices: &[usize], min: [f64; 3], size: [f64; 3], quantize: bool) -> Vec<u8> {
+        let mut table = serde_json::Map::new();
+        table.insert("POINTS_LENGTH".into(), json!(indices.len()));
+
+        // Four byte components come first so that every attribute stays aligned.
+        let mut bin = Vec::new();
+        if quantize {
+            table.insert("QUANTIZED_VOLUME_OFFSET".into(), json!(min));
+            table.insert("QUANTIZED_VOLUME_SCALE".into(), json!(size));
+        } else {
+            let center: [f64; 3] = std::array::from_fn(|i| min[i] + size[i] / 2.);
+            table.insert("RTC_CENTER".into(), json!(center));
+            table.insert("POSITION".into(), json!({ "byteOffset": bin.len() }));
+            for &i in indices {
+                for (v, c) in self.positions[i].iter().zip(&center) {
+                    bin.extend(((v - c) as f32).to_le_bytes());
+                }
+            }
+        }
+        if !self.normals.is_empty() {
+            table.insert("NORMAL".into(), json!({ "byteOffset": bin.len() }));
+            for &i in indices {
+                for v in self.normals[i] {
+                    bin.extend(v.to_le_bytes());
+                }
+            }
+        }
+        if quantize {
+            table.insert(
+                "POSITION_QUANTIZED".into(),
+                json!({ "byteOffset": bin.len() }),
+            );
+            let max = f64::from(u16::MAX);
+            for &i in indices {
+                for axis in 0..3 {
+                    let v = if size[axis] > 0. {
+                        (self.positions[i][axis] - min[axis]) / size[axis] * max
+                    } else {
+                        0.
+                    };
+                    bin.extend((v.round().clamp(0., max) as u16).to_le_bytes());
+                }
+            }
+        }
+        if !self.colors.is_empty() {
+            let (name, channels) = if self.has_alpha {
+                ("RGBA", 4)
+            } else {
+                ("RGB", 3)
+            };
+            table.insert(name.into(), json!({ "byteOffset": bin.len() }));
+            for &i in indices {
+                bin.extend(&self.colors[i][..channels]);
+            }
+        }
+
+        let mut table = Value::Object(table).to_string().into_bytes();
+        while (PNTS_HEADER_SIZE + table.len()) % ALIGNMENT != 0 {
+            table.push(b' ');
+        }
+        while bin.len() % ALIGNMENT != 0 {
+            bin.push(0);
+        }
+
+        let length = PNTS_HEADER_SIZE + table.len() + bin.len();
+        let mut bytes = Vec::with_capacity(length);
+        bytes.extend(PNTS_MAGIC);
+        bytes.extend(PNTS_VERSION.to_le_bytes());
+        // Feature table and batch table lengths, there is no batch table.
+        for v in [length, table.len(), bin.len(), 0, 0] {
+            bytes.extend((v as u32).to_le_bytes());
+        }
+        bytes.extend(table);
+        bytes.extend(bin);
+        bytes
+    }
+}
@@ -0,0 +1,140 @@
+//! Legacy `.vtk` PolyData files. Points are written as vertex cells with their attributes as
+//! point data.
+
+mod read;
+mod write;
+
+use pointrain_core::pc::PointCloudBase;
+
+pub use read::vtk_read;
+pub use write::vtk_write;
+
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    mesh::WritableColumns,
+    point::{canonical_column_name, PointReadable},
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum VtkEncoding {
+    #[default]
+    Ascii,
+    /// Big-endian binary data.
+    Binary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArrayKind {
+    Scalars,
+    Colors,
+    Normals,
+}
+
+/// A point data array and the point columns it is made of.
+pub(crate) struct PointArray {
+    pub name: &'static str,
+    pub kind: ArrayKind,
+    pub columns: Vec<usize>,
+}
+
+pub(crate) fn point_arrays(columns: &WritableColumns) -> Vec<PointArray> {
+    let mut arrays = Vec::new();
+
+    if let Some(i) = columns.intensity {
+        arrays.push(PointArray {
+            name: "intensity",
+            kind: ArrayKind::Scalars,
+            columns: vec![i],
+        });
+    }
+    if let Some(i) = columns.curvature {
+        arrays.push(PointArray {
+            name: "curvature",
+            kind: ArrayKind::Scalars,
+            columns: vec![i],
+        });
+    }
+    if let Some(rgb) = columns.rgb {
+        let mut channels = rgb.to_vec();
+        channels.extend(columns.alpha);
+        arrays.push(PointArray {
+            name: if columns.alpha.is_some() {
+                "rgba"
+            } else {
+                "rgb"
+            },
+            kind: ArrayKind::Colors,
+            columns: channels,
+        });
+    }
+    if let Some(normal) = columns.normal {
+        arrays.push(PointArray {
+            name: "normals",
+            kind: ArrayKind::Normals,
+            columns: normal.to_vec(),
+        });
+    }
+
+    arrays
+}
+
+/// Point columns of a point data array, `None` if it has no counterpart in the point types.
+pub(crate) fn array_columns(name: &str, kind: ArrayKind, components: usize) -> Option<Vec<String>> {
+    let kind = match name.to_lowercase().as_str() {
+        "rgb" | "rgba" | "color" | "colors" => ArrayKind::Colors,
+        "normal" | "normals" => ArrayKind::Normals,
+        _ => kind,
+    };
+
+    let names: &[&str] = match (kind, components) {
+        (ArrayKind::Colors, 3) => &["r", "g", "b"],
+        (ArrayKind::Colors, 4) => &["r", "g", "b", "a"],
+        (ArrayKind::Normals, 3) => &["nx", "ny", "nz"],
+        (ArrayKind::Scalars, 1) => return Some(vec![canonical_column_name(name)]),
+        _ => return None,
+    };
+
+    Some(names.iter().map(|n| n.to_string()).collect())
+}
+
+/// Builds a point cloud from `x y z` positions and point data arrays.
+pub(crate) fn read_cloud<PC>(
+    positions: &[PointFieldDatum],
+    arrays: &[(Vec<String>, Vec<PointFieldDatum>)],
+) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let n = positions.len() / 3;
+    if let Some((names, _)) = arrays.iter().find(|(c, v)| v.len() != n * c.len()) {
+        return Err(format!("Point data {names:?} does not match {n} points").into());
+    }
+
+    let fields: Vec<_> = ["x", "y", "z"]
+        .into_iter()
+        .map(String::from)
+        .chain(arrays.iter().flat_map(|(names, _)| names.iter().cloned()))
+        .map(|name| PointField {
+            name,
+            datatype: PointFieldType::F64,
+            count: 1,
+        })
+        .collect();
+    let func = PC::Point::read_data_func(&fields)?;
+
+    let mut pc = PC::with_capacity(n);
+    let mut data = Vec::with_capacity(fields.len());
+    for i in 0..n {
+        data.clear();
+        data.extend_from_slice(&positions[3 * i..3 * i + 3]);
+        for (names, values) in arrays {
+            let c = names.len();
+            data.extend_from_slice(&values[c * i..c * i + c]);
+        }
+        pc.push(func(&data)?);
+    }
+
+    Ok(pc)
+}
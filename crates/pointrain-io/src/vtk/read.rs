@@ -0,0 +1,228 @@
+use std::{fs, path::Path};
+
+use pointrain_core::pc::PointCloudBase;
+
+use super::{array_columns, read_cloud, ArrayKind};
+use crate::{
+    error::PointRainIOError,
+    field::{PointFieldDatum, PointFieldType},
+    point::PointReadable,
+};
+
+/// Reads the points and point data of a legacy ASCII or binary `POLYDATA` file.
+///
+/// Cells and cell data are ignored. Point data arrays are mapped to point columns by name
+/// (e.g. `intensity`, `curvature`); `COLOR_SCALARS` and `NORMALS` are read as colors and
+/// normals.
+pub fn vtk_read<PC>(f: impl AsRef<Path>) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let bytes = fs::read(f)?;
+    let mut cursor = Cursor {
+        data: &bytes,
+        binary: false,
+    };
+
+    if !cursor.line()?.starts_with("# vtk DataFile") {
+        return Err("Not a VTK file".to_string().into());
+    }
+    cursor.line()?;
+    cursor.binary = match cursor.line()?.trim() {
+        "ASCII" => false,
+        "BINARY" => true,
+        e => return Err(format!("Unknown VTK encoding: {e}").into()),
+    };
+    if cursor.token()? != "DATASET" || cursor.token()? != "POLYDATA" {
+        return Err("Only POLYDATA datasets are supported".to_string().into());
+    }
+
+    let mut positions = Vec::new();
+    let mut arrays = Vec::new();
+    let mut push_array = |name: &str, kind, components, values| {
+        if let Some(columns) = array_columns(name, kind, components) {
+            arrays.push((columns, values));
+        }
+    };
+
+    while let Some(keyword) = cursor.try_token() {
+        match keyword {
+            "POINTS" => {
+                let n: usize = cursor.token()?.parse()?;
+                let datatype = PointFieldType::from_vtk_type(cursor.token()?)?;
+                positions = cursor.values(3 * n, datatype)?;
+            }
+            "VERTICES" | "LINES" | "POLYGONS" | "TRIANGLE_STRIPS" => {
+                let n: usize = cursor.token()?.parse()?;
+                let size: usize = cursor.token()?.parse()?;
+                if cursor.peek_token() == Some("OFFSETS") {
+                    // Version 5 cells: `n` offsets followed by `size` connectivity entries.
+                    cursor.token()?;
+                    let offset_size = cell_type_size(cursor.token()?)?;
+                    cursor.skip(n, offset_size)?;
+                    cursor.token()?;
+                    let connectivity_size = cell_type_size(cursor.token()?)?;
+                    cursor.skip(size, connectivity_size)?;
+                } else {
+                    cursor.skip(size, 4)?;
+                }
+            }
+            "POINT_DATA" => {
+                let n: usize = cursor.token()?.parse()?;
+                if n != positions.len() / 3 {
+                    return Err(format!("POINT_DATA of {n} points does not match POINTS").into());
+                }
+            }
+            "SCALARS" => {
+                let name = cursor.token()?;
+                let datatype = PointFieldType::from_vtk_type(cursor.token()?)?;
+                let components = match cursor.peek_token() {
+                    Some(c) if c.parse::<usize>().is_ok() => cursor.token()?.parse()?,
+                    _ => 1,
+                };
+                if cursor.token()? != "LOOKUP_TABLE" {
+                    return Err(format!("Missing LOOKUP_TABLE of SCALARS {name}").into());
+                }
+                cursor.token()?;
+                let values = cursor.values(positions.len() / 3 * components, datatype)?;
+                push_array(name, ArrayKind::Scalars, components, values);
+            }
+            "COLOR_SCALARS" => {
+                let name = cursor.token()?;
+                let components: usize = cursor.token()?.parse()?;
+                let datatype = if cursor.binary {
+                    PointFieldType::U8
+                } else {
+                    PointFieldType::F32
+                };
+                let values = cursor.values(positions.len() / 3 * components, datatype)?;
+                push_array(name, ArrayKind::Colors, components, values);
+            }
+            "NORMALS" | "VECTORS" => {
+                let name = cursor.token()?;
+                let datatype = PointFieldType::from_vtk_type(cursor.token()?)?;
+                let values = cursor.values(positions.len(), datatype)?;
+                if keyword == "NORMALS" {
+                    push_array(name, ArrayKind::Normals, 3, values);
+                }
+            }
+            "FIELD" => {
+                cursor.token()?;
+                let count: usize = cursor.token()?.parse()?;
+                for _ in 0..count {
+                    let name = cursor.token()?;
+                    let components: usize = cursor.token()?.parse()?;
+                    let tuples: usize = cursor.token()?.parse()?;
+                    let datatype = PointFieldType::from_vtk_type(cursor.token()?)?;
+                    let values = cursor.values(tuples * components, datatype)?;
+                    push_array(name, ArrayKind::Scalars, components, values);
+                }
+            }
+            "METADATA" => {
+                // Rest of the keyword line, then everything up to a blank line.
+                cursor.line()?;
+                while !cursor.line()?.trim().is_empty() {}
+            }
+            "CELL_DATA" => break,
+            _ => return Err(format!("Unsupported VTK section: {keyword}").into()),
+        }
+    }
+
+    read_cloud(&positions, &arrays)
+}
+
+fn cell_type_size(r#type: &str) -> Result<usize, PointRainIOError> {
+    match r#type {
+        "vtktypeint64" | "vtktypeuint64" => Ok(8),
+        t => Ok(PointFieldType::from_vtk_type(t)?.bytes()),
+    }
+}
+
+struct Cursor<'a> {
+    data: &'a [u8],
+    binary: bool,
+}
+
+impl<'a> Cursor<'a> {
+    fn eof() -> PointRainIOError {
+        "Unexpected end of file".to_string().into()
+    }
+
+    fn utf8(bytes: &[u8]) -> Result<&str, PointRainIOError> {
+        std::str::from_utf8(bytes).map_err(|e| e.to_string().into())
+    }
+
+    fn line(&mut self) -> Result<&'a str, PointRainIOError> {
+        if self.data.is_empty() {
+            return Err(Self::eof());
+        }
+        let end = self
+            .data
+            .iter()
+            .position(|b| *b == b'\n')
+            .unwrap_or(self.data.len());
+        let line = &self.data[..end];
+        self.data = &self.data[(end + 1).min(self.data.len())..];
+        Self::utf8(line)
+    }
+
+    fn peek_token(&self) -> Option<&'a str> {
+        let start = self.data.iter().position(|b| !b.is_ascii_whitespace())?;
+        let data = &self.data[start..];
+        let end = data
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .unwrap_or(data.len());
+        std::str::from_utf8(&data[..end]).ok()
+    }
+
+    fn try_token(&mut self) -> Option<&'a str> {
+        let token = self.peek_token()?;
+        let start = self.data.iter().position(|b| !b.is_ascii_whitespace())?;
+        self.data = &self.data[start + token.len()..];
+        Some(token)
+    }
+
+    fn token(&mut self) -> Result<&'a str, PointRainIOError> {
+        self.try_token().ok_or_else(Self::eof)
+    }
+
+    /// Moves to the start of the binary data following a keyword line.
+    fn binary_start(&mut self) -> Result<(), PointRainIOError> {
+        self.line().map(|_| ())
+    }
+
+    fn values(
+        &mut self,
+        n: usize,
+        datatype: PointFieldType,
+    ) -> Result<Vec<PointFieldDatum>, PointRainIOError> {
+        if !self.binary {
+            return (0..n)
+                .map(|_| Ok(PointFieldDatum::parse(self.token()?, datatype)?))
+                .collect();
+        }
+
+        self.binary_start()?;
+        let size = n * datatype.bytes();
+        let mut bytes = self.data.get(..size).ok_or_else(Self::eof)?;
+        self.data = &self.data[size..];
+        Ok((0..n)
+            .map(|_| PointFieldDatum::from_bytes_be(&mut bytes, datatype))
+            .collect())
+    }
+
+    fn skip(&mut self, n: usize, size: usize) -> Result<(), PointRainIOError> {
+        if !self.binary {
+            for _ in 0..n {
+                self.token()?;
+            }
+            return Ok(());
+        }
+
+        self.binary_start()?;
+        self.data = self.data.get(n * size..).ok_or_else(Self::eof)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+
+use super::{point_arrays, ArrayKind, VtkEncoding};
+use crate::{
+    error::PointRainIOError, field::PointFieldDatum, mesh::WritableColumns, point::PointWritable,
+};
+
+/// Writes the points as `POLYDATA` with one vertex cell per point.
+///
+/// Intensity and curvature become `SCALARS`, colors `COLOR_SCALARS` and normals `NORMALS`.
+pub fn vtk_write<PC>(
+    f: impl AsRef<Path>,
+    pc: &PC,
+    encoding: VtkEncoding,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = WritableColumns::new(PC::Point::columns())?;
+    let arrays = point_arrays(&columns);
+    let mut writer = BufWriter::new(File::create(f)?);
+    let n = pc.len();
+
+    let mut positions = Vec::with_capacity(3 * n);
+    let mut values = vec![Vec::new(); arrays.len()];
+    let mut data = Vec::new();
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        positions.extend(columns.xyz.map(|i| data[i]));
+        for (array, values) in arrays.iter().zip(&mut values) {
+            values.extend(array.columns.iter().map(|i| data[*i]));
+        }
+    }
+
+    writeln!(writer, "# vtk DataFile Version 3.0")?;
+    writeln!(writer, "pointrain")?;
+    match encoding {
+        VtkEncoding::Ascii => writeln!(writer, "ASCII")?,
+        VtkEncoding::Binary => writeln!(writer, "BINARY")?,
+    }
+    writeln!(writer, "DATASET POLYDATA")?;
+
+    writeln!(writer, "POINTS {n} float")?;
+    write_values(&mut writer, encoding, &positions, 3)?;
+
+    writeln!(writer, "VERTICES {n} {}", 2 * n)?;
+    let vertices: Vec<_> = (0..n as i32)
+        .flat_map(|i| [PointFieldDatum::I32(1), PointFieldDatum::I32(i)])
+        .collect();
+    write_values(&mut writer, encoding, &vertices, 2)?;
+
+    if !arrays.is_empty() {
+        writeln!(writer, "POINT_DATA {n}")?;
+    }
+    for (array, values) in arrays.iter().zip(&values) {
+        let components = array.columns.len();
+        match array.kind {
+            ArrayKind::Scalars => {
+                writeln!(writer, "SCALARS {} float 1", array.name)?;
+                writeln!(writer, "LOOKUP_TABLE default")?;
+                write_values(&mut writer, encoding, values, components)?;
+            }
+            ArrayKind::Colors => {
+                writeln!(writer, "COLOR_SCALARS {} {components}", array.name)?;
+                // ASCII colors are floats in [0, 1], binary colors are bytes.
+                let values: Vec<_> = match encoding {
+                    VtkEncoding::Ascii => values
+                        .iter()
+                        .map(|v| PointFieldDatum::F32(v.to_float() / 255.))
+                        .collect(),
+                    VtkEncoding::Binary => values.clone(),
+                };
+                write_values(&mut writer, encoding, &values, components)?;
+            }
+            ArrayKind::Normals => {
+                writeln!(writer, "NORMALS {} float", array.name)?;
+                write_values(&mut writer, encoding, values, components)?;
+            }
+        }
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn write_values(
+    writer: &mut impl Write,
+    encoding: VtkEncoding,
+    values: &[PointFieldDatum],
+    components: usize,
+) -> Result<(), PointRainIOError> {
+    match encoding {
+        VtkEncoding::Ascii => {
+            for tuple in values.chunks(components) {
+                let tuple: Vec<_> = tuple.iter().map(|v| v.to_string()).collect();
+                writeln!(writer, "{}", tuple.join(" "))?;
+            }
+        }
+        VtkEncoding::Binary => {
+            let mut bytes = Vec::with_capacity(4 * values.len());
+            for v in values {
+                v.put_be(&mut bytes);
+            }
+            writer.write_all(&bytes)?;
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+//! VTK XML PolyData (`.vtp`) files with ASCII data arrays.
+
+mod read;
+mod write;
+
+pub use read::vtp_read;
+pub use write::vtp_write;
@@ -0,0 +1,80 @@
+use std::{fs, path::Path};
+
+use pointrain_core::pc::PointCloudBase;
+use roxmltree::Node;
+
+use crate::{
+    error::PointRainIOError,
+    field::{PointFieldDatum, PointFieldType},
+    point::PointReadable,
+    vtk::{array_columns, read_cloud, ArrayKind},
+};
+
+/// Reads the points and point data of the first piece of a PolyData file.
+///
+/// Only `format="ascii"` data arrays are supported. Point data arrays are mapped to point
+/// columns by name, as well as the arrays marked as the `Normals` of the point data.
+pub fn vtp_read<PC>(f: impl AsRef<Path>) -> Result<PC, PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let xml = fs::read_to_string(f)?;
+    let doc = roxmltree::Document::parse(&xml).map_err(|e| format!("Invalid XML: {e}"))?;
+
+    let root = doc.root_element();
+    if root.attribute("type") != Some("PolyData") {
+        return Err("Only PolyData files are supported".to_string().into());
+    }
+    let piece = descendant(root, "Piece").ok_or_else(|| "No Piece".to_string())?;
+
+    let points = descendant(piece, "Points")
+        .and_then(|p| descendant(p, "DataArray"))
+        .ok_or_else(|| "No Points".to_string())?;
+    let positions = read_array(points)?;
+
+    let mut arrays = Vec::new();
+    if let Some(point_data) = descendant(piece, "PointData") {
+        let normals = point_data.attribute("Normals");
+
+        for array in point_data
+            .children()
+            .filter(|n| n.has_tag_name("DataArray"))
+        {
+            let name = array.attribute("Name").unwrap_or_default();
+            let components = array
+                .attribute("NumberOfComponents")
+                .map_or(Ok(1), str::parse)?;
+            let kind = if normals == Some(name) {
+                ArrayKind::Normals
+            } else {
+                ArrayKind::Scalars
+            };
+
+            if let Some(columns) = array_columns(name, kind, components) {
+                arrays.push((columns, read_array(array)?));
+            }
+        }
+    }
+
+    read_cloud(&positions, &arrays)
+}
+
+fn descendant<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.descendants().find(|n| n.has_tag_name(name))
+}
+
+fn read_array(array: Node) -> Result<Vec<PointFieldDatum>, PointRainIOError> {
+    let format = array.attribute("format").unwrap_or("ascii");
+    if format != "ascii" {
+        return Err(format!("Unsupported data array format: {format}").into());
+    }
+    let datatype = PointFieldType::from_vtk_type(array.attribute("type").unwrap_or("Float32"))?;
+
+    array
+        .text()
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|v| Ok(PointFieldDatum::parse(v, datatype)?))
+        .collect()
+}
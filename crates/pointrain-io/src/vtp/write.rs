@@ -0,0 +1,120 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+
+use crate::{
+    error::PointRainIOError,
+    field::PointFieldDatum,
+    mesh::WritableColumns,
+    point::PointWritable,
+    vtk::{point_arrays, ArrayKind},
+};
+
+/// Writes the points as a single piece with one vertex cell per point and their attributes
+/// as point data.
+pub fn vtp_write<PC>(f: impl AsRef<Path>, pc: &PC) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let columns = WritableColumns::new(PC::Point::columns())?;
+    let arrays = point_arrays(&columns);
+    let mut writer = BufWriter::new(File::create(f)?);
+    let n = pc.len();
+
+    let mut positions = Vec::with_capacity(3 * n);
+    let mut values = vec![Vec::new(); arrays.len()];
+    let mut data = Vec::new();
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+
+        positions.extend(columns.xyz.map(|i| data[i]));
+        for (array, values) in arrays.iter().zip(&mut values) {
+            values.extend(array.columns.iter().map(|i| data[*i]));
+        }
+    }
+
+    writeln!(writer, r#"<?xml version="1.0"?>"#)?;
+    writeln!(
+        writer,
+        r#"<VTKFile type="PolyData" version="0.1" byte_order="LittleEndian">"#
+    )?;
+    writeln!(writer, "  <PolyData>")?;
+    writeln!(
+        writer,
+        r#"    <Piece NumberOfPoints="{n}" NumberOfVerts="{n}" NumberOfLines="0" NumberOfStrips="0" NumberOfPolys="0">"#
+    )?;
+
+    let scalars = arrays.iter().find(|a| a.kind == ArrayKind::Scalars);
+    let normals = arrays.iter().find(|a| a.kind == ArrayKind::Normals);
+    write!(writer, "      <PointData")?;
+    if let Some(scalars) = scalars {
+        write!(writer, r#" Scalars="{}""#, scalars.name)?;
+    }
+    if let Some(normals) = normals {
+        write!(writer, r#" Normals="{}""#, normals.name)?;
+    }
+    writeln!(writer, ">")?;
+    for (array, values) in arrays.iter().zip(&values) {
+        let datatype = match array.kind {
+            ArrayKind::Colors => "UInt8",
+            ArrayKind::Scalars | ArrayKind::Normals => "Float32",
+        };
+        write_array(
+            &mut writer,
+            datatype,
+            Some(array.name),
+            array.columns.len(),
+            values,
+        )?;
+    }
+    writeln!(writer, "      </PointData>")?;
+
+    writeln!(writer, "      <Points>")?;
+    write_array(&mut writer, "Float32", None, 3, &positions)?;
+    writeln!(writer, "      </Points>")?;
+
+    let connectivity: Vec<_> = (0..n as i32).map(PointFieldDatum::I32).collect();
+    let offsets: Vec<_> = (1..=n as i32).map(PointFieldDatum::I32).collect();
+    writeln!(writer, "      <Verts>")?;
+    write_array(&mut writer, "Int32", Some("connectivity"), 1, &connectivity)?;
+    write_array(&mut writer, "Int32", Some("offsets"), 1, &offsets)?;
+    writeln!(writer, "      </Verts>")?;
+
+    writeln!(writer, "    </Piece>")?;
+    writeln!(writer, "  </PolyData>")?;
+    writeln!(writer, "</VTKFile>")?;
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn write_array(
+    writer: &mut impl Write,
+    datatype: &str,
+    name: Option<&str>,
+    components: usize,
+    values: &[PointFieldDatum],
+) -> Result<(), PointRainIOError> {
+    write!(writer, r#"        <DataArray type="{datatype}""#)?;
+    if let Some(name) = name {
+        write!(writer, r#" Name="{name}""#)?;
+    }
+    writeln!(
+        writer,
+        r#" NumberOfComponents="{components}" format="ascii">"#
+    )?;
+    for tuple in values.chunks(components.max(1)) {
+        let tuple: Vec<_> = tuple.iter().map(|v| v.to_string()).collect();
+        writeln!(writer, "          {}", tuple.join(" "))?;
+    }
+    writeln!(writer, "        </DataArray>")?;
+
+    Ok(())
+}
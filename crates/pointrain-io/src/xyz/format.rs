@@ -0,0 +1,90 @@
+use crate::point::canonical_column_name;
+
+/// Layout of an ASCII column file (`.xyz`, `.txt`, `.csv`, `.pts`, ...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XyzFormat {
+    /// Column separator. `None` splits on any run of whitespace.
+    pub delimiter: Option<char>,
+    /// Whether the first non-comment line holds the column names.
+    pub header: bool,
+    /// Canonical names of the columns in file order (see [`crate::point`]).
+    /// When `None`, the header names are used if there is a header, otherwise `x y z`.
+    /// Columns with unknown names are ignored on read.
+    pub columns: Option<Vec<String>>,
+    /// Lines starting with this character are skipped.
+    pub comment: Option<char>,
+}
+
+impl Default for XyzFormat {
+    fn default() -> Self {
+        Self {
+            delimiter: None,
+            header: false,
+            columns: None,
+            comment: Some('#'),
+        }
+    }
+}
+
+impl XyzFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Comma separated values with a header row.
+    pub fn csv() -> Self {
+        Self {
+            delimiter: Some(','),
+            header: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    pub fn with_columns<S: AsRef<str>>(mut self, columns: impl IntoIterator<Item = S>) -> Self {
+        self.columns = Some(
+            columns
+                .into_iter()
+                .map(|c| canonical_column_name(c.as_ref()))
+                .collect(),
+        );
+        self
+    }
+
+    pub fn xyz() -> Self {
+        Self::new().with_columns(["x", "y", "z"])
+    }
+
+    pub fn xyzi() -> Self {
+        Self::new().with_columns(["x", "y", "z", "intensity"])
+    }
+
+    pub fn xyzrgb() -> Self {
+        Self::new().with_columns(["x", "y", "z", "r", "g", "b"])
+    }
+
+    pub fn xyzn() -> Self {
+        Self::new().with_columns(["x", "y", "z", "nx", "ny", "nz"])
+    }
+
+    pub(crate) fn split<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        let line = line.trim();
+        match self.delimiter {
+            Some(d) => line.split(d).map(str::trim).collect(),
+            None => line.split_whitespace().collect(),
+        }
+    }
+
+    pub(crate) fn is_comment(&self, line: &str) -> bool {
+        self.comment.is_some_and(|c| line.starts_with(c))
+    }
+}
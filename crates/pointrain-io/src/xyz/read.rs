@@ -0,0 +1,126 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use pointrain_core::pc::{PointCloud, PointCloudBase};
+
+use super::XyzFormat;
+use crate::{
+    error::PointRainIOError,
+    field::{PointField, PointFieldDatum, PointFieldType},
+    point::{canonical_column_name, PointReadable},
+    ReadOptions, ReadReport,
+};
+
+pub fn xyz_read(f: impl AsRef<Path>) -> Result<PointCloud, PointRainIOError> {
+    xyz_read_with_options(f, &ReadOptions::default()).map(|(pc, _)| pc)
+}
+
+pub fn xyz_read_with_options(
+    f: impl AsRef<Path>,
+    options: &ReadOptions,
+) -> Result<(PointCloud, ReadReport), PointRainIOError> {
+    xyz_read_with_format(f, &XyzFormat::default(), options)
+}
+
+pub fn xyz_read_with_format<PC>(
+    f: impl AsRef<Path>,
+    format: &XyzFormat,
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    xyz_read_from(BufReader::new(File::open(f)?), format, options)
+}
+
+/// Reads an ASCII column stream, shared by the file and the async readers.
+pub(crate) fn xyz_read_from<PC>(
+    mut reader: impl BufRead,
+    format: &XyzFormat,
+    options: &ReadOptions,
+) -> Result<(PC, ReadReport), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let mut line = String::new();
+    let mut pc = PC::new();
+    let mut report = ReadReport::default();
+    let mut index = 0;
+    let mut columns = format.columns.clone();
+
+    if format.header {
+        while reader.read_line(&mut line)? > 0 && format.is_comment(&line) {
+            line.clear();
+        }
+
+        let names = format.split(&line);
+        columns.get_or_insert_with(|| names.iter().map(|n| canonical_column_name(n)).collect());
+        line.clear();
+    }
+
+    let fields: Vec<_> = columns
+        .unwrap_or_else(|| vec!["x".into(), "y".into(), "z".into()])
+        .into_iter()
+        .map(|name| PointField {
+            name,
+            datatype: PointFieldType::F64,
+            count: 1,
+        })
+        .collect();
+    let func = PC::Point::read_data_func(&fields)?;
+
+    while reader.read_line(&mut line)? > 0 {
+        if format.is_comment(&line) {
+            line.clear();
+            continue;
+        }
+
+        if line.trim().is_empty() && !options.is_strict() {
+            report.blank_lines += 1;
+            line.clear();
+            continue;
+        }
+
+        match xyz_read_datum(format, &fields, &line).and_then(|data| func(&data)) {
+            Ok(p) => {
+                pc.push(p);
+            }
+            Err(e) => options.recover(&mut report, &mut pc, index, e)?,
+        }
+
+        index += 1;
+        line.clear();
+    }
+
+    report.read_points = pc.len();
+
+    Ok((pc, report))
+}
+
+fn xyz_read_datum(
+    format: &XyzFormat,
+    fields: &[PointField],
+    line: &str,
+) -> Result<Vec<PointFieldDatum>, PointRainIOError> {
+    let tokens = format.split(line);
+    if tokens.len() != fields.len() {
+        return Err(PointRainIOError::Error {
+            msg: format!(
+                "Invalid number of tokens in line: expected {}, got {}",
+                fields.len(),
+                tokens.len()
+            )
+            .into(),
+        });
+    }
+
+    Ok(tokens
+        .iter()
+        .map(|token| PointFieldDatum::parse_number(token))
+        .collect::<Result<_, _>>()?)
+}
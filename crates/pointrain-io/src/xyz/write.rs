@@ -0,0 +1,91 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use pointrain_core::pc::PointCloudBase;
+
+use super::XyzFormat;
+use crate::{error::PointRainIOError, point::PointWritable};
+
+pub fn xyz_write<PC>(f: impl AsRef<Path>, pc: &PC) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    xyz_write_with_format(f, pc, &XyzFormat::default())
+}
+
+/// Writes `pc` with the layout of `format`.
+///
+/// If `format.columns` is given, only those columns are written in that order.
+pub fn xyz_write_with_format<PC>(
+    f: impl AsRef<Path>,
+    pc: &PC,
+    format: &XyzFormat,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let mut writer = BufWriter::new(File::create(f)?);
+    xyz_write_to(&mut writer, pc, format)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Writes `pc` to a stream, shared by the file and the async writers.
+pub(crate) fn xyz_write_to<PC>(
+    writer: &mut impl Write,
+    pc: &PC,
+    format: &XyzFormat,
+) -> Result<(), PointRainIOError>
+where
+    PC: PointCloudBase,
+    PC::Point: PointWritable,
+{
+    let all_columns = PC::Point::columns();
+    let indices = match &format.columns {
+        Some(columns) => columns
+            .iter()
+            .map(|c| {
+                all_columns
+                    .iter()
+                    .position(|a| a == c)
+                    .ok_or_else(|| format!("Point type has no column: {c}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => (0..all_columns.len()).collect(),
+    };
+    let delimiter = format.delimiter.unwrap_or(' ');
+
+    if format.header {
+        xyz_write_line(writer, delimiter, indices.iter().map(|&i| all_columns[i]))?;
+    }
+
+    let mut data = Vec::with_capacity(all_columns.len());
+    for p in pc.iter() {
+        data.clear();
+        PC::Point::write_data(&p, &mut data);
+        xyz_write_line(writer, delimiter, indices.iter().map(|&i| data[i]))?;
+    }
+
+    Ok(())
+}
+
+fn xyz_write_line<T: std::fmt::Display>(
+    writer: &mut impl Write,
+    delimiter: char,
+    values: impl Iterator<Item = T>,
+) -> Result<(), PointRainIOError> {
+    for (i, v) in values.enumerate() {
+        if i > 0 {
+            write!(writer, "{delimiter}")?;
+        }
+        write!(writer, "{v}")?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Helpers shared by the integration tests.
+
+// Each test binary uses only some of the helpers.
+#![allow(dead_code)]
+
+use pointrain_core::{
+    pc::PointCloudRgbNormal,
+    point::PointRgbNormal,
+    types::{Normal, Position, Rgb},
+};
+use tempfile::TempDir;
+
+/// A new directory for the files written by one test, removed when dropped.
+pub fn temp_dir() -> TempDir {
+    tempfile::Builder::new()
+        .prefix("pointrain_test_")
+        .tempdir()
+        .unwrap()
+}
+
+/// `n` points `step` radians apart on a helix of radius 3, with all attributes set.
+///
+/// Colors are unique for `n <= 65536` and encode the index as `r + 256 * g`, to match
+/// points up after a codec reorders them.
+pub fn helix_cloud(n: usize, step: f32) -> PointCloudRgbNormal {
+    (0..n)
+        .map(|i| {
+            let a = i as f32 * step;
+            PointRgbNormal {
+                position: Position::new(a.cos() * 3., a.sin() * 3., a * 0.05 - 1.),
+                color: Rgb::new((i % 256) as u8, (i / 256) as u8, 7),
+                normal: Normal::new(a.cos(), a.sin(), 0.),
+                curvature: a * 0.1,
+            }
+        })
+        .collect()
+}
+
+/// A LAS point record: raw integer coordinates, intensity and 16-bit color.
+pub struct LasPoint {
+    pub xyz: [i32; 3],
+    pub intensity: u16,
+    pub rgb: [u16; 3],
+}
+
+/// A LAS 1.2 file of point `format` 0 to 3 with a scale of 0.5 and an offset of `(1, 2, 3)`.
+///
+/// A variable length record precedes the points, and every record is padded by `extra` bytes.
+pub fn las_bytes(format: u8, points: &[LasPoint], extra: usize) -> Vec<u8> {
+    let record_len = [20, 28, 26, 34][usize::from(format)] + extra;
+    let vlr = [[0; 54].as_slice(), b"\x01\x02\x03\x04"].concat();
+
+    let mut bytes = vec![0; 227];
+    bytes[..4].copy_from_slice(b"LASF");
+    bytes[24..26].copy_from_slice(&[1, 2]);
+    bytes[94..96].copy_from_slice(&227u16.to_le_bytes());
+    bytes[96..100].copy_from_slice(&(227 + vlr.len() as u32).to_le_bytes());
+    bytes[100..104].copy_from_slice(&1u32.to_le_bytes());
+    bytes[104] = format;
+    bytes[105..107].copy_from_slice(&(record_len as u16).to_le_bytes());
+    bytes[107..111].copy_from_slice(&(points.len() as u32).to_le_bytes());
+    for a in 0..3 {
+        bytes[131 + 8 * a..139 + 8 * a].copy_from_slice(&0.5f64.to_le_bytes());
+        bytes[155 + 8 * a..163 + 8 * a].copy_from_slice(&(a as f64 + 1.).to_le_bytes());
+    }
+    bytes.extend(vlr);
+
+    for p in points {
+        let mut record = vec![0; record_len];
+        for (a, v) in p.xyz.iter().enumerate() {
+            record[4 * a..4 * a + 4].copy_from_slice(&v.to_le_bytes());
+        }
+        record[12..14].copy_from_slice(&p.intensity.to_le_bytes());
+        let color_at = match format {
+            2 => Some(20),
+            3 => Some(28),
+            _ => None,
+        };
+        if let Some(at) = color_at {
+            for (c, v) in p.rgb.iter().enumerate() {
+                record[at + 2 * c..at + 2 * c + 2].copy_from_slice(&v.to_le_bytes());
+            }
+        }
+        bytes.extend(record);
+    }
+    bytes
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "arrow")]
+
+mod common;
+
+use std::sync::Arc;
+
+use arrow_array::{Float64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+use pointrain_core::{
+    pc::{
+        PointCloud, PointCloudBase, PointCloudIntensity, PointCloudRgbNormal, PointCloudWithColor,
+        PointCloudWithNormal,
+    },
+    point::PointRgbNormal,
+    types::{Normal, Position, Rgb},
+};
+use pointrain_io::{from_record_batch, parquet_read, parquet_write, to_record_batch};
+
+fn rgb_normal_cloud() -> PointCloudRgbNormal {
+    let mut pc = PointCloudRgbNormal::new();
+    for i in 0..5u8 {
+        let v = f32::from(i);
+        pc.push(PointRgbNormal {
+            position: Position::new(v, 2. * v, -v),
+            color: Rgb::new(i, 50 * i, 255 - i),
+            normal: Normal::new(0., 1., 0.),
+            curvature: 0.5 * v,
+        });
+    }
+    pc
+}
+
+#[test]
+fn test_record_batch_round_trip() {
+    let pc = rgb_normal_cloud();
+
+    let batch = to_record_batch(&pc).unwrap();
+    assert_eq!(batch.num_rows(), 5);
+    assert_eq!(
+        batch.schema().field_with_name("r").unwrap().data_type(),
+        &DataType::UInt8
+    );
+    assert_eq!(
+        batch.schema().field_with_name("x").unwrap().data_type(),
+        &DataType::Float32
+    );
+
+    let read: PointCloudRgbNormal = from_record_batch(&batch).unwrap();
+    assert_eq!(read.positions(), pc.positions());
+    assert_eq!(read.colors(), pc.colors());
+    assert_eq!(read.normals(), pc.normals());
+    assert_eq!(read.curvatures(), pc.curvatures());
+
+    assert!(from_record_batch::<PointCloudIntensity>(&batch).is_err());
+}
+
+#[test]
+fn test_from_record_batch_foreign_schema() {
+    let schema = Schema::new(vec![
+        Field::new("X", DataType::Float64, false),
+        Field::new("Y", DataType::Float64, false),
+        Field::new("Z", DataType::Float64, true),
+        Field::new("label", DataType::Utf8, false),
+    ]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Float64Array::from(vec![1., 2.])),
+            Arc::new(Float64Array::from(vec![3., 4.])),
+            Arc::new(Float64Array::from(vec![Some(5.), None])),
+            Arc::new(StringArray::from(vec!["a", "b"])),
+        ],
+    )
+    .unwrap();
+
+    let pc: PointCloud = from_record_batch(&batch).unwrap();
+    assert_eq!(pc.positions()[0], Position::new(1., 3., 5.));
+    assert!(pc.positions()[1].z.is_nan());
+}
+
+#[test]
+fn test_parquet_write_read() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("parquet_write_read.parquet");
+    let pc = rgb_normal_cloud();
+
+    parquet_write(&path, &pc).unwrap();
+    let read: PointCloudRgbNormal = parquet_read(&path).unwrap();
+
+    assert_eq!(read.positions(), pc.positions());
+    assert_eq!(read.colors(), pc.colors());
+    assert_eq!(read.normals(), pc.normals());
+}
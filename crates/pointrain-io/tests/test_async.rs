@@ -0,0 +1,94 @@
+#![cfg(feature = "async")]
+
+use pointrain_core::{
+    pc::{
+        PointCloudBase, PointCloudNormal, PointCloudRgb, PointCloudRgba, PointCloudWithAlpha,
+        PointCloudWithColor, PointCloudWithNormal,
+    },
+    types::Position,
+};
+use pointrain_io::{
+    pcd_read, pcd_read_async, pcd_read_async_with_options, pcd_read_with_options, pcd_write_async,
+    ply_read, ply_read_async, ply_write_async, xyz_read_async_with_format, xyz_read_with_format,
+    xyz_write_async_with_format, ReadOptions, XyzFormat,
+};
+use tokio::fs::File;
+
+#[tokio::test]
+async fn test_pcd_read_async() {
+    let file = File::open("tests/data/pcd/test_binary.pcd").await.unwrap();
+    let pc: PointCloudNormal = pcd_read_async(file).await.unwrap();
+    let expected: PointCloudNormal = pcd_read("tests/data/pcd/test_binary.pcd").unwrap();
+
+    assert_eq!(pc.positions(), expected.positions());
+    assert_eq!(pc.normals(), expected.normals());
+}
+
+#[tokio::test]
+async fn test_pcd_read_async_lenient() {
+    let path = "tests/data/pcd/test_ascii_broken.pcd";
+    let file = File::open(path).await.unwrap();
+    let (pc, report) =
+        pcd_read_async_with_options::<PointCloudNormal>(file, &ReadOptions::lenient())
+            .await
+            .unwrap();
+    let (expected, expected_report) =
+        pcd_read_with_options::<PointCloudNormal>(path, &ReadOptions::lenient()).unwrap();
+
+    assert_eq!(pc.positions(), expected.positions());
+    assert_eq!(report, expected_report);
+}
+
+#[tokio::test]
+async fn test_pcd_write_read_async() {
+    let pc: PointCloudRgba = pcd_read("tests/data/pcd/test_rgba.pcd").unwrap();
+
+    let mut data = Vec::new();
+    pcd_write_async(&mut data, &pc).await.unwrap();
+    let written: PointCloudRgba = pcd_read_async(data.as_slice()).await.unwrap();
+    assert_eq!(written.positions(), pc.positions());
+    assert_eq!(written.colors(), pc.colors());
+    assert_eq!(written.alphas(), pc.alphas());
+
+    let pc: PointCloudNormal = pcd_read("tests/data/pcd/test_ascii.pcd").unwrap();
+    let mut data = Vec::new();
+    pcd_write_async(&mut data, &pc).await.unwrap();
+    let written: PointCloudNormal = pcd_read_async(data.as_slice()).await.unwrap();
+    assert_eq!(written.normals(), pc.normals());
+    assert_eq!(written.curvatures(), pc.curvatures());
+}
+
+#[tokio::test]
+async fn test_ply_write_read_async() {
+    let pc: PointCloudRgba = ply_read("tests/data/ply/test_color.ply").unwrap();
+
+    let mut data = Vec::new();
+    ply_write_async(&mut data, &pc).await.unwrap();
+    let written: PointCloudRgba = ply_read_async(data.as_slice()).await.unwrap();
+    assert_eq!(written.positions(), pc.positions());
+    assert_eq!(written.colors(), pc.colors());
+    assert_eq!(written.alphas(), pc.alphas());
+}
+
+#[tokio::test]
+async fn test_xyz_write_read_async() {
+    let format = XyzFormat::csv();
+    let (pc, _): (PointCloudRgb, _) = xyz_read_with_format(
+        "tests/data/xyz/test_rgb.csv",
+        &format,
+        &ReadOptions::default(),
+    )
+    .unwrap();
+
+    let mut data = Vec::new();
+    xyz_write_async_with_format(&mut data, &pc, &format)
+        .await
+        .unwrap();
+    let (written, _): (PointCloudRgb, _) =
+        xyz_read_async_with_format(data.as_slice(), &format, &ReadOptions::default())
+            .await
+            .unwrap();
+    assert_eq!(written.positions(), pc.positions());
+    assert_eq!(written.colors(), pc.colors());
+    assert_eq!(written.positions()[0], Position::new(0.5, 1.5, 2.5));
+}
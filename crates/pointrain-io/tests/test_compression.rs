@@ -0,0 +1,90 @@
+mod common;
+
+use pointrain_core::{
+    pc::{
+        PointCloud, PointCloudBase, PointCloudIntensity, PointCloudRgbNormal, PointCloudWithColor,
+        PointCloudWithIntensity, PointCloudWithNormal,
+    },
+    point::{Point, PointIntensity},
+    types::Position,
+};
+use pointrain_io::compression::{decode, encode};
+
+#[test]
+fn test_compression_roundtrip() {
+    let pc = common::helix_cloud(1000, 0.01);
+    let resolution = 0.01;
+
+    let data = encode(&pc, resolution).unwrap();
+    let decoded: PointCloudRgbNormal = decode(&data).unwrap();
+    assert_eq!(decoded.len(), pc.len());
+    assert!(data.len() < pc.len() * 12);
+
+    for p in decoded.iter() {
+        let i = p.color.x as usize + 256 * p.color.y as usize;
+        assert!((p.position - pc.positions()[i]).abs().max() <= resolution as f32 / 2. + 1e-5);
+        assert_eq!(p.color, &pc.colors()[i]);
+        assert_eq!(p.normal, &pc.normals()[i]);
+        assert_eq!(*p.curvature, pc.curvatures()[i]);
+    }
+}
+
+#[test]
+fn test_compression_shared_voxels() {
+    let pc: PointCloudIntensity = (0..20)
+        .map(|i| PointIntensity {
+            position: Position::new((i % 2) as f32, 0., 0.),
+            intensity: i as f32,
+        })
+        .collect();
+
+    let decoded: PointCloudIntensity = decode(&encode(&pc, 0.5).unwrap()).unwrap();
+    assert_eq!(decoded.len(), 20);
+    assert_eq!(decoded.positions()[0], Position::new(0.25, 0.25, 0.25));
+    assert_eq!(decoded.positions()[19], Position::new(1.25, 0.25, 0.25));
+    // Points of a voxel keep their order.
+    let expected: Vec<_> = (0..20)
+        .step_by(2)
+        .chain((1..20).step_by(2))
+        .map(|i| i as f32)
+        .collect();
+    assert_eq!(decoded.intensities(), expected);
+}
+
+#[test]
+fn test_compression_empty_and_single() {
+    let decoded: PointCloud = decode(&encode(&PointCloud::new(), 1.).unwrap()).unwrap();
+    assert!(decoded.is_empty());
+
+    let mut pc = PointCloud::new();
+    pc.push(Point {
+        position: Position::new(1., 2., 3.),
+    });
+    let decoded: PointCloud = decode(&encode(&pc, 1.).unwrap()).unwrap();
+    assert_eq!(decoded.positions(), &[Position::new(1.5, 2.5, 3.5)]);
+}
+
+#[test]
+fn test_compression_invalid() {
+    let pc = common::helix_cloud(1000, 0.01);
+    assert!(encode(&pc, 0.).is_err());
+    assert!(encode(&pc, f64::NAN).is_err());
+    assert!(encode(&pc, 1e-12).is_err());
+
+    let data = encode(&pc, 0.01).unwrap();
+    assert!(decode::<PointCloud>(&data[..data.len() - 10]).is_err());
+    assert!(decode::<PointCloud>(b"DRACO").is_err());
+    // The declared number of points must not be trusted for allocations.
+    let mut huge = data.clone();
+    huge[7..15].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(decode::<PointCloud>(&huge).is_err());
+    // The point type needs attributes that were not stored.
+    let data = encode(
+        &PointCloud::from_iter(pc.iter().map(|p| Point {
+            position: *p.position,
+        })),
+        0.01,
+    )
+    .unwrap();
+    assert!(decode::<PointCloudRgbNormal>(&data).is_err());
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "draco")]
+
+mod common;
+
+use pointrain_core::{
+    pc::{
+        PointCloud, PointCloudBase, PointCloudRgb, PointCloudRgbNormal, PointCloudRgba,
+        PointCloudWithAlpha, PointCloudWithColor, PointCloudWithNormal,
+    },
+    point::{PointRgbNormal, PointRgba},
+    types::{Normal, Position, Rgb},
+};
+use pointrain_io::{draco_decode, draco_encode, draco_read, draco_write, DracoOptions};
+
+#[test]
+fn test_draco_write_read() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("draco_write_read.drc");
+    let pc = common::helix_cloud(200, 0.1);
+
+    draco_write(&path, &pc, &DracoOptions::default()).unwrap();
+    let decoded: PointCloudRgbNormal = draco_read(&path).unwrap();
+
+    assert_eq!(decoded.len(), pc.len());
+    assert_eq!(decoded.colors(), pc.colors());
+    // The bounding cube is 6 wide, quantized with 11 bits.
+    let max_error = 6. / 2047. / 2. + 1e-5;
+    for (a, b) in decoded.positions().iter().zip(pc.positions()) {
+        assert!((a - b).abs().max() <= max_error, "{a} != {b}");
+    }
+    // 8 bit octahedral coordinates are within a few degrees.
+    for (a, b) in decoded.normals().iter().zip(pc.normals()) {
+        assert!((a.norm() - 1.).abs() < 1e-5);
+        assert!(a.angle(b) < 0.05, "{a} != {b}");
+    }
+}
+
+#[test]
+fn test_draco_normals() {
+    // Directions all over the sphere, including the equator and the poles where octahedral
+    // coordinates are on the border, each predicted from the previous one.
+    let mut normals = vec![Normal::new(0., 0., 1.), Normal::new(0., 0., -1.)];
+    for z in [-0.9, -0.5, 0., 0.5, 0.9] {
+        for i in 0..200 {
+            let a = i as f32 * 0.1;
+            let r = (1f32 - z * z).sqrt();
+            normals.push(Normal::new(a.cos() * r, a.sin() * r, z));
+        }
+    }
+    let pc: PointCloudRgbNormal = normals
+        .iter()
+        .enumerate()
+        .map(|(i, &normal)| PointRgbNormal {
+            position: Position::new(i as f32, 0., 0.),
+            color: Rgb::new(0, 0, 0),
+            normal,
+            curvature: 0.,
+        })
+        .collect();
+
+    let decoded: PointCloudRgbNormal =
+        draco_decode(&draco_encode(&pc, &DracoOptions::default()).unwrap()).unwrap();
+    for (a, b) in decoded.normals().iter().zip(pc.normals()) {
+        assert!(a.angle(b) < 0.05, "{a} != {b}");
+    }
+}
+
+#[test]
+fn test_draco_lossless_positions() {
+    let pc = common::helix_cloud(200, 0.1);
+    let options = DracoOptions {
+        position_quantization_bits: 0,
+        ..Default::default()
+    };
+
+    let decoded: PointCloud = draco_decode(&draco_encode(&pc, &options).unwrap()).unwrap();
+    assert_eq!(decoded.positions(), pc.positions());
+}
+
+#[test]
+fn test_draco_alpha() {
+    let pc: PointCloudRgba = (0..10)
+        .map(|i| PointRgba {
+            position: Position::new(i as f32, 0., 0.),
+            color: Rgb::new(1, 2, 3),
+            alpha: i * 20,
+        })
+        .collect();
+
+    let decoded: PointCloudRgba =
+        draco_decode(&draco_encode(&pc, &DracoOptions::default()).unwrap()).unwrap();
+    assert_eq!(decoded.alphas(), pc.alphas());
+    assert_eq!(decoded.colors(), pc.colors());
+}
+
+#[test]
+fn test_draco_invalid() {
+    let pc = common::helix_cloud(200, 0.1);
+    let options = DracoOptions {
+        normal_quantization_bits: 31,
+        ..Default::default()
+    };
+    assert!(draco_encode(&pc, &options).is_err());
+    assert!(draco_encode(&PointCloud::new(), &DracoOptions::default()).is_err());
+
+    let data = draco_encode(&pc, &DracoOptions::default()).unwrap();
+    assert!(draco_decode::<PointCloud>(&data[..data.len() / 2]).is_err());
+    assert!(draco_decode::<PointCloud>(b"PLY\n").is_err());
+}
+
+#[test]
+fn test_draco_read_kd_tree() {
+    let pc: PointCloudRgb = draco_read("tests/data/draco/test_kd_tree.drc").unwrap();
+    let expected: Vec<([f32; 3], [u8; 3])> =
+        std::fs::read_to_string("tests/data/draco/test_kd_tree.txt")
+            .unwrap()
+            .lines()
+            .map(|l| {
+                let v: Vec<f32> = l.split(' ').map(|v| v.parse().unwrap()).collect();
+                ([v[0], v[1], v[2]], [v[3], v[4], v[5]].map(|c| c as u8))
+            })
+            .collect();
+    assert_eq!(pc.len(), expected.len());
+
+    // The kd-tree encoding reorders points, match them up by color and position.
+    let max_error = 4. / 255. / 2. + 1e-5;
+    for (position, color) in pc.positions().iter().zip(pc.colors()) {
+        assert!(expected.iter().any(|(p, c)| {
+            c == &[color.x, color.y, color.z]
+                && (0..3).all(|i| (p[i] - position[i]).abs() <= max_error)
+        }));
+    }
+}
+
+#[test]
+fn test_draco_read_tagged_with_metadata() {
+    let pc: PointCloud = draco_read("tests/data/draco/test_tagged.drc").unwrap();
+
+    assert_eq!(pc.len(), 20);
+    let max_error = 9.5 / 1023. / 2. + 1e-5;
+    for (i, p) in pc.positions().iter().enumerate() {
+        let expected = Position::new(
+            0.5 * i as f32,
+            (i as f32).sin(),
+            -1. + 0.25 * (i % 4) as f32,
+        );
+        assert!((p - expected).abs().max() <= max_error, "{p} != {expected}");
+    }
+}
@@ -0,0 +1,63 @@
+use pointrain_core::{
+    pc::{
+        PointCloudBase, PointCloudIntensity, PointCloudRgb, PointCloudWithColor,
+        PointCloudWithIntensity,
+    },
+    types::{Position, Rgb},
+};
+use pointrain_io::{e57_read, e57_read_scans};
+
+fn assert_position_eq(a: &Position, b: &Position) {
+    assert!((a - b).norm() < 1e-5, "{a:?} != {b:?}");
+}
+
+#[test]
+fn test_e57_read_scans() {
+    let scans = e57_read_scans::<PointCloudIntensity>("tests/data/e57/test_scans.e57").unwrap();
+
+    assert_eq!(scans.len(), 2);
+    assert_eq!(scans[0].name.as_deref(), Some("first"));
+    assert_eq!(scans[1].guid.as_deref(), Some("scan-1"));
+
+    // The third point of the first scan is flagged as invalid.
+    let first = &scans[0].pc;
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.positions()[1], Position::new(2., 0., 0.25));
+    assert_eq!(first.intensities(), &[0.1, 0.5]);
+
+    // Spherical coordinates, spread over two data packets.
+    let second = &scans[1].pc;
+    assert_eq!(second.len(), 3);
+    assert_position_eq(&second.positions()[0], &Position::new(2., 0., 0.));
+    assert_position_eq(&second.positions()[1], &Position::new(0., 1.5, 0.));
+    assert_position_eq(&second.positions()[2], &Position::new(0., 0., 3.));
+}
+
+#[test]
+fn test_e57_read_merged() {
+    let pc = e57_read::<PointCloudIntensity>("tests/data/e57/test_scans.e57").unwrap();
+
+    assert_eq!(pc.len(), 5);
+    assert_eq!(pc.positions()[0], Position::new(1., 0.5, 0.));
+    assert_position_eq(&pc.positions()[2], &Position::new(10., 2., 0.));
+    assert_position_eq(&pc.positions()[3], &Position::new(8.5, 0., 0.));
+    assert_position_eq(&pc.positions()[4], &Position::new(10., 0., 3.));
+    assert_eq!(pc.intensities()[4], 0.6);
+}
+
+#[test]
+fn test_e57_read_color() {
+    let pc = e57_read::<PointCloudRgb>("tests/data/e57/test_scans.e57").unwrap();
+
+    // 8-bit colors of the first scan and 16-bit colors of the second one.
+    assert_eq!(
+        pc.colors(),
+        &[
+            Rgb::new(255, 0, 0),
+            Rgb::new(0, 255, 0),
+            Rgb::new(255, 255, 255),
+            Rgb::new(128, 128, 128),
+            Rgb::new(0, 0, 0),
+        ]
+    );
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use pointrain_core::{
+    pc::{PointCloud, PointCloudBase, PointCloudRgbNormal},
+    point::PointRgbNormal,
+    types::{Normal, Position, Rgb},
+};
+use pointrain_io::glb_write;
+use serde_json::Value;
+
+fn read_glb(path: &std::path::Path) -> (Value, Vec<u8>) {
+    let bytes = std::fs::read(path).unwrap();
+    let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+
+    assert_eq!(&bytes[..4], b"glTF");
+    assert_eq!(u32_at(4), 2);
+    assert_eq!(u32_at(8) as usize, bytes.len());
+
+    let json_len = u32_at(12) as usize;
+    assert_eq!(&bytes[16..20], b"JSON");
+    assert_eq!(json_len % 4, 0);
+    let json = serde_json::from_slice(&bytes[20..20 + json_len]).unwrap();
+
+    let bin_start = 20 + json_len;
+    let bin_len = u32_at(bin_start) as usize;
+    assert_eq!(&bytes[bin_start + 4..bin_start + 8], b"BIN\0");
+    (json, bytes[bin_start + 8..bin_start + 8 + bin_len].to_vec())
+}
+
+#[test]
+fn test_glb_write() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("glb_write.glb");
+    let mut pc = PointCloudRgbNormal::new();
+    pc.push(PointRgbNormal {
+        position: Position::new(-1., 2., 0.5),
+        color: Rgb::new(255, 0, 0),
+        normal: Normal::new(0., 0., 2.),
+        curvature: 0.,
+    })
+    .push(PointRgbNormal {
+        position: Position::new(3., -4., 0.25),
+        color: Rgb::new(0, 128, 255),
+        normal: Normal::new(1., 0., 0.),
+        curvature: 0.,
+    })
+    .push(PointRgbNormal {
+        position: Position::new(f32::NAN, 0., 0.),
+        ..Default::default()
+    });
+
+    glb_write(&path, &pc).unwrap();
+    let (json, bin) = read_glb(&path);
+
+    let primitive = &json["meshes"][0]["primitives"][0];
+    assert_eq!(primitive["mode"], 0);
+
+    let position =
+        &json["accessors"][primitive["attributes"]["POSITION"].as_u64().unwrap() as usize];
+    assert_eq!(position["count"], 2);
+    assert_eq!(position["min"], serde_json::json!([-1., -4., 0.25]));
+    assert_eq!(position["max"], serde_json::json!([3., 2., 0.5]));
+
+    let color = &json["accessors"][primitive["attributes"]["COLOR_0"].as_u64().unwrap() as usize];
+    assert_eq!(color["type"], "VEC3");
+    assert_eq!(color["normalized"], true);
+    let view = &json["bufferViews"][color["bufferView"].as_u64().unwrap() as usize];
+    assert_eq!(view["byteStride"], 4);
+    let offset = view["byteOffset"].as_u64().unwrap() as usize;
+    assert_eq!(&bin[offset..offset + 7], &[255, 0, 0, 255, 0, 128, 255]);
+
+    let normal = &json["accessors"][primitive["attributes"]["NORMAL"].as_u64().unwrap() as usize];
+    assert_eq!(normal["max"], serde_json::json!([1., 0., 1.]));
+
+    assert_eq!(
+        json["buffers"][0]["byteLength"].as_u64().unwrap() as usize,
+        bin.len()
+    );
+}
+
+#[test]
+fn test_glb_write_positions_only() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("glb_write_positions_only.glb");
+    let mut pc = PointCloud::new();
+    pc.push(pointrain_core::point::Point {
+        position: Position::new(1., 2., 3.),
+    });
+
+    glb_write(&path, &pc).unwrap();
+    let (json, bin) = read_glb(&path);
+
+    let attributes = json["meshes"][0]["primitives"][0]["attributes"]
+        .as_object()
+        .unwrap();
+    assert_eq!(attributes.len(), 1);
+    assert_eq!(bin.len(), 12);
+
+    assert!(glb_write(&path, &PointCloud::new()).is_err());
+}
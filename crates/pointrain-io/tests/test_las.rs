@@ -0,0 +1,92 @@
+mod common;
+
+use common::{las_bytes, LasPoint};
+use pointrain_core::{
+    pc::{
+        PointCloud, PointCloudBase, PointCloudIntensity, PointCloudRgb, PointCloudWithColor,
+        PointCloudWithIntensity,
+    },
+    types::{Position, Rgb},
+};
+use pointrain_io::{las_read, las_read_chunks};
+
+fn test_points() -> Vec<LasPoint> {
+    (0..5)
+        .map(|i| LasPoint {
+            xyz: [i, -i, 2 * i],
+            intensity: 1000 * i as u16,
+            rgb: [i as u16 * 256, 65535, 0],
+        })
+        .collect()
+}
+
+#[test]
+fn test_las_read() {
+    let tmp = common::temp_dir();
+    for format in 0..4 {
+        let path = tmp.path().join(format!("format_{format}.las"));
+        std::fs::write(&path, las_bytes(format, &test_points(), 3)).unwrap();
+
+        let pc: PointCloudIntensity = las_read(&path).unwrap();
+        assert_eq!(pc.len(), 5);
+        assert_eq!(pc.positions()[2], Position::new(2., 1., 5.));
+        assert_eq!(pc.intensities()[4], 4000.);
+
+        let rgb = las_read::<PointCloudRgb>(&path);
+        if format >= 2 {
+            assert_eq!(rgb.unwrap().colors()[3], Rgb::new(3, 255, 0));
+        } else {
+            assert!(rgb.is_err());
+        }
+    }
+}
+
+#[test]
+fn test_las_read_chunks() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("chunks.las");
+    std::fs::write(&path, las_bytes(3, &test_points(), 0)).unwrap();
+
+    let mut lens = Vec::new();
+    let mut pc = PointCloud::new();
+    las_read_chunks::<PointCloud>(&path, 2, |mut chunk| {
+        lens.push(chunk.len());
+        pc.append(&mut chunk);
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(lens, [2, 2, 1]);
+    assert_eq!(
+        pc.positions(),
+        las_read::<PointCloud>(&path).unwrap().positions()
+    );
+}
+
+#[test]
+fn test_las_read_invalid() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("invalid.las");
+    let bytes = las_bytes(1, &test_points(), 0);
+
+    // Truncated point data.
+    std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+    assert!(las_read::<PointCloud>(&path).is_err());
+
+    // LAZ compressed and unsupported point formats.
+    for format in [0x81, 6] {
+        let mut bytes = bytes.clone();
+        bytes[104] = format;
+        std::fs::write(&path, bytes).unwrap();
+        assert!(las_read::<PointCloud>(&path).is_err());
+    }
+
+    // A record count far beyond the file is not allocated upfront.
+    let mut bytes = bytes.clone();
+    bytes[107..111].copy_from_slice(&u32::MAX.to_le_bytes());
+    std::fs::write(&path, bytes).unwrap();
+    assert!(las_read::<PointCloud>(&path).is_err());
+
+    std::fs::write(&path, b"PCD").unwrap();
+    assert!(las_read::<PointCloud>(&path).is_err());
+}
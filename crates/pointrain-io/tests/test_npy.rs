@@ -0,0 +1,104 @@
+mod common;
+
+use pointrain_core::{
+    pc::{
+        PointCloud, PointCloudBase, PointCloudIntensity, PointCloudRgb, PointCloudRgbNormal,
+        PointCloudWithColor, PointCloudWithIntensity, PointCloudWithNormal,
+    },
+    point::PointRgbNormal,
+    types::{Normal, Position, Rgb},
+};
+use pointrain_io::{npy_read, npy_write, npz_read, npz_write};
+
+#[test]
+fn test_npy_read_fortran_order() {
+    let pc = npy_read("tests/data/npy/test_fortran.npy").unwrap();
+
+    assert_eq!(
+        pc.positions(),
+        &[Position::new(1., 2., 3.), Position::new(4., 5., 6.)]
+    );
+}
+
+#[test]
+fn test_npy_write_read() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("npy_write_read.npy");
+    let pc = npy_read("tests/data/npy/test_fortran.npy").unwrap();
+
+    npy_write(&path, &pc).unwrap();
+    assert_eq!(npy_read(&path).unwrap().positions(), pc.positions());
+
+    // The header is padded so that the data is 64-byte aligned.
+    let bytes = std::fs::read(&path).unwrap();
+    assert_eq!((bytes.len() - 2 * 3 * 4) % 64, 0);
+}
+
+#[test]
+fn test_npy_read_truncated() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("npy_truncated.npy");
+    let pc = npy_read("tests/data/npy/test_fortran.npy").unwrap();
+    npy_write(&path, &pc).unwrap();
+
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+    assert!(npy_read(&path).is_err());
+
+    // A shape whose size overflows must fail before anything is allocated.
+    let header = format!(
+        "{{'descr': '<f4', 'fortran_order': False, 'shape': ({}, 3), }}\n",
+        usize::MAX / 2
+    );
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend((header.len() as u16).to_le_bytes());
+    bytes.extend(header.as_bytes());
+    std::fs::write(&path, bytes).unwrap();
+    assert!(npy_read(&path).is_err());
+}
+
+#[test]
+fn test_npz_read_compressed() {
+    let pc: PointCloudRgb = npz_read("tests/data/npy/test_compressed.npz").unwrap();
+
+    assert_eq!(pc.len(), 3);
+    assert_eq!(pc.positions()[2], Position::new(-1., -2., -3.));
+    assert_eq!(pc.colors()[1], Rgb::new(0, 255, 0));
+
+    let pc: PointCloudIntensity = npz_read("tests/data/npy/test_compressed.npz").unwrap();
+    assert_eq!(pc.intensities(), &[0.5, 0.25, 1.]);
+}
+
+#[test]
+fn test_npz_read_invalid_dtype() {
+    assert!(npz_read::<PointCloud>("tests/data/npy/test_bad_dtype.npz").is_err());
+}
+
+#[test]
+fn test_npz_write_read() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("npz_write_read.npz");
+    let mut pc = PointCloudRgbNormal::new();
+    pc.push(PointRgbNormal {
+        position: Position::new(1., 2., 3.),
+        color: Rgb::new(10, 20, 30),
+        normal: Normal::new(0., 0., 1.),
+        curvature: 0.5,
+    })
+    .push(PointRgbNormal {
+        position: Position::new(-1., 0., 1.),
+        color: Rgb::new(40, 50, 60),
+        normal: Normal::new(1., 0., 0.),
+        curvature: 0.25,
+    });
+
+    npz_write(&path, &pc).unwrap();
+    let read: PointCloudRgbNormal = npz_read(&path).unwrap();
+
+    assert_eq!(read.positions(), pc.positions());
+    assert_eq!(read.colors(), pc.colors());
+    assert_eq!(read.normals(), pc.normals());
+    assert_eq!(read.curvatures(), pc.curvatures());
+
+    assert!(npz_read::<PointCloudIntensity>(&path).is_err());
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use pointrain_core::{
+    pc::{
+        PointCloudBase, PointCloudNormal, PointCloudRgb, PointCloudRgbNormal, PointCloudWithColor,
+        PointCloudWithNormal,
+    },
+    types::{Normal, Position, Rgb},
+};
+use pointrain_io::{obj_read, obj_write};
+
+#[test]
+fn test_obj_read() {
+    let mesh = obj_read::<PointCloudRgbNormal>("tests/data/obj/test_triangle.obj").unwrap();
+
+    assert_eq!(mesh.vertices.len(), 3);
+    assert_eq!(mesh.vertices.positions()[1], Position::new(1., 0., 0.));
+    assert_eq!(mesh.vertices.colors()[1], Rgb::new(0, 255, 0));
+    assert_eq!(mesh.vertices.normals()[2], Normal::new(0., 0., 1.));
+    assert_eq!(mesh.faces, vec![vec![0, 1, 2]]);
+}
+
+#[test]
+fn test_obj_write_read() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("obj_write_read.obj");
+    let mesh = obj_read::<PointCloudRgbNormal>("tests/data/obj/test_triangle.obj").unwrap();
+
+    obj_write(&path, &mesh.vertices).unwrap();
+    let read = obj_read::<PointCloudNormal>(&path).unwrap();
+
+    assert!(read.faces.is_empty());
+    assert_eq!(read.vertices.positions(), mesh.vertices.positions());
+    assert_eq!(read.vertices.normals(), mesh.vertices.normals());
+
+    let read = obj_read::<PointCloudRgbNormal>(&path).unwrap();
+    assert_eq!(read.vertices.colors(), mesh.vertices.colors());
+}
+
+#[test]
+fn test_obj_read_color_range() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("obj_read_color_range.obj");
+    std::fs::write(&path, "v 0 0 0 0 0 1\nv 1 0 0 0 0 1.0\nv 0 1 0 0.5 0 1.0\n").unwrap();
+
+    // All-integer colors are `0..=255`, any float token makes the color `[0, 1]`.
+    let mesh = obj_read::<PointCloudRgb>(&path).unwrap();
+    assert_eq!(
+        mesh.vertices.colors(),
+        &[
+            Rgb::new(0, 0, 1),
+            Rgb::new(0, 0, 255),
+            Rgb::new(128, 0, 255)
+        ]
+    );
+}
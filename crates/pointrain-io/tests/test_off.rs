@@ -0,0 +1,65 @@
+mod common;
+
+use pointrain_core::{
+    pc::{PointCloud, PointCloudBase, PointCloudRgba, PointCloudWithAlpha, PointCloudWithColor},
+    point::PointRgba,
+    types::{Position, Rgb},
+};
+use pointrain_io::{off_read, off_write};
+
+#[test]
+fn test_off_read() {
+    let mesh = off_read::<PointCloudRgba>("tests/data/off/test_square.off").unwrap();
+
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.vertices.positions()[2], Position::new(1., 1., 0.));
+    assert_eq!(mesh.vertices.colors()[3], Rgb::new(128, 128, 128));
+    assert_eq!(mesh.vertices.alphas(), &[255, 255, 128, 255]);
+    assert_eq!(mesh.faces, vec![vec![0, 1, 2], vec![0, 2, 3]]);
+}
+
+#[test]
+fn test_off_write_read() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("off_write_read.off");
+    let mesh = off_read::<PointCloudRgba>("tests/data/off/test_square.off").unwrap();
+
+    off_write(&path, &mesh.vertices).unwrap();
+    let read = off_read::<PointCloudRgba>(&path).unwrap();
+
+    assert!(read.faces.is_empty());
+    assert_eq!(read.vertices.positions(), mesh.vertices.positions());
+    assert_eq!(read.vertices.colors(), mesh.vertices.colors());
+    assert_eq!(read.vertices.alphas(), mesh.vertices.alphas());
+
+    let read = off_read::<PointCloud>(&path).unwrap();
+    assert_eq!(read.vertices.len(), 4);
+}
+
+#[test]
+fn test_off_read_face_colors() {
+    let mesh = off_read::<PointCloud>("tests/data/off/test_face_colors.off").unwrap();
+
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.faces, vec![vec![0, 1, 2], vec![0, 2, 3]]);
+}
+
+#[test]
+fn test_off_write_read_low_colors() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("off_write_read_low_colors.off");
+    let pc: PointCloudRgba = [Rgb::new(1, 2, 3), Rgb::new(0, 1, 0)]
+        .into_iter()
+        .map(|color| PointRgba {
+            position: Position::new(0., 0., 0.),
+            color,
+            alpha: 1,
+        })
+        .collect();
+
+    off_write(&path, &pc).unwrap();
+    let read = off_read::<PointCloudRgba>(&path).unwrap();
+
+    assert_eq!(read.vertices.colors(), pc.colors());
+    assert_eq!(read.vertices.alphas(), pc.alphas());
+}
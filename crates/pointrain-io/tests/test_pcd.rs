@@ -1,8 +1,15 @@
 use pointrain_core::{
-    pc::{PointCloudBase, PointCloudIntensity, PointCloudNormal, PointCloudWithNormal},
-    types::{Normal, Position},
+    pc::{
+        PointCloudBase, PointCloudDescriptor, PointCloudFpfh, PointCloudIntensity,
+        PointCloudNormal, PointCloudRgb, PointCloudRgba, PointCloudWithAlpha, PointCloudWithColor,
+        PointCloudWithDescriptor, PointCloudWithNormal,
+    },
+    types::{Normal, Position, Rgb},
+};
+use pointrain_io::{
+    pcd_read, pcd_read_chunks, pcd_read_with_options, InvalidRecordPolicy, PointRainIOError,
+    ReadOptions,
 };
-use pointrain_io::{pcd_read, PointRainIOError};
 
 #[test]
 fn test_pcd_read_ascii() {
@@ -33,3 +40,108 @@ fn test_pcd_read_binary() {
     assert_eq!(pc.normals()[0], Normal::new(4., 5., 6.));
     assert_eq!(pc.curvatures()[0], 7.);
 }
+
+#[test]
+fn test_pcd_read_chunks() {
+    for path in [
+        "tests/data/pcd/test_ascii.pcd",
+        "tests/data/pcd/test_binary.pcd",
+    ] {
+        let mut chunks = Vec::new();
+        let report = pcd_read_chunks(path, 2, &ReadOptions::strict(), |pc: PointCloudNormal| {
+            chunks.push(pc);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(report.read_points, 3);
+        assert_eq!(chunks.iter().map(|pc| pc.len()).collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(chunks[0].positions()[0], Position::new(1., 2., 3.));
+        let pc: PointCloudNormal = pcd_read(path).unwrap();
+        assert_eq!(chunks[1].positions()[0], pc.positions()[2]);
+    }
+}
+
+#[test]
+fn test_pcd_read_ascii_broken_strict() {
+    assert!(pcd_read::<PointCloudNormal>("tests/data/pcd/test_ascii_broken.pcd").is_err());
+}
+
+#[test]
+fn test_pcd_read_ascii_broken_lenient() {
+    let (pc, report) = pcd_read_with_options::<PointCloudNormal>(
+        "tests/data/pcd/test_ascii_broken.pcd",
+        &ReadOptions::lenient(),
+    )
+    .unwrap();
+
+    assert_eq!(pc.len(), 2);
+    assert_eq!(pc.positions()[1], Position::new(1., 2., 3.));
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].index, 1);
+    assert_eq!(report.blank_lines, 1);
+    assert_eq!(report.declared_points, Some(4));
+    assert_eq!(report.read_points, 2);
+    assert!(report.count_mismatch());
+}
+
+#[test]
+fn test_pcd_read_ascii_broken_fill_nan() {
+    let options = ReadOptions::lenient().with_invalid_record(InvalidRecordPolicy::FillNan);
+    let (pc, report) =
+        pcd_read_with_options::<PointCloudNormal>("tests/data/pcd/test_ascii_broken.pcd", &options)
+            .unwrap();
+
+    assert_eq!(pc.len(), 3);
+    assert!(pc.positions()[1].x.is_nan());
+    assert_eq!(pc.positions()[2], Position::new(1., 2., 3.));
+    assert_eq!(report.nan_filled.len(), 1);
+}
+
+#[test]
+fn test_pcd_read_rgb_float() {
+    let pc: PointCloudRgb = pcd_read("tests/data/pcd/test_rgb_float.pcd").unwrap();
+
+    assert_eq!(pc.len(), 2);
+    assert_eq!(pc.colors()[0], Rgb::new(0x10, 0x20, 0x30));
+}
+
+#[test]
+fn test_pcd_read_rgb_uint() {
+    let pc: PointCloudRgba = pcd_read("tests/data/pcd/test_rgb_uint.pcd").unwrap();
+
+    assert_eq!(pc.len(), 2);
+    assert_eq!(pc.colors()[0], Rgb::new(0x10, 0x20, 0x30));
+    assert_eq!(pc.colors()[1], Rgb::new(0x10, 0x20, 0x30));
+    assert_eq!(pc.alphas(), &[255, 255]);
+}
+
+#[test]
+fn test_pcd_read_rgba() {
+    let pc: PointCloudRgba = pcd_read("tests/data/pcd/test_rgba.pcd").unwrap();
+
+    assert_eq!(pc.len(), 2);
+    assert_eq!(pc.positions()[1], Position::new(4., 5., 6.));
+    assert_eq!(pc.colors()[0], Rgb::new(0xFF, 0x00, 0x10));
+    assert_eq!(pc.alphas(), &[0x80, 0xFF]);
+}
+
+#[test]
+fn test_pcd_read_multi_count_ascii() {
+    let pc: PointCloudDescriptor<4> = pcd_read("tests/data/pcd/test_histogram.pcd").unwrap();
+
+    assert_eq!(pc.len(), 2);
+    assert_eq!(pc.positions()[1], Position::new(4., 5., 6.));
+    assert_eq!(pc.descriptors()[0], [0.1, 0.2, 0.3, 0.4]);
+    assert_eq!(pc.descriptors()[1], [1., 2., 3., 4.]);
+}
+
+#[test]
+fn test_pcd_read_fpfh_binary() {
+    let pc: PointCloudFpfh = pcd_read("tests/data/pcd/test_fpfh_binary.pcd").unwrap();
+
+    assert_eq!(pc.len(), 2);
+    assert!(pc.positions()[0].x.is_nan());
+    assert_eq!(pc.descriptors()[0][32], 32.);
+    assert_eq!(pc.descriptors()[1][0], 100.);
+}
@@ -1,8 +1,11 @@
 use pointrain_core::{
-    pc::{PointCloud, PointCloudBase},
-    types::Position,
+    pc::{PointCloud, PointCloudBase, PointCloudRgba, PointCloudWithAlpha, PointCloudWithColor},
+    types::{Position, Rgb},
+};
+use pointrain_io::{
+    ply::{ply_read, ply_read_with_options},
+    InvalidRecordPolicy, ReadOptions,
 };
-use pointrain_io::ply::ply_read;
 
 #[test]
 fn test_ply_read_ascii() {
@@ -12,3 +15,74 @@ fn test_ply_read_ascii() {
     assert_eq!(pc.positions()[0], Position::new(0., 0., 0.));
     assert_eq!(pc.positions()[7], Position::new(1., 1., 0.));
 }
+
+#[test]
+fn test_ply_read_color_encodings() {
+    let pc = ply_read::<PointCloudRgba>("tests/data/ply/test_color.ply").unwrap();
+
+    assert_eq!(pc.len(), 2);
+    assert_eq!(pc.colors()[0], Rgb::new(255, 0, 128));
+    assert_eq!(pc.colors()[1], Rgb::new(0, 255, 1));
+    assert_eq!(pc.alphas(), &[128, 255]);
+}
+
+#[test]
+fn test_ply_read_ascii_broken_strict() {
+    assert!(ply_read::<PointCloud>("tests/data/ply/test_ascii_broken.ply").is_err());
+
+    // Valid vertices, but fewer than declared.
+    let err = ply_read::<PointCloud>("tests/data/ply/test_ascii_short.ply").unwrap_err();
+    assert!(err.to_string().contains("does not match"));
+    let (pc, report) = ply_read_with_options::<PointCloud>(
+        "tests/data/ply/test_ascii_short.ply",
+        &ReadOptions::lenient(),
+    )
+    .unwrap();
+    assert_eq!(pc.len(), 2);
+    assert!(report.skipped.is_empty() && report.count_mismatch());
+}
+
+#[test]
+fn test_ply_read_ascii_broken_lenient() {
+    let (pc, report) = ply_read_with_options::<PointCloud>(
+        "tests/data/ply/test_ascii_broken.ply",
+        &ReadOptions::lenient(),
+    )
+    .unwrap();
+
+    assert_eq!(pc.len(), 2);
+    assert_eq!(pc.positions()[1], Position::new(1., 2., 3.));
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].index, 1);
+    assert_eq!(report.blank_lines, 1);
+    assert_eq!(report.declared_points, Some(4));
+    assert_eq!(report.read_points, 2);
+    assert!(report.count_mismatch());
+}
+
+#[test]
+fn test_ply_read_ascii_broken_fill_nan() {
+    let options = ReadOptions::lenient().with_invalid_record(InvalidRecordPolicy::FillNan);
+    let (pc, report) =
+        ply_read_with_options::<PointCloud>("tests/data/ply/test_ascii_broken.ply", &options)
+            .unwrap();
+
+    assert_eq!(pc.len(), 3);
+    assert!(pc.positions()[1].x.is_nan());
+    assert_eq!(pc.positions()[2], Position::new(1., 2., 3.));
+    assert_eq!(report.nan_filled.len(), 1);
+}
+
+#[test]
+fn test_ply_read_binary_truncated() {
+    let path = "tests/data/ply/test_binary_truncated.ply";
+    assert!(ply_read::<PointCloud>(path).is_err());
+
+    let (pc, report) = ply_read_with_options::<PointCloud>(path, &ReadOptions::lenient()).unwrap();
+    assert_eq!(
+        pc.positions(),
+        &[Position::new(0., 0., 0.), Position::new(1., 2., 3.)]
+    );
+    assert_eq!(report.declared_points, Some(3));
+    assert!(report.count_mismatch());
+}
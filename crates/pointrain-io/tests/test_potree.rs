@@ -0,0 +1,194 @@
+mod common;
+
+use pointrain_core::{
+    pc::{PointCloud, PointCloudBase, PointCloudIntensity, PointCloudWithIntensity},
+    point::{Point, PointIntensity},
+    types::Position,
+};
+use pointrain_io::{
+    las_read, potree_convert, potree_convert_las, potree_convert_pcd, PotreeOptions,
+};
+use serde_json::Value;
+
+struct HierarchyNode {
+    node_type: u8,
+    child_mask: u8,
+    num_points: u32,
+    byte_offset: u64,
+    byte_size: u64,
+}
+
+fn read_hierarchy(bytes: &[u8]) -> Vec<HierarchyNode> {
+    bytes
+        .chunks_exact(22)
+        .map(|r| HierarchyNode {
+            node_type: r[0],
+            child_mask: r[1],
+            num_points: u32::from_le_bytes(r[2..6].try_into().unwrap()),
+            byte_offset: u64::from_le_bytes(r[6..14].try_into().unwrap()),
+            byte_size: u64::from_le_bytes(r[14..22].try_into().unwrap()),
+        })
+        .collect()
+}
+
+fn grid_cloud(n: usize) -> PointCloudIntensity {
+    let mut pc = PointCloudIntensity::new();
+    for i in 0..n {
+        for j in 0..n {
+            for k in 0..n {
+                pc.push(PointIntensity {
+                    position: Position::new(i as f32, j as f32 * 0.5, k as f32 * 0.25),
+                    intensity: (i + j + k) as f32 / (3 * n) as f32,
+                });
+            }
+        }
+    }
+    pc
+}
+
+#[test]
+fn test_potree_convert() {
+    let tmp = common::temp_dir();
+    let dir = tmp.path();
+    let pc = grid_cloud(16);
+    let options = PotreeOptions {
+        max_points_per_node: 500,
+        grid_size: 8,
+        ..Default::default()
+    };
+
+    potree_convert(&pc, dir, &options).unwrap();
+
+    let metadata: Value =
+        serde_json::from_slice(&std::fs::read(dir.join("metadata.json")).unwrap()).unwrap();
+    assert_eq!(metadata["version"], "2.0");
+    assert_eq!(metadata["points"], 4096);
+    assert_eq!(
+        metadata["boundingBox"]["max"],
+        serde_json::json!([15., 15., 15.])
+    );
+    let attributes: Vec<_> = metadata["attributes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(attributes, ["position", "intensity"]);
+
+    let hierarchy = std::fs::read(dir.join("hierarchy.bin")).unwrap();
+    let octree = std::fs::read(dir.join("octree.bin")).unwrap();
+    assert_eq!(metadata["hierarchy"]["firstChunkSize"], hierarchy.len());
+
+    let nodes = read_hierarchy(&hierarchy);
+    assert!(nodes.len() > 1);
+    assert_eq!(nodes[0].node_type, 0);
+
+    // Every point is stored exactly once, records of 12 + 2 bytes.
+    let total: u32 = nodes.iter().map(|n| n.num_points).sum();
+    assert_eq!(total, 4096);
+    let mut offset = 0;
+    for node in &nodes {
+        assert_eq!(node.byte_offset, offset);
+        assert_eq!(node.byte_size, u64::from(node.num_points) * 14);
+        assert_eq!(node.node_type == 1, node.child_mask == 0);
+        offset += node.byte_size;
+    }
+    assert_eq!(offset as usize, octree.len());
+
+    // Breadth first: the number of records matches the child masks.
+    let children: u32 = nodes.iter().map(|n| n.child_mask.count_ones()).sum();
+    assert_eq!(children as usize + 1, nodes.len());
+
+    // The root is a sample of the whole cloud on an 8^3 grid.
+    assert!(nodes[0].num_points > 0 && nodes[0].num_points <= 8 * 8 * 8);
+    let x = i32::from_le_bytes(octree[0..4].try_into().unwrap());
+    assert_eq!(f64::from(x) * 0.001, 0.);
+}
+
+#[test]
+fn test_potree_convert_small() {
+    let tmp = common::temp_dir();
+    let dir = tmp.path();
+    let mut pc = PointCloud::new();
+    pc.push(Point {
+        position: Position::new(1., 2., 3.),
+    });
+
+    potree_convert(&pc, dir, &PotreeOptions::default()).unwrap();
+
+    let nodes = read_hierarchy(&std::fs::read(dir.join("hierarchy.bin")).unwrap());
+    assert_eq!(nodes.len(), 1);
+    assert_eq!((nodes[0].node_type, nodes[0].num_points), (1, 1));
+    assert_eq!(std::fs::read(dir.join("octree.bin")).unwrap().len(), 12);
+
+    assert!(potree_convert(&PointCloud::new(), dir, &PotreeOptions::default()).is_err());
+}
+
+#[test]
+fn test_potree_convert_pcd() {
+    let tmp = common::temp_dir();
+    let dir = tmp.path();
+    let pc = grid_cloud(48);
+    let options = PotreeOptions {
+        max_points_per_node: 5000,
+        grid_size: 16,
+        ..Default::default()
+    };
+
+    // More points than fit into one chunk of the streamed reader.
+    let mut pcd = format!(
+        "FIELDS x y z intensity\nSIZE 4 4 4 4\nTYPE F F F F\nCOUNT 1 1 1 1\n\
+         WIDTH {0}\nHEIGHT 1\nPOINTS {0}\nDATA ascii\n",
+        pc.len()
+    );
+    for (p, i) in pc.positions().iter().zip(pc.intensities()) {
+        pcd += &format!("{} {} {} {i}\n", p.x, p.y, p.z);
+    }
+    let path = dir.join("input.pcd");
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(&path, pcd).unwrap();
+    potree_convert_pcd::<PointCloudIntensity>(&path, dir.join("pcd"), &options).unwrap();
+    potree_convert(&pc, dir.join("pc"), &options).unwrap();
+
+    for file in ["metadata.json", "hierarchy.bin", "octree.bin"] {
+        assert_eq!(
+            std::fs::read(dir.join("pcd").join(file)).unwrap(),
+            std::fs::read(dir.join("pc").join(file)).unwrap(),
+            "{file}"
+        );
+    }
+}
+
+#[test]
+fn test_potree_convert_las() {
+    let tmp = common::temp_dir();
+    let dir = tmp.path();
+    let options = PotreeOptions {
+        max_points_per_node: 5000,
+        grid_size: 16,
+        ..Default::default()
+    };
+
+    // More points than fit into one chunk of the streamed reader.
+    let points: Vec<_> = (0..100_000)
+        .map(|i| common::LasPoint {
+            xyz: [i % 47, i / 47 % 53, i / (47 * 53)],
+            intensity: (i % 65536) as u16,
+            rgb: [(i % 65536) as u16, 0, 65535],
+        })
+        .collect();
+    let path = dir.join("input.las");
+    std::fs::write(&path, common::las_bytes(3, &points, 0)).unwrap();
+
+    potree_convert_las::<PointCloudIntensity>(&path, dir.join("las"), &options).unwrap();
+    let pc: PointCloudIntensity = las_read(&path).unwrap();
+    potree_convert(&pc, dir.join("pc"), &options).unwrap();
+
+    for file in ["metadata.json", "hierarchy.bin", "octree.bin"] {
+        assert_eq!(
+            std::fs::read(dir.join("las").join(file)).unwrap(),
+            std::fs::read(dir.join("pc").join(file)).unwrap(),
+            "{file}"
+        );
+    }
+}
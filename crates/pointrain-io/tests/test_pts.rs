@@ -0,0 +1,45 @@
+mod common;
+
+use pointrain_core::{
+    pc::{
+        PointCloud, PointCloudBase, PointCloudIntensity, PointCloudRgb, PointCloudWithColor,
+        PointCloudWithIntensity,
+    },
+    types::{Position, Rgb},
+};
+use pointrain_io::{pts_read, pts_write};
+
+#[test]
+fn test_pts_read() {
+    let pc = pts_read::<PointCloudIntensity>("tests/data/pts/test_blocks.pts").unwrap();
+
+    assert_eq!(pc.len(), 3);
+    assert_eq!(pc.positions()[2], Position::new(-1., -2., -3.));
+    assert_eq!(pc.intensities(), &[-120., 300., 2047.]);
+
+    let pc = pts_read::<PointCloudRgb>("tests/data/pts/test_blocks.pts").unwrap();
+    assert_eq!(pc.colors()[1], Rgb::new(0, 255, 0));
+}
+
+#[test]
+fn test_pts_write_read() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("pts_write_read.pts");
+    let pc = pts_read::<PointCloudRgb>("tests/data/pts/test_blocks.pts").unwrap();
+
+    pts_write(&path, &pc).unwrap();
+    let read = pts_read::<PointCloudRgb>(&path).unwrap();
+
+    assert_eq!(read.positions(), pc.positions());
+    assert_eq!(read.colors(), pc.colors());
+
+    assert!(pts_read::<PointCloudIntensity>(&path).is_err());
+    assert_eq!(pts_read::<PointCloud>(&path).unwrap().len(), 3);
+}
+
+#[test]
+fn test_pts_read_malformed() {
+    // The second point has fewer values than the first.
+    let err = pts_read::<PointCloudIntensity>("tests/data/pts/test_malformed.pts").unwrap_err();
+    assert!(err.to_string().contains("Expected 4 values"), "{err}");
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use pointrain_core::{
+    pc::{
+        PointCloudBase, PointCloudIntensity, PointCloudRgb, PointCloudWithColor,
+        PointCloudWithIntensity,
+    },
+    types::{Position, Rgb},
+};
+use pointrain_io::{ptx_read, ptx_write};
+
+#[test]
+fn test_ptx_read() {
+    let scans = ptx_read::<PointCloudRgb>("tests/data/ptx/test_scans.ptx").unwrap();
+
+    assert_eq!(scans.len(), 2);
+    let first = &scans[0];
+    assert_eq!((first.rows, first.cols), (2, 2));
+    assert_eq!(
+        first.pc.positions()[first.index(1, 0)],
+        Position::new(1., 1., 0.)
+    );
+    assert!(first.pc.positions()[first.index(0, 1)].x.is_nan());
+    assert_eq!(first.pc.colors()[first.index(1, 1)], Rgb::new(0, 0, 255));
+
+    // Rotated by 90 degrees around z and translated by 10 along x.
+    let second = &scans[1];
+    assert_eq!((second.rows, second.cols), (2, 1));
+    assert_eq!(second.pc.positions()[0], Position::new(10., 1., 0.));
+    assert_eq!(second.pc.positions()[1], Position::new(10., 0., 2.));
+    assert_eq!(second.transform[(0, 3)], 10.);
+}
+
+#[test]
+fn test_ptx_write_read() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("ptx_write_read.ptx");
+    let scans = ptx_read::<PointCloudIntensity>("tests/data/ptx/test_scans.ptx").unwrap();
+
+    ptx_write(&path, &scans).unwrap();
+    let read = ptx_read::<PointCloudIntensity>(&path).unwrap();
+
+    assert_eq!(read.len(), 2);
+    for (a, b) in read.iter().zip(&scans) {
+        assert_eq!((a.rows, a.cols), (b.rows, b.cols));
+        assert_eq!(a.transform, b.transform);
+        assert_eq!(a.pc.intensities(), b.pc.intensities());
+        for (p, q) in a.pc.positions().iter().zip(b.pc.positions()) {
+            assert!(p == q || (p.x.is_nan() && q.x.is_nan()));
+        }
+    }
+}
+
+#[test]
+fn test_ptx_read_malformed() {
+    for (file, message) in [
+        ("test_short_line.ptx", "Expected 4 values"),
+        ("test_two_values.ptx", "Invalid number of values"),
+        // Header values are not trusted for allocation.
+        ("test_large_grid.ptx", "Unexpected end of file"),
+        ("test_huge_grid.ptx", "too large"),
+    ] {
+        let err = ptx_read::<PointCloudIntensity>(format!("tests/data/ptx/{file}")).unwrap_err();
+        assert!(err.to_string().contains(message), "{file}: {err}");
+    }
+}
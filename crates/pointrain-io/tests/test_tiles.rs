@@ -0,0 +1,185 @@
+mod common;
+
+use std::path::Path;
+
+use pointrain_core::{
+    pc::{PointCloud, PointCloudBase, PointCloudRgbNormal},
+    point::{Point, PointRgbNormal},
+    types::{Normal, Position, Rgb},
+};
+use pointrain_io::{pnts_write, tileset_write, TilesetOptions};
+use serde_json::Value;
+
+struct Pnts {
+    feature_table: Value,
+    body: Vec<u8>,
+}
+
+fn read_pnts(path: &Path) -> Pnts {
+    let bytes = std::fs::read(path).unwrap();
+    let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as usize;
+
+    assert_eq!(&bytes[..4], b"pnts");
+    assert_eq!(u32_at(4), 1);
+    assert_eq!(u32_at(8), bytes.len());
+    let json_length = u32_at(12);
+    let bin_length = u32_at(16);
+    assert_eq!((u32_at(20), u32_at(24)), (0, 0));
+    assert_eq!((28 + json_length) % 8, 0);
+    assert_eq!(bin_length % 8, 0);
+    assert_eq!(28 + json_length + bin_length, bytes.len());
+
+    Pnts {
+        feature_table: serde_json::from_slice(&bytes[28..28 + json_length]).unwrap(),
+        body: bytes[28 + json_length..].to_vec(),
+    }
+}
+
+fn f32s(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+        .collect()
+}
+
+fn grid_cloud(n: usize) -> PointCloudRgbNormal {
+    let mut pc = PointCloudRgbNormal::new();
+    for i in 0..n {
+        for j in 0..n {
+            for k in 0..n {
+                pc.push(PointRgbNormal {
+                    position: Position::new(i as f32, j as f32, k as f32 * 0.5),
+                    color: Rgb::new(i as u8, j as u8, k as u8),
+                    normal: Normal::z(),
+                    curvature: 0.,
+                });
+            }
+        }
+    }
+    pc
+}
+
+#[test]
+fn test_pnts_write() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("pnts_write.pnts");
+    let pc = grid_cloud(3);
+
+    pnts_write(&path, &pc, false).unwrap();
+
+    let pnts = read_pnts(&path);
+    let table = &pnts.feature_table;
+    assert_eq!(table["POINTS_LENGTH"], 27);
+    assert_eq!(table["RTC_CENTER"], serde_json::json!([1.0, 1.0, 0.5]));
+
+    let offset = table["POSITION"]["byteOffset"].as_u64().unwrap() as usize;
+    let positions = f32s(&pnts.body[offset..offset + 27 * 12]);
+    for (p, expected) in positions.chunks_exact(3).zip(pc.positions()) {
+        assert_eq!(p, [expected.x - 1., expected.y - 1., expected.z - 0.5]);
+    }
+
+    let offset = table["NORMAL"]["byteOffset"].as_u64().unwrap() as usize;
+    let normals = f32s(&pnts.body[offset..offset + 27 * 12]);
+    assert!(normals.chunks_exact(3).all(|n| n == [0., 0., 1.]));
+
+    let offset = table["RGB"]["byteOffset"].as_u64().unwrap() as usize;
+    assert_eq!(&pnts.body[offset..offset + 6], [0, 0, 0, 0, 0, 1]);
+}
+
+#[test]
+fn test_pnts_write_quantized() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("pnts_write_quantized.pnts");
+    let mut pc = PointCloud::new();
+    pc.push(Point {
+        position: Position::new(-1., 2., 3.),
+    })
+    .push(Point {
+        position: Position::new(1., 4., 3.),
+    });
+
+    pnts_write(&path, &pc, true).unwrap();
+
+    let pnts = read_pnts(&path);
+    let table = &pnts.feature_table;
+    assert!(table.get("POSITION").is_none());
+    assert!(table.get("RGB").is_none());
+    assert_eq!(
+        table["QUANTIZED_VOLUME_OFFSET"],
+        serde_json::json!([-1.0, 2.0, 3.0])
+    );
+    assert_eq!(
+        table["QUANTIZED_VOLUME_SCALE"],
+        serde_json::json!([2.0, 2.0, 0.0])
+    );
+
+    let offset = table["POSITION_QUANTIZED"]["byteOffset"].as_u64().unwrap() as usize;
+    let quantized: Vec<_> = pnts.body[offset..offset + 12]
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes(b.try_into().unwrap()))
+        .collect();
+    assert_eq!(quantized, [0, 0, 0, u16::MAX, u16::MAX, 0]);
+}
+
+fn count_points(dir: &Path, tile: &Value, depth: usize, max_depth: &mut usize) -> u64 {
+    *max_depth = (*max_depth).max(depth);
+    assert_eq!(tile["refine"], "ADD");
+    assert_eq!(tile["boundingVolume"]["box"].as_array().unwrap().len(), 12);
+
+    let mut count = 0;
+    if let Some(uri) = tile["content"]["uri"].as_str() {
+        count += read_pnts(&dir.join(uri)).feature_table["POINTS_LENGTH"]
+            .as_u64()
+            .unwrap();
+    }
+    let children = tile["children"].as_array().cloned().unwrap_or_default();
+    if children.is_empty() {
+        assert_eq!(tile["geometricError"], 0.);
+    }
+    for child in &children {
+        assert!(child["geometricError"].as_f64() < tile["geometricError"].as_f64());
+        count += count_points(dir, child, depth + 1, max_depth);
+    }
+    count
+}
+
+#[test]
+fn test_tileset_write() {
+    let tmp = common::temp_dir();
+    let dir = tmp.path();
+    let pc = grid_cloud(16);
+    let options = TilesetOptions {
+        max_points_per_tile: 500,
+        grid_size: 8,
+        quantize_positions: true,
+        transform: Some(nalgebra::Matrix4::new_translation(&nalgebra::Vector3::new(
+            1., 2., 3.,
+        ))),
+        ..Default::default()
+    };
+
+    tileset_write(&pc, dir, &options).unwrap();
+
+    let tileset: Value =
+        serde_json::from_slice(&std::fs::read(dir.join("tileset.json")).unwrap()).unwrap();
+    assert_eq!(tileset["asset"]["version"], "1.0");
+    assert_eq!(tileset["geometricError"], 15.);
+
+    let root = &tileset["root"];
+    assert_eq!(root["content"]["uri"], "r.pnts");
+    assert_eq!(
+        root["boundingVolume"]["box"],
+        serde_json::json!([7.5, 7.5, 7.5, 7.5, 0.0, 0.0, 0.0, 7.5, 0.0, 0.0, 0.0, 7.5])
+    );
+    let transform: Vec<_> = root["transform"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_f64().unwrap())
+        .collect();
+    assert_eq!(transform[12..15], [1., 2., 3.]);
+
+    let mut depth = 0;
+    assert_eq!(count_points(dir, root, 0, &mut depth), 4096);
+    assert!(depth > 0);
+}
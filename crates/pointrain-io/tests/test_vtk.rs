@@ -0,0 +1,95 @@
+mod common;
+
+use pointrain_core::{
+    pc::{
+        PointCloud, PointCloudBase, PointCloudIntensity, PointCloudIntensityNormal, PointCloudRgba,
+        PointCloudWithAlpha, PointCloudWithColor, PointCloudWithIntensity, PointCloudWithNormal,
+    },
+    point::PointIntensityNormal,
+    types::{Normal, Position},
+};
+use pointrain_io::{ply_read, vtk_read, vtk_write, vtp_read, vtp_write, VtkEncoding};
+
+fn intensity_normal_cloud() -> PointCloudIntensityNormal {
+    let mut pc = PointCloudIntensityNormal::new();
+    for i in 0..4 {
+        let i = i as f32;
+        pc.push(PointIntensityNormal {
+            position: Position::new(i, -i, 0.5 * i),
+            intensity: 0.25 * i,
+            normal: Normal::new(0., i.sin(), i.cos()),
+            curvature: 0.1 * i,
+        });
+    }
+    pc
+}
+
+#[test]
+fn test_vtk_read_field_data() {
+    let pc = vtk_read::<PointCloudIntensityNormal>("tests/data/vtk/test_field.vtk").unwrap();
+
+    assert_eq!(pc.len(), 3);
+    assert_eq!(pc.positions()[2], Position::new(0., 1., 0.5));
+    assert_eq!(pc.intensities(), &[0.25, 0.5, 1.]);
+    assert_eq!(pc.normals()[2], Normal::new(1., 0., 0.));
+}
+
+#[test]
+fn test_vtk_write_read() {
+    let pc = intensity_normal_cloud();
+
+    let tmp = common::temp_dir();
+    for encoding in [VtkEncoding::Ascii, VtkEncoding::Binary] {
+        let path = tmp.path().join(format!("vtk_{encoding:?}.vtk"));
+        vtk_write(&path, &pc, encoding).unwrap();
+        let read = vtk_read::<PointCloudIntensityNormal>(&path).unwrap();
+
+        assert_eq!(read.positions(), pc.positions());
+        assert_eq!(read.intensities(), pc.intensities());
+        assert_eq!(read.normals(), pc.normals());
+        assert_eq!(read.curvatures(), pc.curvatures());
+
+        assert_eq!(vtk_read::<PointCloud>(&path).unwrap().len(), pc.len());
+    }
+}
+
+#[test]
+fn test_vtk_write_read_color() {
+    let pc: PointCloudRgba = ply_read("tests/data/ply/test_color.ply").unwrap();
+
+    let tmp = common::temp_dir();
+    for encoding in [VtkEncoding::Ascii, VtkEncoding::Binary] {
+        let path = tmp.path().join(format!("vtk_color_{encoding:?}.vtk"));
+        vtk_write(&path, &pc, encoding).unwrap();
+        let read = vtk_read::<PointCloudRgba>(&path).unwrap();
+
+        assert_eq!(read.colors(), pc.colors());
+        assert_eq!(read.alphas(), pc.alphas());
+    }
+}
+
+#[test]
+fn test_vtp_write_read() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("vtp_write_read.vtp");
+    let pc = intensity_normal_cloud();
+
+    vtp_write(&path, &pc).unwrap();
+    let read = vtp_read::<PointCloudIntensityNormal>(&path).unwrap();
+
+    assert_eq!(read.positions(), pc.positions());
+    assert_eq!(read.intensities(), pc.intensities());
+    assert_eq!(read.normals(), pc.normals());
+    assert_eq!(read.curvatures(), pc.curvatures());
+
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("vtp_write_read_color.vtp");
+    let pc: PointCloudRgba = ply_read("tests/data/ply/test_color.ply").unwrap();
+
+    vtp_write(&path, &pc).unwrap();
+    let read = vtp_read::<PointCloudRgba>(&path).unwrap();
+
+    assert_eq!(read.colors(), pc.colors());
+    assert_eq!(read.alphas(), pc.alphas());
+    assert!(vtp_read::<PointCloudIntensity>(&path).is_err());
+}
@@ -1,5 +1,17 @@
-use pointrain_core::{pc::PointCloudBase, types::Position};
-use pointrain_io::{xyz_read, PointRainIOError};
+mod common;
+
+use pointrain_core::{
+    pc::{
+        PointCloudBase, PointCloudIntensity, PointCloudRgb, PointCloudRgbNormal,
+        PointCloudWithColor, PointCloudWithIntensity, PointCloudWithNormal,
+    },
+    point::PointRgbNormal,
+    types::{Normal, Position, Rgb},
+};
+use pointrain_io::{
+    xyz_read, xyz_read_with_format, xyz_read_with_options, xyz_write_with_format, PointRainIOError,
+    ReadOptions, XyzFormat,
+};
 
 #[test]
 fn test_xyz_read() {
@@ -16,3 +28,80 @@ fn test_xyz_read_error() {
 
     assert!(matches!(err, PointRainIOError::Error { .. }));
 }
+
+#[test]
+fn test_xyz_read_lenient() {
+    let (pc, report) =
+        xyz_read_with_options("tests/data/xyz/invalid_size.xyz", &ReadOptions::lenient()).unwrap();
+
+    assert_eq!(pc.len(), 1);
+    assert_eq!(pc.positions()[0], Position::new(1., 2., 3.));
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.declared_points, None);
+    assert!(!report.is_clean());
+}
+
+#[test]
+fn test_xyz_read_csv_header() {
+    let (pc, _) = xyz_read_with_format::<PointCloudRgb>(
+        "tests/data/xyz/test_rgb.csv",
+        &XyzFormat::csv(),
+        &ReadOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(pc.len(), 2);
+    assert_eq!(pc.positions()[0], Position::new(0.5, 1.5, 2.5));
+    assert_eq!(pc.colors()[0], Rgb::new(255, 128, 0));
+    assert_eq!(pc.colors()[1], Rgb::new(1, 2, 3));
+}
+
+#[test]
+fn test_xyz_read_columns() {
+    let (pc, _) = xyz_read_with_format::<PointCloudIntensity>(
+        "tests/data/xyz/test_xyzi.txt",
+        &XyzFormat::xyzi(),
+        &ReadOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(pc.len(), 2);
+    assert_eq!(pc.positions()[1], Position::new(4., 5., 6.));
+    assert_eq!(pc.intensities(), &[0.25, 0.5]);
+}
+
+#[test]
+fn test_xyz_write_read() {
+    let tmp = common::temp_dir();
+    let path = tmp.path().join("xyz_write_read.csv");
+    let pc: PointCloudRgbNormal = [
+        PointRgbNormal {
+            position: Position::new(0.1, 0.2, 0.3),
+            color: Rgb::new(10, 20, 30),
+            normal: Normal::new(0., 0., 1.),
+            curvature: 0.5,
+        },
+        PointRgbNormal {
+            position: Position::new(-1., 1e-3, 1e3),
+            color: Rgb::new(255, 0, 1),
+            normal: Normal::new(1., 0., 0.),
+            curvature: 0.,
+        },
+    ]
+    .into_iter()
+    .collect();
+
+    xyz_write_with_format(&path, &pc, &XyzFormat::csv()).unwrap();
+    let (read, report) = xyz_read_with_format::<PointCloudRgbNormal>(
+        &path,
+        &XyzFormat::csv(),
+        &ReadOptions::default(),
+    )
+    .unwrap();
+
+    assert!(report.is_clean());
+    assert_eq!(read.positions(), pc.positions());
+    assert_eq!(read.colors(), pc.colors());
+    assert_eq!(read.normals(), pc.normals());
+    assert_eq!(read.curvatures(), pc.curvatures());
+}
@@ -0,0 +1,131 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use pointrain::{
+    io::{
+        compression::{decode, encode},
+        pcd_read, ply_read,
+        point::{PointReadable, PointWritable},
+        xyz_read_with_format, ReadOptions, XyzFormat,
+    },
+    pc::{PointCloud, PointCloudBase, PointCloudRgb},
+};
+use structopt::StructOpt;
+
+/// Reports the compression ratio and the position error of the octree codec.
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// Input cloud (pcd, ply, or xyz/txt/csv with a header)
+    #[structopt(long, short = "P")]
+    path: PathBuf,
+    /// xyz or xyzrgb
+    #[structopt(long, short, default_value = "xyz")]
+    point: String,
+    /// Voxel sizes to compress with
+    #[structopt(long, short, use_delimiter = true, default_value = "0.001,0.01,0.1")]
+    resolutions: Vec<f64>,
+}
+
+fn xyz_read<PC>(path: &Path) -> anyhow::Result<PC>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let format = XyzFormat::default().with_header(true);
+    Ok(xyz_read_with_format(path, &format, &ReadOptions::default())?.0)
+}
+
+fn bench<PC>(pc: &PC, point_size: usize, resolutions: &[f64]) -> anyhow::Result<()>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable + PointWritable,
+{
+    let raw_size = pc.len() * point_size;
+    println!("{} points, {} bytes uncompressed", pc.len(), raw_size);
+    println!(
+        "{:>12} {:>12} {:>8} {:>12} {:>12} {:>10} {:>10}",
+        "resolution", "bytes", "ratio", "max error", "rms error", "encode ms", "decode ms"
+    );
+
+    for &resolution in resolutions {
+        let start = Instant::now();
+        let data = encode(pc, resolution)?;
+        let encode_time = start.elapsed();
+        let start = Instant::now();
+        let decoded: PC = decode(&data)?;
+        let decode_time = start.elapsed();
+
+        // Every point is within the voxel of a decoded center, on the grid of the centers.
+        let positions = decoded.positions();
+        let first = positions
+            .first()
+            .map_or([0.; 3], |p| p.coords.map(f64::from).into());
+        let cell = |p: [f64; 3]| p.map(|v| v.round() as i64);
+        let grid = |p: [f64; 3]| std::array::from_fn(|a| (p[a] - first[a]) / resolution);
+        let cells: HashSet<_> = positions
+            .iter()
+            .map(|p| cell(grid(p.coords.map(f64::from).into())))
+            .collect();
+
+        let (mut max_error, mut squared_sum) = (0f64, 0.);
+        for p in pc.positions() {
+            let p: [f64; 3] = p.coords.map(f64::from).into();
+            // Points on a voxel boundary may have gone to either side.
+            let g = grid(p);
+            let c = (0..8)
+                .map(|m| std::array::from_fn(|a| (g[a] + [-0.01, 0.01][m >> a & 1]).round() as i64))
+                .find(|c| cells.contains(c))
+                .ok_or_else(|| anyhow::anyhow!("Point {:?} was not decoded", p))?;
+            let squared = (0..3)
+                .map(|a| (p[a] - (first[a] + c[a] as f64 * resolution)).powi(2))
+                .sum::<f64>();
+            max_error = max_error.max(squared.sqrt());
+            squared_sum += squared;
+        }
+
+        println!(
+            "{:>12} {:>12} {:>8.2} {:>12.6} {:>12.6} {:>10.1} {:>10.1}",
+            resolution,
+            data.len(),
+            raw_size as f64 / data.len() as f64,
+            max_error,
+            (squared_sum / pc.len().max(1) as f64).sqrt(),
+            encode_time.as_secs_f64() * 1e3,
+            decode_time.as_secs_f64() * 1e3,
+        );
+    }
+
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+
+    let ext = opt.path.extension().unwrap().to_str().unwrap();
+    if !matches!(ext, "pcd" | "ply" | "xyz" | "txt" | "csv") {
+        panic!("Unknown extension: {}", ext);
+    }
+
+    match opt.point.as_str() {
+        "xyz" => {
+            let pc: PointCloud = match ext {
+                "pcd" => pcd_read(&opt.path)?,
+                "ply" => ply_read(&opt.path)?,
+                _ => xyz_read(&opt.path)?,
+            };
+            bench(&pc, 12, &opt.resolutions)
+        }
+        "xyzrgb" => {
+            let pc: PointCloudRgb = match ext {
+                "pcd" => pcd_read(&opt.path)?,
+                "ply" => ply_read(&opt.path)?,
+                _ => xyz_read(&opt.path)?,
+            };
+            bench(&pc, 15, &opt.resolutions)
+        }
+        v => Err(anyhow::anyhow!("Unknown point type: {}", v)),
+    }
+}
@@ -4,7 +4,7 @@ use pointrain::{
     io::{pcd_read, ply_read},
     pc::{
         PointCloud, PointCloudIntensity, PointCloudIntensityNormal, PointCloudNormal,
-        PointCloudRgb, PointCloudRgbNormal,
+        PointCloudRgb, PointCloudRgbNormal, PointCloudRgba,
     },
 };
 use rerun::RecordingStreamBuilder;
@@ -69,6 +69,14 @@ fn main() -> anyhow::Result<()> {
             };
             rec.log("pointrain", &pc.rerun_points())?;
         }
+        "xyzrgba" => {
+            let pc: PointCloudRgba = match ext {
+                "pcd" => pcd_read(opt.path)?,
+                "ply" => ply_read(opt.path)?,
+                _ => unreachable!(),
+            };
+            rec.log("pointrain", &pc.rerun_points())?;
+        }
         "xyzrgb_normal" => {
             let pc: PointCloudRgbNormal = match ext {
                 "pcd" => pcd_read(opt.path)?,
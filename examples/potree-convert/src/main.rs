@@ -0,0 +1,101 @@
+use std::path::{Path, PathBuf};
+
+use pointrain::{
+    io::{
+        ply_read, point::PointReadable, potree_convert, potree_convert_las, potree_convert_pcd,
+        xyz_read_with_format, PotreeOptions, ReadOptions, XyzFormat,
+    },
+    pc::{PointCloud, PointCloudBase, PointCloudIntensity, PointCloudRgb},
+};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Opt {
+    /// Input cloud (pcd, las, ply, or xyz/txt/csv with a header). PCD and LAS files are
+    /// streamed, the others are read whole.
+    #[structopt(long, short = "P")]
+    path: PathBuf,
+    /// Output directory
+    #[structopt(long, short)]
+    output: PathBuf,
+    /// xyz, xyzi or xyzrgb
+    #[structopt(long, short, default_value = "xyz")]
+    point: String,
+    #[structopt(long, default_value = "20000")]
+    max_points_per_node: usize,
+}
+
+fn xyz_read<PC>(path: &Path) -> anyhow::Result<PC>
+where
+    PC: PointCloudBase,
+    PC::Point: PointReadable,
+{
+    let format = XyzFormat::default().with_header(true);
+    Ok(xyz_read_with_format(path, &format, &ReadOptions::default())?.0)
+}
+
+fn main() -> anyhow::Result<()> {
+    let opt = Opt::from_args();
+
+    let ext = opt
+        .path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .ok_or_else(|| anyhow::anyhow!("No extension: {}", opt.path.display()))?;
+    if !matches!(ext, "pcd" | "las" | "ply" | "xyz" | "txt" | "csv") {
+        return Err(anyhow::anyhow!("Unknown extension: {}", ext));
+    }
+
+    let options = PotreeOptions {
+        name: opt.path.file_stem().unwrap().to_string_lossy().into(),
+        max_points_per_node: opt.max_points_per_node,
+        ..Default::default()
+    };
+
+    if ext == "pcd" {
+        match opt.point.as_str() {
+            "xyz" => potree_convert_pcd::<PointCloud>(&opt.path, &opt.output, &options)?,
+            "xyzi" => potree_convert_pcd::<PointCloudIntensity>(&opt.path, &opt.output, &options)?,
+            "xyzrgb" => potree_convert_pcd::<PointCloudRgb>(&opt.path, &opt.output, &options)?,
+            v => return Err(anyhow::anyhow!("Unknown point type: {}", v)),
+        }
+        return Ok(());
+    }
+
+    if ext == "las" {
+        match opt.point.as_str() {
+            "xyz" => potree_convert_las::<PointCloud>(&opt.path, &opt.output, &options)?,
+            "xyzi" => potree_convert_las::<PointCloudIntensity>(&opt.path, &opt.output, &options)?,
+            "xyzrgb" => potree_convert_las::<PointCloudRgb>(&opt.path, &opt.output, &options)?,
+            v => return Err(anyhow::anyhow!("Unknown point type: {}", v)),
+        }
+        return Ok(());
+    }
+
+    match opt.point.as_str() {
+        "xyz" => {
+            let pc: PointCloud = match ext {
+                "ply" => ply_read(&opt.path)?,
+                _ => xyz_read(&opt.path)?,
+            };
+            potree_convert(&pc, &opt.output, &options)?;
+        }
+        "xyzi" => {
+            let pc: PointCloudIntensity = match ext {
+                "ply" => panic!("ply not supported for xyzi"),
+                _ => xyz_read(&opt.path)?,
+            };
+            potree_convert(&pc, &opt.output, &options)?;
+        }
+        "xyzrgb" => {
+            let pc: PointCloudRgb = match ext {
+                "ply" => ply_read(&opt.path)?,
+                _ => xyz_read(&opt.path)?,
+            };
+            potree_convert(&pc, &opt.output, &options)?;
+        }
+        v => return Err(anyhow::anyhow!("Unknown point type: {}", v)),
+    }
+
+    Ok(())
+}